@@ -3,7 +3,7 @@ use clap_complete::Shell;
 use std::path::PathBuf;
 
 use super::commands;
-use crate::core::Mode;
+use crate::core::{Layout, Mode, ScriptStyle};
 
 #[derive(Parser)]
 #[command(
@@ -32,10 +32,14 @@ pub struct GlobalOptions {
     )]
     pub server: String,
 
-    /// Display mode (affects theme and background)
-    #[arg(long, default_value = "dark", global = true)]
+    /// Display mode (affects theme and background); auto follows the terminal
+    #[arg(long, default_value = "auto", global = true)]
     pub mode: Mode,
 
+    /// Layout engine (elk helps large flowcharts avoid overlaps)
+    #[arg(long, global = true)]
+    pub layout: Option<Layout>,
+
     /// Diagram theme
     #[arg(short, long, default_value = "default", global = true)]
     pub theme: String,
@@ -52,13 +56,19 @@ pub struct GlobalOptions {
     #[arg(long, global = true)]
     pub clipboard: bool,
 
-    /// Open result in default browser
+    /// Open result in the default viewer (temp file unless --output is given)
     #[arg(long, global = true)]
     pub open: bool,
 
-    /// Output format
-    #[arg(short, long, default_value = "svg", global = true)]
-    pub format: OutputFormat,
+    /// Output format; comma-separate to emit several (e.g. svg,png)
+    #[arg(
+        short,
+        long,
+        default_value = "svg",
+        value_delimiter = ',',
+        global = true
+    )]
+    pub format: Vec<OutputFormat>,
 
     /// Output width in pixels
     #[arg(long, global = true)]
@@ -68,7 +78,7 @@ pub struct GlobalOptions {
     #[arg(long, global = true)]
     pub height: Option<u32>,
 
-    /// Scale factor (0.1 to 3.0)
+    /// Scale factor (1 to 3); requires --width or --height
     #[arg(long, global = true)]
     pub scale: Option<f32>,
 
@@ -77,6 +87,50 @@ pub struct GlobalOptions {
     #[arg(long, global = true)]
     pub background_color: Option<String>,
 
+    /// Transparent PNG background (ignores the --mode background)
+    #[arg(long, global = true, conflicts_with = "background_color")]
+    pub transparent: bool,
+
+    /// Fail on unrecognized shape/type/risk tokens instead of using defaults
+    #[arg(long, global = true)]
+    pub strict: bool,
+
+    /// Format of --input/--stdin definitions; overrides the extension or content guess
+    #[arg(long, value_enum, global = true)]
+    pub input_format: Option<InputFormat>,
+
+    /// Mermaid config file (JSON/YAML/TOML) with theme and themeVariables
+    #[arg(long, value_name = "FILE", global = true)]
+    pub config_file: Option<PathBuf>,
+
+    /// Theme variable as key=value (e.g. primaryColor=#ff0000); repeatable
+    #[arg(long = "theme-var", value_name = "KEY=VALUE", global = true)]
+    pub theme_var: Vec<String>,
+
+    /// How theme/config is embedded in the script
+    #[arg(long, value_enum, default_value = "init-directive", global = true)]
+    pub config_style: ScriptStyle,
+
+    /// CSS file injected into SVG output as a <style> block
+    #[arg(long, value_name = "FILE", global = true)]
+    pub svg_css: Option<PathBuf>,
+
+    /// Make SVG output scale to its container (width="100%", keeps viewBox)
+    #[arg(long, global = true)]
+    pub responsive: bool,
+
+    /// Re-indent mermaid output (default keeps each diagram's native indentation)
+    #[arg(long, global = true)]
+    pub indent: Option<IndentKind>,
+
+    /// Spaces per level with --indent spaces
+    #[arg(long, default_value_t = 4, global = true)]
+    pub indent_width: usize,
+
+    /// What to do when rendering fails: fail, or write a placeholder SVG with the error
+    #[arg(long, value_enum, default_value = "fail", global = true)]
+    pub on_error: OnError,
+
     /// Suppress non-error output
     #[arg(short, long, global = true)]
     pub quiet: bool,
@@ -86,7 +140,53 @@ pub struct GlobalOptions {
     pub verbose: u8,
 }
 
-#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum IndentKind {
+    Spaces,
+    Tabs,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OnError {
+    /// Abort with the render error
+    #[default]
+    Fail,
+    /// Write an SVG showing the error instead (SVG output only)
+    Placeholder,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum InputFormat {
+    Json,
+    Yaml,
+    Toml,
+    /// Raw mermaid syntax
+    Mermaid,
+}
+
+impl InputFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Yaml => "yaml",
+            Self::Toml => "toml",
+            Self::Mermaid => "mermaid",
+        }
+    }
+
+    /// Format for a file extension (case-insensitive); `.yml` counts as yaml, `.mmd` as mermaid
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "toml" => Some(Self::Toml),
+            "mmd" | "mermaid" => Some(Self::Mermaid),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
 pub enum OutputFormat {
     #[default]
     Svg,
@@ -102,6 +202,24 @@ impl OutputFormat {
             Self::Mermaid => "mmd",
         }
     }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Svg => "image/svg+xml",
+            Self::Png => "image/png",
+            Self::Mermaid => "text/plain",
+        }
+    }
+
+    /// Format for a file extension (case-insensitive); `.mermaid` counts as mermaid
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_lowercase().as_str() {
+            "svg" => Some(Self::Svg),
+            "png" => Some(Self::Png),
+            "mmd" | "mermaid" => Some(Self::Mermaid),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -119,6 +237,9 @@ pub enum Commands {
     /// Generate a user journey diagram
     Journey(commands::journey::JourneyArgs),
 
+    /// List accepted spec tokens (diagram types, shapes, message types, ...)
+    List(commands::list::ListArgs),
+
     /// Generate a mindmap diagram
     Mindmap(commands::mindmap::MindmapArgs),
 
@@ -131,6 +252,10 @@ pub enum Commands {
     /// Generate a requirement diagram
     Requirement(commands::requirement::RequirementArgs),
 
+    /// Print the JSON Schema for a diagram type's spec file
+    #[cfg(feature = "schema")]
+    Schema(commands::schema::SchemaArgs),
+
     /// Generate a sequence diagram
     Sequence(commands::sequence::SequenceArgs),
 
@@ -152,7 +277,7 @@ pub struct InputOptions {
     #[arg(short, long)]
     pub input: Option<PathBuf>,
 
-    /// Read diagram definition from stdin (JSON/YAML)
+    /// Read diagram definition from stdin (JSON/YAML, or any --input-format)
     #[arg(long)]
     pub stdin: bool,
 
@@ -167,3 +292,43 @@ impl InputOptions {
         self.input.is_some() || self.stdin || self.mermaid.is_some()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_format_extension_and_mime() {
+        assert_eq!(OutputFormat::Svg.extension(), "svg");
+        assert_eq!(OutputFormat::Svg.mime_type(), "image/svg+xml");
+        assert_eq!(OutputFormat::Png.extension(), "png");
+        assert_eq!(OutputFormat::Png.mime_type(), "image/png");
+        assert_eq!(OutputFormat::Mermaid.extension(), "mmd");
+        assert_eq!(OutputFormat::Mermaid.mime_type(), "text/plain");
+    }
+
+    #[test]
+    fn output_format_from_extension() {
+        for format in [OutputFormat::Svg, OutputFormat::Png, OutputFormat::Mermaid] {
+            assert_eq!(
+                OutputFormat::from_extension(format.extension()),
+                Some(format)
+            );
+        }
+        assert_eq!(
+            OutputFormat::from_extension("MERMAID"),
+            Some(OutputFormat::Mermaid)
+        );
+        assert_eq!(OutputFormat::from_extension("pdf"), None);
+    }
+
+    #[test]
+    fn input_format_from_extension() {
+        assert_eq!(InputFormat::from_extension("YML"), Some(InputFormat::Yaml));
+        assert_eq!(
+            InputFormat::from_extension("mmd"),
+            Some(InputFormat::Mermaid)
+        );
+        assert_eq!(InputFormat::from_extension("svg"), None);
+    }
+}
@@ -1,11 +1,13 @@
 use clap::Parser;
 
-use crate::cli::{GlobalOptions, InputOptions, OutputFormat, OutputHandler};
-use crate::core::{Config, Diagram, MermaidError};
+use crate::cli::commands::{
+    check_valid, parse_input, parse_spec, read_input, resolve_token, run_render,
+};
+use crate::cli::{GlobalOptions, InputFormat, InputOptions};
+use crate::core::MermaidError;
 use crate::diagrams::er::{
     Attribute, AttributeKey, AttributeType, Cardinality, ERDiagram, Entity, Relationship,
 };
-use crate::render::{MermaidClient, RenderOptions};
 
 #[derive(Parser, Debug)]
 pub struct ERArgs {
@@ -23,75 +25,26 @@ pub struct ERArgs {
     /// Diagram title
     #[arg(long)]
     pub title: Option<String>,
+
+    /// Keep unrecognized attribute types (e.g. jsonb) verbatim instead of defaulting to string
+    #[arg(long)]
+    pub allow_custom_types: bool,
 }
 
 pub async fn run(args: ERArgs, global: &GlobalOptions) -> Result<(), MermaidError> {
-    let mut diagram = build_diagram(&args).await?;
-
-    // Apply mode's theme to diagram config
-    let config = diagram.config.get_or_insert_with(Config::default);
-    config.theme = global.mode.theme();
-
-    let render_options = RenderOptions {
-        width: global.width,
-        height: global.height,
-        scale: global.scale,
-        background_color: global
-            .background_color
-            .clone()
-            .or_else(|| global.mode.background_color().map(String::from)),
-    };
-
-    let output_handler = OutputHandler::new(
-        global.output.clone(),
-        global.stdout,
-        global.clipboard,
-        global.open,
-    );
-
-    if matches!(global.format, OutputFormat::Mermaid) {
-        let script = diagram.build_script();
-        output_handler.write_mermaid(&script).await?;
-        return Ok(());
-    }
-
-    let client = MermaidClient::new(Some(global.server.clone()));
-
-    match global.format {
-        OutputFormat::Svg => {
-            let svg = client.render_svg(&diagram, &render_options).await?;
-            output_handler.write_svg(&svg).await?;
-        }
-        OutputFormat::Png => {
-            let png = client.render_png(&diagram, &render_options).await?;
-            output_handler.write_png(&png).await?;
-        }
-        OutputFormat::Mermaid => unreachable!(),
-    }
+    let diagram = build_diagram(&args, global.strict, global.input_format).await?;
+    check_valid(diagram.validate(), global.strict)?;
 
-    Ok(())
+    run_render(diagram, |diagram| &mut diagram.config, global).await
 }
 
-async fn build_diagram(args: &ERArgs) -> Result<ERDiagram, MermaidError> {
-    if let Some(path) = &args.input.input {
-        let content = tokio::fs::read_to_string(path).await?;
-        let ext = path
-            .extension()
-            .and_then(std::ffi::OsStr::to_str)
-            .unwrap_or("yaml");
-        return parse_diagram(&content, ext);
-    }
-
-    if args.input.stdin {
-        use tokio::io::AsyncReadExt;
-        let mut buffer = String::new();
-        tokio::io::stdin().read_to_string(&mut buffer).await?;
-        let ext = if buffer.trim_start().starts_with('{') {
-            "json"
-        } else {
-            "yaml"
-        };
-        return parse_diagram(&buffer, ext);
+async fn build_diagram(
+    args: &ERArgs,
+    strict: bool,
+    input_format: Option<InputFormat>,
+) -> Result<ERDiagram, MermaidError> {
+    if let Some((content, format)) = read_input(&args.input, input_format).await? {
+        return parse_input(&content, &format, parse_diagram);
     }
 
     if let Some(mermaid_str) = &args.input.mermaid {
@@ -107,13 +60,13 @@ async fn build_diagram(args: &ERArgs) -> Result<ERDiagram, MermaidError> {
 
     // Parse entities
     for entity_spec in &args.entity {
-        let entity = parse_entity_spec(entity_spec)?;
+        let entity = parse_entity_spec(entity_spec, strict, args.allow_custom_types)?;
         builder = builder.entity(entity);
     }
 
     // Parse relationships
     for rel_spec in &args.relationship {
-        let rel = parse_relationship_spec(rel_spec)?;
+        let rel = parse_relationship_spec(rel_spec, strict)?;
         builder = builder.relationship(rel);
     }
 
@@ -121,19 +74,11 @@ async fn build_diagram(args: &ERArgs) -> Result<ERDiagram, MermaidError> {
 }
 
 fn parse_diagram(content: &str, format: &str) -> Result<ERDiagram, MermaidError> {
-    match format.to_lowercase().as_str() {
-        "json" => ERDiagram::from_json(content),
-        "yaml" | "yml" => ERDiagram::from_yaml(content),
-        "toml" => ERDiagram::from_toml(content),
-        _ => Err(MermaidError::InvalidInput(format!(
-            "Unsupported format: {}",
-            format
-        ))),
-    }
+    parse_spec(content, format, ERDiagram::from_raw_mermaid)
 }
 
-fn parse_entity_spec(spec: &str) -> Result<Entity, MermaidError> {
-    // Format: "name" or "name:attr1:type,attr2:type:PK"
+fn parse_entity_spec(spec: &str, strict: bool, allow_custom: bool) -> Result<Entity, MermaidError> {
+    // Format: "name" or "name:attr1:type,attr2:type(255):PK"
     let parts: Vec<&str> = spec.splitn(2, ':').collect();
     let name = parts[0].trim().to_string();
 
@@ -148,19 +93,35 @@ fn parse_entity_spec(spec: &str) -> Result<Entity, MermaidError> {
             }
 
             let attr_name = attr_parts[0].trim();
-            let attr_type = if attr_parts.len() > 1 {
-                AttributeType::parse(attr_parts[1].trim()).unwrap_or_default()
+            let (attr_type, length) = if attr_parts.len() > 1 {
+                let (token, length) = split_type_length(attr_parts[1].trim());
+                let attr_type = if allow_custom {
+                    AttributeType::parse_or_custom(token)
+                } else {
+                    resolve_token(AttributeType::parse(token), "attribute type", token, strict)?
+                        .unwrap_or_default()
+                };
+                (attr_type, length)
             } else {
-                AttributeType::default()
+                (AttributeType::default(), None)
             };
 
             let mut attr = Attribute::new(attr_type, attr_name);
+            if let Some(length) = length {
+                attr = attr.with_length(length);
+            }
 
             if attr_parts.len() > 2 {
                 let key = match attr_parts[2].trim().to_uppercase().as_str() {
                     "PK" => AttributeKey::PrimaryKey,
                     "FK" => AttributeKey::ForeignKey,
                     "UK" => AttributeKey::UniqueKey,
+                    _ if strict => {
+                        return Err(MermaidError::InvalidInput(format!(
+                            "Unknown attribute key '{}'",
+                            attr_parts[2].trim()
+                        )))
+                    }
                     _ => AttributeKey::None,
                 };
                 attr = attr.with_key(key);
@@ -173,7 +134,16 @@ fn parse_entity_spec(spec: &str) -> Result<Entity, MermaidError> {
     Ok(entity)
 }
 
-fn parse_relationship_spec(spec: &str) -> Result<Relationship, MermaidError> {
+/// Split a `type(length)` token, e.g. "string(255)" into ("string", Some(255))
+fn split_type_length(token: &str) -> (&str, Option<u32>) {
+    token
+        .strip_suffix(')')
+        .and_then(|rest| rest.split_once('('))
+        .and_then(|(base, length)| Some((base.trim(), length.trim().parse().ok()?)))
+        .map_or((token, None), |(base, length)| (base, Some(length)))
+}
+
+fn parse_relationship_spec(spec: &str, strict: bool) -> Result<Relationship, MermaidError> {
     // Format: "from->to:type:label"
     let arrow_pos = spec.find("->").ok_or_else(|| {
         MermaidError::InvalidInput(format!(
@@ -205,6 +175,12 @@ fn parse_relationship_spec(spec: &str) -> Result<Relationship, MermaidError> {
             "one-to-many" | "1:n" | "1:m" => (Cardinality::ExactlyOne, Cardinality::ZeroOrMore),
             "many-to-one" | "n:1" | "m:1" => (Cardinality::ZeroOrMore, Cardinality::ExactlyOne),
             "many-to-many" | "n:n" | "m:m" => (Cardinality::ZeroOrMore, Cardinality::ZeroOrMore),
+            _ if strict => {
+                return Err(MermaidError::InvalidInput(format!(
+                    "Unknown relationship type '{}'",
+                    parts[1].trim()
+                )))
+            }
             _ => (Cardinality::ExactlyOne, Cardinality::ZeroOrMore),
         };
         rel = rel.with_cardinality(from_card, to_card);
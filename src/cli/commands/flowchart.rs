@@ -1,20 +1,24 @@
 use clap::Parser;
 
-use crate::cli::{GlobalOptions, InputOptions, OutputFormat, OutputHandler};
-use crate::core::{Config, Diagram, Direction, MermaidError};
+use crate::cli::commands::{
+    check_valid, parse_input, parse_spec, read_input, resolve_shape, resolve_token, run_render,
+    split_spec,
+};
+use crate::cli::{GlobalOptions, InputFormat, InputOptions};
+use crate::core::{Direction, MermaidError};
 use crate::diagrams::flowchart::{FlowChart, Link, LinkStyle, Node, NodeShape, Subgraph};
-use crate::render::{MermaidClient, RenderOptions};
 
 #[derive(Parser, Debug)]
 pub struct FlowchartArgs {
     #[command(flatten)]
     pub input: InputOptions,
 
-    /// Add node: "id:label:shape" (shape optional)
+    /// Add node: "id:label:shape:class" or "id:label:::class" (shape and class optional;
+    /// write a literal colon as \:)
     #[arg(short, long, value_name = "SPEC")]
     pub node: Vec<String>,
 
-    /// Add link: "from->to:style:label" (style/label optional)
+    /// Add link: "from->to:style:label" (style/label optional; \: is a literal colon)
     #[arg(short, long, value_name = "SPEC")]
     pub link: Vec<String>,
 
@@ -32,72 +36,19 @@ pub struct FlowchartArgs {
 }
 
 pub async fn run(args: FlowchartArgs, global: &GlobalOptions) -> Result<(), MermaidError> {
-    let mut chart = build_chart(&args).await?;
-
-    // Apply mode's theme to diagram config
-    let config = chart.config.get_or_insert_with(Config::default);
-    config.theme = global.mode.theme();
-
-    let render_options = RenderOptions {
-        width: global.width,
-        height: global.height,
-        scale: global.scale,
-        background_color: global
-            .background_color
-            .clone()
-            .or_else(|| global.mode.background_color().map(String::from)),
-    };
-
-    let output_handler = OutputHandler::new(
-        global.output.clone(),
-        global.stdout,
-        global.clipboard,
-        global.open,
-    );
-
-    if matches!(global.format, OutputFormat::Mermaid) {
-        let script = chart.build_script();
-        output_handler.write_mermaid(&script).await?;
-        return Ok(());
-    }
-
-    let client = MermaidClient::new(Some(global.server.clone()));
-
-    match global.format {
-        OutputFormat::Svg => {
-            let svg = client.render_svg(&chart, &render_options).await?;
-            output_handler.write_svg(&svg).await?;
-        }
-        OutputFormat::Png => {
-            let png = client.render_png(&chart, &render_options).await?;
-            output_handler.write_png(&png).await?;
-        }
-        OutputFormat::Mermaid => unreachable!(),
-    }
+    let chart = build_chart(&args, global.strict, global.input_format).await?;
+    check_valid(chart.validate(), global.strict)?;
 
-    Ok(())
+    run_render(chart, |chart| &mut chart.config, global).await
 }
 
-async fn build_chart(args: &FlowchartArgs) -> Result<FlowChart, MermaidError> {
-    if let Some(path) = &args.input.input {
-        let content = tokio::fs::read_to_string(path).await?;
-        let ext = path
-            .extension()
-            .and_then(std::ffi::OsStr::to_str)
-            .unwrap_or("yaml");
-        return parse_chart(&content, ext);
-    }
-
-    if args.input.stdin {
-        use tokio::io::AsyncReadExt;
-        let mut buffer = String::new();
-        tokio::io::stdin().read_to_string(&mut buffer).await?;
-        let ext = if buffer.trim_start().starts_with('{') {
-            "json"
-        } else {
-            "yaml"
-        };
-        return parse_chart(&buffer, ext);
+async fn build_chart(
+    args: &FlowchartArgs,
+    strict: bool,
+    input_format: Option<InputFormat>,
+) -> Result<FlowChart, MermaidError> {
+    if let Some((content, format)) = read_input(&args.input, input_format).await? {
+        return parse_input(&content, &format, parse_chart);
     }
 
     if let Some(mermaid_str) = &args.input.mermaid {
@@ -123,13 +74,13 @@ async fn build_chart(args: &FlowchartArgs) -> Result<FlowChart, MermaidError> {
 
     // Parse nodes
     for node_spec in &args.node {
-        let node = parse_node_spec(node_spec)?;
+        let node = parse_node_spec(node_spec, strict)?;
         builder = builder.node(node);
     }
 
     // Parse links
     for link_spec in &args.link {
-        let link = parse_link_spec(link_spec)?;
+        let link = parse_link_spec(link_spec, strict)?;
         builder = builder.link(link);
     }
 
@@ -143,22 +94,20 @@ async fn build_chart(args: &FlowchartArgs) -> Result<FlowChart, MermaidError> {
 }
 
 fn parse_chart(content: &str, format: &str) -> Result<FlowChart, MermaidError> {
-    match format.to_lowercase().as_str() {
-        "json" => FlowChart::from_json(content),
-        "yaml" | "yml" => FlowChart::from_yaml(content),
-        "toml" => FlowChart::from_toml(content),
-        _ => Err(MermaidError::InvalidInput(format!(
-            "Unsupported format: {}",
-            format
-        ))),
-    }
+    parse_spec(content, format, FlowChart::from_raw_mermaid)
 }
 
-fn parse_node_spec(spec: &str) -> Result<Node, MermaidError> {
-    let parts: Vec<&str> = spec.splitn(3, ':').collect();
+fn parse_node_spec(spec: &str, strict: bool) -> Result<Node, MermaidError> {
+    // A `:::class` suffix works like mermaid's own shorthand
+    let (spec_body, suffix_class) = match spec.rsplit_once(":::") {
+        Some((body, class)) if !class.is_empty() && !class.contains(':') => (body, Some(class)),
+        _ => (spec, None),
+    };
+
+    let parts = split_spec(spec_body, 4);
     if parts.is_empty() {
         return Err(MermaidError::InvalidInput(format!(
-            "Invalid node spec '{}'. Expected format: 'id:label:shape'",
+            "Invalid node spec '{}'. Expected format: 'id:label:shape:class'",
             spec
         )));
     }
@@ -169,16 +118,22 @@ fn parse_node_spec(spec: &str) -> Result<Node, MermaidError> {
     } else {
         id.clone()
     };
-    let shape = if parts.len() > 2 {
-        NodeShape::parse(parts[2].trim()).unwrap_or_default()
-    } else {
-        NodeShape::default()
-    };
+    let mut node = Node::simple(id, label);
+    if parts.len() > 2 && !parts[2].trim().is_empty() {
+        let token = parts[2].trim();
+        if let Some(shape) = resolve_shape(NodeShape::parse(token), token, strict)? {
+            node = node.with_shape(shape);
+        }
+    }
+    let class = suffix_class.or_else(|| parts.get(3).map(|class| class.trim()));
+    if let Some(class) = class.filter(|class| !class.is_empty()) {
+        node = node.with_class(class);
+    }
 
-    Ok(Node::new(id, label, shape))
+    Ok(node)
 }
 
-fn parse_link_spec(spec: &str) -> Result<Link, MermaidError> {
+fn parse_link_spec(spec: &str, strict: bool) -> Result<Link, MermaidError> {
     // Format: "from->to:style:label"
     let arrow_pos = spec.find("->").ok_or_else(|| {
         MermaidError::InvalidInput(format!(
@@ -187,10 +142,11 @@ fn parse_link_spec(spec: &str) -> Result<Link, MermaidError> {
         ))
     })?;
 
-    let from = spec[..arrow_pos].trim().to_string();
+    // A single part: just unescape `\:`
+    let from = split_spec(&spec[..arrow_pos], 1)[0].trim().to_string();
     let rest = &spec[arrow_pos + 2..];
 
-    let parts: Vec<&str> = rest.splitn(3, ':').collect();
+    let parts = split_spec(rest, 3);
     if parts.is_empty() {
         return Err(MermaidError::InvalidInput(format!(
             "Invalid link spec '{}'. Expected format: 'from->to:style:label'",
@@ -202,7 +158,8 @@ fn parse_link_spec(spec: &str) -> Result<Link, MermaidError> {
     let mut link = Link::new(from, to);
 
     if parts.len() > 1 && !parts[1].is_empty() {
-        if let Some(style) = LinkStyle::parse(parts[1].trim()) {
+        let token = parts[1].trim();
+        if let Some(style) = resolve_token(LinkStyle::parse(token), "link style", token, strict)? {
             link = link.with_style(style);
         }
     }
@@ -1,9 +1,9 @@
 use clap::Parser;
 
-use crate::cli::{GlobalOptions, InputOptions, OutputFormat, OutputHandler};
-use crate::core::{Config, Diagram, MermaidError};
-use crate::diagrams::journey::Journey;
-use crate::render::{MermaidClient, RenderOptions};
+use crate::cli::commands::{check_valid, parse_input, parse_spec, read_input, run_render};
+use crate::cli::{GlobalOptions, InputFormat, InputOptions};
+use crate::core::MermaidError;
+use crate::diagrams::journey::{Journey, Task};
 
 #[derive(Parser, Debug)]
 pub struct JourneyArgs {
@@ -24,72 +24,18 @@ pub struct JourneyArgs {
 }
 
 pub async fn run(args: JourneyArgs, global: &GlobalOptions) -> Result<(), MermaidError> {
-    let mut diagram = build_diagram(&args).await?;
-
-    // Apply mode's theme to diagram config
-    let config = diagram.config.get_or_insert_with(Config::default);
-    config.theme = global.mode.theme();
-
-    let render_options = RenderOptions {
-        width: global.width,
-        height: global.height,
-        scale: global.scale,
-        background_color: global
-            .background_color
-            .clone()
-            .or_else(|| global.mode.background_color().map(String::from)),
-    };
-
-    let output_handler = OutputHandler::new(
-        global.output.clone(),
-        global.stdout,
-        global.clipboard,
-        global.open,
-    );
-
-    if matches!(global.format, OutputFormat::Mermaid) {
-        let script = diagram.build_script();
-        output_handler.write_mermaid(&script).await?;
-        return Ok(());
-    }
-
-    let client = MermaidClient::new(Some(global.server.clone()));
-
-    match global.format {
-        OutputFormat::Svg => {
-            let svg = client.render_svg(&diagram, &render_options).await?;
-            output_handler.write_svg(&svg).await?;
-        }
-        OutputFormat::Png => {
-            let png = client.render_png(&diagram, &render_options).await?;
-            output_handler.write_png(&png).await?;
-        }
-        OutputFormat::Mermaid => unreachable!(),
-    }
+    let diagram = build_diagram(&args, global.input_format).await?;
+    check_valid(diagram.validate(), global.strict)?;
 
-    Ok(())
+    run_render(diagram, |diagram| &mut diagram.config, global).await
 }
 
-async fn build_diagram(args: &JourneyArgs) -> Result<Journey, MermaidError> {
-    if let Some(path) = &args.input.input {
-        let content = tokio::fs::read_to_string(path).await?;
-        let ext = path
-            .extension()
-            .and_then(std::ffi::OsStr::to_str)
-            .unwrap_or("yaml");
-        return parse_diagram(&content, ext);
-    }
-
-    if args.input.stdin {
-        use tokio::io::AsyncReadExt;
-        let mut buffer = String::new();
-        tokio::io::stdin().read_to_string(&mut buffer).await?;
-        let ext = if buffer.trim_start().starts_with('{') {
-            "json"
-        } else {
-            "yaml"
-        };
-        return parse_diagram(&buffer, ext);
+async fn build_diagram(
+    args: &JourneyArgs,
+    input_format: Option<InputFormat>,
+) -> Result<Journey, MermaidError> {
+    if let Some((content, format)) = read_input(&args.input, input_format).await? {
+        return parse_input(&content, &format, parse_diagram);
     }
 
     if let Some(mermaid_str) = &args.input.mermaid {
@@ -131,15 +77,7 @@ async fn build_diagram(args: &JourneyArgs) -> Result<Journey, MermaidError> {
 }
 
 fn parse_diagram(content: &str, format: &str) -> Result<Journey, MermaidError> {
-    match format.to_lowercase().as_str() {
-        "json" => Journey::from_json(content),
-        "yaml" | "yml" => Journey::from_yaml(content),
-        "toml" => Journey::from_toml(content),
-        _ => Err(MermaidError::InvalidInput(format!(
-            "Unsupported format: {}",
-            format
-        ))),
-    }
+    parse_spec(content, format, Journey::from_raw_mermaid)
 }
 
 fn parse_task_spec(spec: &str) -> Result<(String, u8, Vec<String>), MermaidError> {
@@ -157,6 +95,7 @@ fn parse_task_spec(spec: &str) -> Result<(String, u8, Vec<String>), MermaidError
         .trim()
         .parse()
         .map_err(|_| MermaidError::InvalidInput(format!("Invalid score in task spec: {}", spec)))?;
+    let score = Task::check_score(score)?;
 
     let actors = if parts.len() > 2 && !parts[2].is_empty() {
         parts[2].split(',').map(|s| s.trim().to_string()).collect()
@@ -0,0 +1,86 @@
+use clap::{Parser, ValueEnum};
+
+use crate::core::{DiagramKind, MermaidError};
+use crate::diagrams::er::Cardinality;
+use crate::diagrams::flowchart::NodeShape;
+use crate::diagrams::requirement::Risk;
+use crate::diagrams::sequence::MessageType;
+
+#[derive(Parser, Debug)]
+pub struct ListArgs {
+    /// What to list
+    #[arg(value_enum)]
+    pub topic: ListTopic,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListTopic {
+    /// Diagram types (subcommand names)
+    DiagramTypes,
+    /// Flowchart node shapes
+    Shapes,
+    /// Sequence message types
+    MessageTypes,
+    /// ER relationship cardinalities
+    Cardinalities,
+    /// Requirement risk levels
+    Risks,
+}
+
+pub fn run(args: ListArgs) -> Result<(), MermaidError> {
+    for line in topic_lines(args.topic) {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// One line per value, listing its canonical token followed by any aliases
+pub fn topic_lines(topic: ListTopic) -> Vec<String> {
+    match topic {
+        ListTopic::DiagramTypes => DiagramKind::ALL
+            .iter()
+            .map(|kind| kind.as_str().to_string())
+            .collect(),
+        ListTopic::Shapes => alias_lines(NodeShape::ALIASES),
+        ListTopic::MessageTypes => alias_lines(MessageType::ALIASES),
+        ListTopic::Cardinalities => alias_lines(Cardinality::ALIASES),
+        ListTopic::Risks => alias_lines(Risk::ALIASES),
+    }
+}
+
+/// Group an alias table by value, keeping first-seen order
+fn alias_lines<T: PartialEq>(aliases: &[(&str, T)]) -> Vec<String> {
+    let mut groups: Vec<(&T, Vec<&str>)> = Vec::new();
+    for (alias, value) in aliases {
+        match groups.iter_mut().find(|(v, _)| *v == value) {
+            Some((_, names)) => names.push(alias),
+            None => groups.push((value, vec![alias])),
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(_, names)| names.join(", "))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes_group_aliases() {
+        let lines = topic_lines(ListTopic::Shapes);
+        assert_eq!(lines[0], "rectangle, rect");
+        assert!(lines.contains(&"rhombus, diamond, decision".to_string()));
+    }
+
+    #[test]
+    fn every_alias_parses() {
+        for (alias, shape) in NodeShape::ALIASES {
+            assert_eq!(NodeShape::parse(alias), Some(*shape));
+        }
+        for (alias, risk) in Risk::ALIASES {
+            assert_eq!(Risk::parse(alias), Some(*risk));
+        }
+    }
+}
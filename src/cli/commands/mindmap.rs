@@ -1,9 +1,9 @@
 use clap::Parser;
 
-use crate::cli::{GlobalOptions, InputOptions, OutputFormat, OutputHandler};
-use crate::core::{Config, Diagram, MermaidError};
+use crate::cli::commands::{parse_input, parse_spec, read_input, resolve_shape, run_render};
+use crate::cli::{GlobalOptions, InputFormat, InputOptions};
+use crate::core::MermaidError;
 use crate::diagrams::mindmap::{Mindmap, MindmapNodeShape};
-use crate::render::{MermaidClient, RenderOptions};
 
 #[derive(Parser, Debug)]
 pub struct MindmapArgs {
@@ -28,72 +28,18 @@ pub struct MindmapArgs {
 }
 
 pub async fn run(args: MindmapArgs, global: &GlobalOptions) -> Result<(), MermaidError> {
-    let mut diagram = build_diagram(&args).await?;
-
-    // Apply mode's theme to diagram config
-    let config = diagram.config.get_or_insert_with(Config::default);
-    config.theme = global.mode.theme();
-
-    let render_options = RenderOptions {
-        width: global.width,
-        height: global.height,
-        scale: global.scale,
-        background_color: global
-            .background_color
-            .clone()
-            .or_else(|| global.mode.background_color().map(String::from)),
-    };
-
-    let output_handler = OutputHandler::new(
-        global.output.clone(),
-        global.stdout,
-        global.clipboard,
-        global.open,
-    );
-
-    if matches!(global.format, OutputFormat::Mermaid) {
-        let script = diagram.build_script();
-        output_handler.write_mermaid(&script).await?;
-        return Ok(());
-    }
-
-    let client = MermaidClient::new(Some(global.server.clone()));
-
-    match global.format {
-        OutputFormat::Svg => {
-            let svg = client.render_svg(&diagram, &render_options).await?;
-            output_handler.write_svg(&svg).await?;
-        }
-        OutputFormat::Png => {
-            let png = client.render_png(&diagram, &render_options).await?;
-            output_handler.write_png(&png).await?;
-        }
-        OutputFormat::Mermaid => unreachable!(),
-    }
+    let diagram = build_diagram(&args, global.strict, global.input_format).await?;
 
-    Ok(())
+    run_render(diagram, |diagram| &mut diagram.config, global).await
 }
 
-async fn build_diagram(args: &MindmapArgs) -> Result<Mindmap, MermaidError> {
-    if let Some(path) = &args.input.input {
-        let content = tokio::fs::read_to_string(path).await?;
-        let ext = path
-            .extension()
-            .and_then(std::ffi::OsStr::to_str)
-            .unwrap_or("yaml");
-        return parse_diagram(&content, ext);
-    }
-
-    if args.input.stdin {
-        use tokio::io::AsyncReadExt;
-        let mut buffer = String::new();
-        tokio::io::stdin().read_to_string(&mut buffer).await?;
-        let ext = if buffer.trim_start().starts_with('{') {
-            "json"
-        } else {
-            "yaml"
-        };
-        return parse_diagram(&buffer, ext);
+async fn build_diagram(
+    args: &MindmapArgs,
+    strict: bool,
+    input_format: Option<InputFormat>,
+) -> Result<Mindmap, MermaidError> {
+    if let Some((content, format)) = read_input(&args.input, input_format).await? {
+        return parse_input(&content, &format, parse_diagram);
     }
 
     if let Some(mermaid_str) = &args.input.mermaid {
@@ -105,7 +51,8 @@ async fn build_diagram(args: &MindmapArgs) -> Result<Mindmap, MermaidError> {
     let mut builder = Mindmap::builder(root_text);
 
     // Set root shape
-    let shape = MindmapNodeShape::parse(&args.shape).unwrap_or_default();
+    let shape = resolve_shape(MindmapNodeShape::parse(&args.shape), &args.shape, strict)?
+        .unwrap_or_default();
     builder = builder.root_shape(shape);
 
     if let Some(title) = &args.title {
@@ -121,13 +68,5 @@ async fn build_diagram(args: &MindmapArgs) -> Result<Mindmap, MermaidError> {
 }
 
 fn parse_diagram(content: &str, format: &str) -> Result<Mindmap, MermaidError> {
-    match format.to_lowercase().as_str() {
-        "json" => Mindmap::from_json(content),
-        "yaml" | "yml" => Mindmap::from_yaml(content),
-        "toml" => Mindmap::from_toml(content),
-        _ => Err(MermaidError::InvalidInput(format!(
-            "Unsupported format: {}",
-            format
-        ))),
-    }
+    parse_spec(content, format, Mindmap::from_raw_mermaid)
 }
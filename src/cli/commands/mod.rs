@@ -1,9 +1,446 @@
 pub mod er;
 pub mod flowchart;
 pub mod journey;
+pub mod list;
 pub mod mindmap;
 pub mod pie;
 pub mod render;
 pub mod requirement;
+#[cfg(feature = "schema")]
+pub mod schema;
 pub mod sequence;
 pub mod state;
+
+use serde::Deserialize;
+use tokio::io::AsyncReadExt;
+
+use crate::cli::{
+    GlobalOptions, IndentKind, InputFormat, InputOptions, OnError, OutputFormat, OutputHandler,
+};
+use crate::core::{Config, Diagram, FormatOptions, FromConfig, IndentStyle, MermaidError, Theme};
+use crate::diagrams::detect_diagram_type;
+use crate::render::{
+    inject_svg_style, make_svg_responsive, render_error_svg, MermaidClient, RenderOptions,
+};
+
+/// Format name [`read_input`] reports for stdin without `--input-format`
+const GUESS_FORMAT: &str = "auto";
+
+/// Read a definition from `--input` or `--stdin`, along with its format name.
+///
+/// `--input-format` wins over the file extension; stdin without it is reported
+/// as [`GUESS_FORMAT`] for [`parse_input`] to work out.
+pub(crate) async fn read_input(
+    input: &InputOptions,
+    format: Option<InputFormat>,
+) -> Result<Option<(String, String)>, MermaidError> {
+    let (content, guess) = if let Some(path) = &input.input {
+        let content = tokio::fs::read_to_string(path).await?;
+        let ext = path
+            .extension()
+            .and_then(std::ffi::OsStr::to_str)
+            .unwrap_or("yaml")
+            .to_string();
+        (content, ext)
+    } else if input.stdin {
+        let mut buffer = String::new();
+        tokio::io::stdin().read_to_string(&mut buffer).await?;
+        (buffer, GUESS_FORMAT.to_string())
+    } else {
+        return Ok(None);
+    };
+
+    let format = format.map_or(guess, |f| f.as_str().to_string());
+    Ok(Some((content, format)))
+}
+
+/// Parse a definition read by [`read_input`].
+///
+/// Guessed stdin is tried as JSON when it starts with `{`, YAML otherwise,
+/// then as TOML; if every attempt fails the error lists each format's failure.
+pub(crate) fn parse_input<T>(
+    content: &str,
+    format: &str,
+    parse: impl Fn(&str, &str) -> Result<T, MermaidError>,
+) -> Result<T, MermaidError> {
+    if format != GUESS_FORMAT {
+        return parse(content, format);
+    }
+
+    let first = if content.trim_start().starts_with('{') {
+        "json"
+    } else {
+        "yaml"
+    };
+    let mut failures = Vec::new();
+    for format in [first, "toml"] {
+        match parse(content, format) {
+            Ok(diagram) => return Ok(diagram),
+            Err(e) => failures.push(format!("{}: {}", format, e)),
+        }
+    }
+    Err(MermaidError::ParseError(format!(
+        "could not parse stdin as {} or toml (use --input-format to choose)\n  {}",
+        first,
+        failures.join("\n  ")
+    )))
+}
+
+/// Parse a definition in `format` (a file extension), keeping mermaid scripts raw
+pub(crate) fn parse_spec<D: FromConfig>(
+    content: &str,
+    format: &str,
+    raw: impl FnOnce(String) -> D,
+) -> Result<D, MermaidError> {
+    match InputFormat::from_extension(format) {
+        Some(InputFormat::Json) => D::from_json(content),
+        Some(InputFormat::Yaml) => D::from_yaml(content),
+        Some(InputFormat::Toml) => D::from_toml(content),
+        Some(InputFormat::Mermaid) => Ok(raw(content.to_string())),
+        None => Err(MermaidError::UnknownFormat(format.to_string())),
+    }
+}
+
+/// Split a CLI spec on `:` into at most `n` parts, honoring `\:` as a literal colon.
+///
+/// Like `str::splitn`, the last part keeps any remaining (unescaped) colons.
+pub(crate) fn split_spec(spec: &str, n: usize) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut chars = spec.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&':') => {
+                current.push(':');
+                chars.next();
+            }
+            ':' if parts.len() + 1 < n => parts.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    parts.push(current);
+    parts
+}
+
+/// Resolve an enum token parsed from a CLI spec.
+///
+/// Unrecognized tokens yield `None` (so the caller falls back to its default)
+/// unless `strict` is set, in which case they are an error.
+pub(crate) fn resolve_token<T>(
+    parsed: Option<T>,
+    what: &str,
+    token: &str,
+    strict: bool,
+) -> Result<Option<T>, MermaidError> {
+    resolve_with(parsed, strict, || {
+        MermaidError::InvalidInput(format!("Unknown {} '{}'", what, token))
+    })
+}
+
+/// Resolve a shape token, failing with `UnknownShape` under `strict`
+pub(crate) fn resolve_shape<T>(
+    parsed: Option<T>,
+    token: &str,
+    strict: bool,
+) -> Result<Option<T>, MermaidError> {
+    resolve_with(parsed, strict, || {
+        MermaidError::UnknownShape(token.to_string())
+    })
+}
+
+fn resolve_with<T>(
+    parsed: Option<T>,
+    strict: bool,
+    unknown: impl FnOnce() -> MermaidError,
+) -> Result<Option<T>, MermaidError> {
+    match parsed {
+        Some(value) => Ok(Some(value)),
+        None if strict => Err(unknown()),
+        None => Ok(None),
+    }
+}
+
+/// Warn about a diagram with nothing to draw, or fail under `strict`
+pub(crate) fn check_empty(diagram: &dyn Diagram, strict: bool) -> Result<(), MermaidError> {
+    if !diagram.is_empty() {
+        return Ok(());
+    }
+    let message = format!("{} diagram is empty", diagram.diagram_type());
+    if strict {
+        return Err(MermaidError::InvalidInput(message));
+    }
+    eprintln!("Warning: {}", message);
+    Ok(())
+}
+
+/// Warn about a diagram that fails validation, or fail under `strict`
+pub(crate) fn check_valid(
+    result: Result<(), MermaidError>,
+    strict: bool,
+) -> Result<(), MermaidError> {
+    match result {
+        Err(e) if !strict => {
+            eprintln!("Warning: {}", e);
+            Ok(())
+        }
+        other => other,
+    }
+}
+
+/// Apply `--mode`'s theme, then `--config-file` and `--layout`, to a diagram config
+pub(crate) async fn apply_global_config(
+    config: &mut Config,
+    global: &GlobalOptions,
+) -> Result<(), MermaidError> {
+    config.theme = global.mode.theme();
+    if let Some(layout) = global.layout {
+        config.layout = Some(layout);
+    }
+
+    let Some(path) = &global.config_file else {
+        return apply_theme_vars(config, &global.theme_var);
+    };
+
+    let content = tokio::fs::read_to_string(path).await?;
+    let ext = path
+        .extension()
+        .and_then(std::ffi::OsStr::to_str)
+        .unwrap_or("yaml");
+    let ConfigFile {
+        theme,
+        config: loaded,
+    } = match ext.to_lowercase().as_str() {
+        "json" => serde_json::from_str(&content)?,
+        "yaml" | "yml" => serde_yaml::from_str(&content)?,
+        "toml" => toml::from_str(&content)?,
+        _ => return Err(MermaidError::UnknownFormat(ext.to_string())),
+    };
+
+    if let Some(theme) = theme {
+        config.theme = theme;
+    }
+    if loaded.theme_variables.is_some() {
+        config.theme_variables = loaded.theme_variables;
+    }
+    if loaded.flowchart.is_some() {
+        config.flowchart = loaded.flowchart;
+    }
+    if global.layout.is_none() && loaded.layout.is_some() {
+        config.layout = loaded.layout;
+    }
+    apply_theme_vars(config, &global.theme_var)
+}
+
+/// A `--config-file`, noting whether it sets a theme so an unset one keeps `--mode`'s
+#[derive(Deserialize)]
+struct ConfigFile {
+    theme: Option<Theme>,
+    #[serde(flatten)]
+    config: Config,
+}
+
+/// Apply `--theme-var key=value` overrides on top of any config file values
+fn apply_theme_vars(config: &mut Config, specs: &[String]) -> Result<(), MermaidError> {
+    for spec in specs {
+        let (key, value) = spec
+            .split_once('=')
+            .filter(|(key, _)| !key.trim().is_empty())
+            .ok_or_else(|| {
+                MermaidError::InvalidInput(format!(
+                    "Invalid theme variable '{}'. Expected format: 'key=value'",
+                    spec
+                ))
+            })?;
+        config
+            .theme_variables
+            .get_or_insert_with(Default::default)
+            .set(key.trim(), value.trim());
+    }
+    Ok(())
+}
+
+/// Render a diagram built by a command, applying the global flags.
+///
+/// Checks for emptiness, applies the mode's theme, `--layout` and any
+/// `--config-file` to the config `config` points at, then emits every
+/// requested `--format`.
+pub(crate) async fn run_render<D: Diagram>(
+    mut diagram: D,
+    config: impl FnOnce(&mut D) -> &mut Option<Config>,
+    global: &GlobalOptions,
+) -> Result<(), MermaidError> {
+    check_empty(&diagram, global.strict)?;
+
+    apply_global_config(
+        config(&mut diagram).get_or_insert_with(Config::default),
+        global,
+    )
+    .await?;
+
+    emit_formats(
+        &diagram.build_script_with(global.config_style),
+        global,
+        &RenderOptions::from(global),
+    )
+    .await
+}
+
+/// Render options from the global flags.
+///
+/// `--background-color` overrides the `--mode` default; `--transparent` drops
+/// the background entirely.
+impl From<&GlobalOptions> for RenderOptions {
+    fn from(global: &GlobalOptions) -> Self {
+        let background_color = if global.transparent {
+            None
+        } else {
+            global
+                .background_color
+                .clone()
+                .or_else(|| global.mode.background_color().map(String::from))
+        };
+
+        Self {
+            width: global.width,
+            height: global.height,
+            scale: global.scale,
+            background_color,
+            transparent: global.transparent,
+        }
+    }
+}
+
+/// Mermaid text layout from `--indent` and `--indent-width`
+pub(crate) fn format_options(global: &GlobalOptions) -> FormatOptions {
+    let indent = global.indent.map(|kind| match kind {
+        IndentKind::Tabs => IndentStyle::Tabs,
+        IndentKind::Spaces => IndentStyle::Spaces(global.indent_width),
+    });
+    FormatOptions {
+        indent,
+        ..Default::default()
+    }
+}
+
+/// Render a script in every requested `--format` and write each result.
+///
+/// With more than one format, file outputs are derived from `--output` by
+/// swapping the extension (e.g. `chart.svg` and `chart.png`).
+pub async fn emit_formats(
+    script: &str,
+    global: &GlobalOptions,
+    render_options: &RenderOptions,
+) -> Result<(), MermaidError> {
+    let output_handler = OutputHandler::new(
+        global.output.clone(),
+        global.stdout,
+        global.clipboard,
+        global.open,
+    );
+
+    let multiple = global.format.len() > 1;
+    if multiple
+        && global.format.contains(&OutputFormat::Mermaid)
+        && output_handler.writes_to_stdout()
+    {
+        return Err(MermaidError::InvalidInput(
+            "Cannot combine mermaid with image formats on stdout. Use --output instead."
+                .to_string(),
+        ));
+    }
+    if global.on_error == OnError::Placeholder && global.format.contains(&OutputFormat::Png) {
+        return Err(MermaidError::InvalidInput(
+            "--on-error placeholder only supports svg output; drop png from --format".to_string(),
+        ));
+    }
+
+    let client = MermaidClient::new(Some(global.server.clone()));
+
+    for format in &global.format {
+        let handler = if multiple {
+            output_handler.with_extension(format.extension())
+        } else {
+            output_handler.clone()
+        };
+
+        match format {
+            OutputFormat::Mermaid => {
+                handler
+                    .write_mermaid(&format_options(global).apply(script))
+                    .await?;
+            }
+            OutputFormat::Svg => {
+                let mut svg = match client.render_svg_from_script(script, render_options).await {
+                    Ok(svg) => svg,
+                    Err(
+                        e @ (MermaidError::HttpError(_)
+                        | MermaidError::NetworkUnreachable(_)
+                        | MermaidError::RenderFailed(_)),
+                    ) if global.on_error == OnError::Placeholder => {
+                        eprintln!("Warning: render failed, writing placeholder SVG: {}", e);
+                        let kind = detect_diagram_type(script)
+                            .map(|kind| kind.as_str())
+                            .unwrap_or("unknown");
+                        render_error_svg(&e.to_string(), kind)
+                    }
+                    Err(e) => return Err(e),
+                };
+                if let Some(path) = &global.svg_css {
+                    let css = tokio::fs::read_to_string(path).await?;
+                    svg = inject_svg_style(&svg, &css);
+                }
+                if global.responsive {
+                    svg = make_svg_responsive(&svg);
+                }
+                handler.write_svg(&svg).await?;
+            }
+            OutputFormat::Png => {
+                let png = client
+                    .render_png_from_script(script, render_options)
+                    .await?;
+                handler.write_png(&png).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagrams::flowchart::NodeShape;
+
+    #[test]
+    fn split_spec_escaped_colon() {
+        assert_eq!(
+            split_spec("A:Start:stadium", 3),
+            vec!["A", "Start", "stadium"]
+        );
+        assert_eq!(
+            split_spec("ns\\:A:Time\\: 10\\:30", 3),
+            vec!["ns:A", "Time: 10:30"]
+        );
+        assert_eq!(split_spec("a:b:c:d", 3), vec!["a", "b", "c:d"]);
+        assert_eq!(split_spec("path\\to", 2), vec!["path\\to"]);
+    }
+
+    #[test]
+    fn resolve_shape_strict_unknown() {
+        let err = resolve_shape(NodeShape::parse("stadum"), "stadum", true).unwrap_err();
+        assert!(matches!(err, MermaidError::UnknownShape(ref s) if s == "stadum"));
+        assert_eq!(err.to_string(), "Unknown node shape 'stadum'");
+    }
+
+    #[test]
+    fn resolve_shape_lenient_and_known() {
+        assert!(resolve_shape(NodeShape::parse("stadum"), "stadum", false)
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            resolve_shape(NodeShape::parse("stadium"), "stadium", true).unwrap(),
+            Some(NodeShape::Stadium)
+        );
+    }
+}
@@ -1,9 +1,9 @@
 use clap::Parser;
 
-use crate::cli::{GlobalOptions, InputOptions, OutputFormat, OutputHandler};
-use crate::core::{Config, Diagram, MermaidError};
+use crate::cli::commands::{check_valid, parse_input, parse_spec, read_input, run_render};
+use crate::cli::{GlobalOptions, InputFormat, InputOptions};
+use crate::core::MermaidError;
 use crate::diagrams::pie::PieChart;
-use crate::render::{MermaidClient, RenderOptions};
 
 #[derive(Parser, Debug)]
 pub struct PieArgs {
@@ -25,79 +25,19 @@ pub struct PieArgs {
 
 pub async fn run(args: PieArgs, global: &GlobalOptions) -> Result<(), MermaidError> {
     // Build the pie chart from args or input file
-    let mut chart = build_chart(&args).await?;
-
-    // Apply mode's theme to diagram config
-    let config = chart.config.get_or_insert_with(Config::default);
-    config.theme = global.mode.theme();
-
-    // Build render options from global options
-    // Use explicit --background-color if provided, otherwise use mode's default
-    let render_options = RenderOptions {
-        width: global.width,
-        height: global.height,
-        scale: global.scale,
-        background_color: global
-            .background_color
-            .clone()
-            .or_else(|| global.mode.background_color().map(String::from)),
-    };
-
-    // Create output handler
-    let output_handler = OutputHandler::new(
-        global.output.clone(),
-        global.stdout,
-        global.clipboard,
-        global.open,
-    );
-
-    // Handle mermaid format specially (no rendering needed)
-    if matches!(global.format, OutputFormat::Mermaid) {
-        let script = chart.build_script();
-        output_handler.write_mermaid(&script).await?;
-        return Ok(());
-    }
-
-    // Create client and render
-    let client = MermaidClient::new(Some(global.server.clone()));
-
-    match global.format {
-        OutputFormat::Svg => {
-            let svg = client.render_svg(&chart, &render_options).await?;
-            output_handler.write_svg(&svg).await?;
-        }
-        OutputFormat::Png => {
-            let png = client.render_png(&chart, &render_options).await?;
-            output_handler.write_png(&png).await?;
-        }
-        OutputFormat::Mermaid => unreachable!(),
-    }
+    let chart = build_chart(&args, global.input_format).await?;
+    check_valid(chart.validate(), global.strict)?;
 
-    Ok(())
+    run_render(chart, |chart| &mut chart.config, global).await
 }
 
-async fn build_chart(args: &PieArgs) -> Result<PieChart, MermaidError> {
+async fn build_chart(
+    args: &PieArgs,
+    input_format: Option<InputFormat>,
+) -> Result<PieChart, MermaidError> {
     // If input file or stdin specified, load from there
-    if let Some(path) = &args.input.input {
-        let content = tokio::fs::read_to_string(path).await?;
-        let ext = path
-            .extension()
-            .and_then(std::ffi::OsStr::to_str)
-            .unwrap_or("yaml");
-        return parse_chart(&content, ext);
-    }
-
-    if args.input.stdin {
-        use tokio::io::AsyncReadExt;
-        let mut buffer = String::new();
-        tokio::io::stdin().read_to_string(&mut buffer).await?;
-        // Try to detect format from content
-        let ext = if buffer.trim_start().starts_with('{') {
-            "json"
-        } else {
-            "yaml"
-        };
-        return parse_chart(&buffer, ext);
+    if let Some((content, format)) = read_input(&args.input, input_format).await? {
+        return parse_input(&content, &format, parse_chart);
     }
 
     if let Some(mermaid_str) = &args.input.mermaid {
@@ -125,15 +65,7 @@ async fn build_chart(args: &PieArgs) -> Result<PieChart, MermaidError> {
 }
 
 fn parse_chart(content: &str, format: &str) -> Result<PieChart, MermaidError> {
-    match format.to_lowercase().as_str() {
-        "json" => PieChart::from_json(content),
-        "yaml" | "yml" => PieChart::from_yaml(content),
-        "toml" => PieChart::from_toml(content),
-        _ => Err(MermaidError::InvalidInput(format!(
-            "Unsupported format: {}",
-            format
-        ))),
-    }
+    parse_spec(content, format, PieChart::from_raw_mermaid)
 }
 
 fn parse_data_spec(spec: &str) -> Result<(String, f64), MermaidError> {
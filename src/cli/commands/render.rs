@@ -1,11 +1,14 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use clap::Parser;
 use tokio::io::AsyncReadExt;
+use tokio::task::JoinSet;
 
-use crate::cli::{GlobalOptions, OutputFormat, OutputHandler};
+use crate::cli::commands::emit_formats;
+use crate::cli::{write_gallery, GalleryEntry, GlobalOptions, InputFormat, OutputFormat};
 use crate::core::MermaidError;
-use crate::render::{MermaidClient, RenderOptions};
+use crate::diagrams::{extract_mermaid_blocks, load_diagram};
+use crate::render::RenderOptions;
 
 #[derive(Parser, Debug)]
 pub struct RenderArgs {
@@ -20,85 +23,207 @@ pub struct RenderArgs {
     /// Raw mermaid string to render
     #[arg(short, long)]
     pub mermaid: Option<String>,
+
+    /// Diagram definition file (JSON/YAML/TOML); the diagram type is auto-detected
+    #[arg(short, long)]
+    pub input: Option<PathBuf>,
+
+    /// Render every ```mermaid block in a markdown file to numbered outputs
+    /// (`<stem>-1.svg`, `<stem>-2.svg`, ... named after --output or the file)
+    #[arg(long, value_name = "FILE")]
+    pub from_markdown: Option<PathBuf>,
+
+    /// With --from-markdown, also write an index.html showing every SVG/PNG output
+    #[arg(long, requires = "from_markdown")]
+    pub gallery: bool,
 }
 
 pub async fn run(args: RenderArgs, global: &GlobalOptions) -> Result<(), MermaidError> {
+    if let Some(path) = &args.from_markdown {
+        return render_markdown(path, args.gallery, global).await;
+    }
+
     // Get the mermaid script from one of the input sources
-    let script = get_script(&args).await?;
+    let script = get_script(&args, global).await?;
 
     // Build render options from global options
     // Note: For raw mermaid passthrough, we can't inject theme but we can set background
-    let render_options = RenderOptions {
-        width: global.width,
-        height: global.height,
-        scale: global.scale,
-        background_color: global
-            .background_color
-            .clone()
-            .or_else(|| global.mode.background_color().map(String::from)),
-    };
-
-    // Create output handler
-    let output_handler = OutputHandler::new(
-        global.output.clone(),
-        global.stdout,
-        global.clipboard,
-        global.open,
-    );
-
-    // Handle mermaid format specially (no rendering needed)
-    if matches!(global.format, OutputFormat::Mermaid) {
-        output_handler.write_mermaid(&script).await?;
-        return Ok(());
+    let render_options = RenderOptions::from(global);
+
+    emit_formats(&script, global, &render_options).await
+}
+
+/// Render each mermaid block of a markdown file concurrently, one numbered output per block.
+///
+/// With `gallery`, an `index.html` linking the SVG/PNG outputs is written next to them.
+async fn render_markdown(
+    path: &Path,
+    gallery: bool,
+    global: &GlobalOptions,
+) -> Result<(), MermaidError> {
+    if gallery
+        && !global
+            .format
+            .iter()
+            .any(|format| matches!(format, OutputFormat::Svg | OutputFormat::Png))
+    {
+        return Err(MermaidError::InvalidInput(
+            "--gallery needs svg or png in --format".to_string(),
+        ));
     }
 
-    // Create client and render
-    let client = MermaidClient::new(Some(global.server.clone()));
+    let markdown = tokio::fs::read_to_string(path).await?;
+    let blocks = extract_mermaid_blocks(&markdown);
+    if blocks.is_empty() {
+        return Err(MermaidError::InvalidInput(format!(
+            "No ```mermaid blocks found in {}",
+            path.display()
+        )));
+    }
 
-    match global.format {
-        OutputFormat::Svg => {
-            let svg = client
-                .render_svg_from_script(&script, &render_options)
-                .await?;
-            output_handler.write_svg(&svg).await?;
-        }
-        OutputFormat::Png => {
-            let png = client
-                .render_png_from_script(&script, &render_options)
-                .await?;
-            output_handler.write_png(&png).await?;
-        }
-        OutputFormat::Mermaid => unreachable!(),
+    let extension = global
+        .format
+        .first()
+        .copied()
+        .unwrap_or_default()
+        .extension();
+    let render_options = RenderOptions::from(global);
+    let mut tasks = JoinSet::new();
+    let mut outputs = Vec::new();
+    for (i, script) in blocks.into_iter().enumerate() {
+        let mut block_global = global.clone();
+        let output = numbered_output(global.output.as_deref(), path, i + 1, extension);
+        outputs.push(output.clone());
+        block_global.output = Some(output);
+        let render_options = render_options.clone();
+        tasks.spawn(async move { emit_formats(&script, &block_global, &render_options).await });
     }
 
+    while let Some(result) = tasks.join_next().await {
+        result.map_err(|e| MermaidError::RenderFailed(e.to_string()))??;
+    }
+
+    if gallery {
+        write_markdown_gallery(&outputs, global).await?;
+    }
     Ok(())
 }
 
-async fn get_script(args: &RenderArgs) -> Result<String, MermaidError> {
-    // Priority: --mermaid flag > file argument > --stdin flag
+/// Write `index.html` beside the numbered outputs, one entry per SVG/PNG file
+async fn write_markdown_gallery(
+    outputs: &[PathBuf],
+    global: &GlobalOptions,
+) -> Result<(), MermaidError> {
+    let multiple = global.format.len() > 1;
+    let entries: Vec<GalleryEntry> = outputs
+        .iter()
+        .flat_map(|output| {
+            global
+                .format
+                .iter()
+                .filter(|format| matches!(format, OutputFormat::Svg | OutputFormat::Png))
+                .map(move |format| match multiple {
+                    true => output.with_extension(format.extension()),
+                    false => output.clone(),
+                })
+        })
+        .filter_map(|file| file.file_name().map(GalleryEntry::from_path))
+        .collect();
+
+    let dir = outputs[0]
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    write_gallery(dir, &entries).await?;
+    Ok(())
+}
+
+/// `out/docs-2.svg` for block 2 with `--output out/docs.svg`; next to the
+/// markdown file (`README-2.svg`) without one
+fn numbered_output(output: Option<&Path>, source: &Path, n: usize, extension: &str) -> PathBuf {
+    let base = output.unwrap_or(source);
+    let stem = base
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "diagram".to_string());
+    let extension = output
+        .and_then(|p| p.extension())
+        .and_then(std::ffi::OsStr::to_str)
+        .unwrap_or(extension);
+    base.with_file_name(format!("{}-{}.{}", stem, n, extension))
+}
+
+async fn get_script(args: &RenderArgs, global: &GlobalOptions) -> Result<String, MermaidError> {
+    // Priority: --mermaid flag > --input definition > file argument > --stdin flag
     if let Some(mermaid) = &args.mermaid {
         return Ok(mermaid.clone());
     }
 
+    if let Some(path) = &args.input {
+        let content = tokio::fs::read_to_string(path).await?;
+        let ext = match global.input_format {
+            Some(format) => format.as_str(),
+            None => path
+                .extension()
+                .and_then(std::ffi::OsStr::to_str)
+                .unwrap_or("yaml"),
+        };
+        let diagram = load_diagram(&content, ext)?;
+        return Ok(diagram.build_script_with(global.config_style));
+    }
+
     if let Some(file) = &args.file {
         let content = tokio::fs::read_to_string(file).await?;
-        return Ok(content);
+        return script_from(content, global);
     }
 
     if args.stdin {
         let mut buffer = String::new();
         tokio::io::stdin().read_to_string(&mut buffer).await?;
-        return Ok(buffer);
+        return script_from(buffer, global);
     }
 
     // If no explicit input, check if there's data on stdin
     let mut buffer = String::new();
     tokio::io::stdin().read_to_string(&mut buffer).await?;
     if !buffer.is_empty() {
-        return Ok(buffer);
+        return script_from(buffer, global);
     }
 
     Err(MermaidError::InvalidInput(
         "No input provided. Use --mermaid, provide a file, or pipe to stdin.".to_string(),
     ))
 }
+
+/// Raw mermaid passes through; a non-mermaid `--input-format` loads a definition
+fn script_from(content: String, global: &GlobalOptions) -> Result<String, MermaidError> {
+    match global.input_format {
+        Some(format) if format != InputFormat::Mermaid => {
+            let diagram = load_diagram(&content, format.as_str())?;
+            Ok(diagram.build_script_with(global.config_style))
+        }
+        _ => Ok(content),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbered_output_names() {
+        assert_eq!(
+            numbered_output(
+                Some(Path::new("out/docs.png")),
+                Path::new("README.md"),
+                2,
+                "svg"
+            ),
+            PathBuf::from("out/docs-2.png")
+        );
+        assert_eq!(
+            numbered_output(None, Path::new("docs/README.md"), 1, "svg"),
+            PathBuf::from("docs/README-1.svg")
+        );
+    }
+}
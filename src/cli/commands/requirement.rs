@@ -1,11 +1,11 @@
 use clap::Parser;
 
-use crate::cli::{GlobalOptions, InputOptions, OutputFormat, OutputHandler};
-use crate::core::{Config, Diagram, MermaidError};
+use crate::cli::commands::{parse_input, parse_spec, read_input, resolve_token, run_render};
+use crate::cli::{GlobalOptions, InputFormat, InputOptions};
+use crate::core::MermaidError;
 use crate::diagrams::requirement::{
     Element, ReqRelationship, Requirement, RequirementDiagram, Risk, VerifyMethod,
 };
-use crate::render::{MermaidClient, RenderOptions};
 
 #[derive(Parser, Debug)]
 pub struct RequirementArgs {
@@ -30,72 +30,18 @@ pub struct RequirementArgs {
 }
 
 pub async fn run(args: RequirementArgs, global: &GlobalOptions) -> Result<(), MermaidError> {
-    let mut diagram = build_diagram(&args).await?;
-
-    // Apply mode's theme to diagram config
-    let config = diagram.config.get_or_insert_with(Config::default);
-    config.theme = global.mode.theme();
-
-    let render_options = RenderOptions {
-        width: global.width,
-        height: global.height,
-        scale: global.scale,
-        background_color: global
-            .background_color
-            .clone()
-            .or_else(|| global.mode.background_color().map(String::from)),
-    };
-
-    let output_handler = OutputHandler::new(
-        global.output.clone(),
-        global.stdout,
-        global.clipboard,
-        global.open,
-    );
-
-    if matches!(global.format, OutputFormat::Mermaid) {
-        let script = diagram.build_script();
-        output_handler.write_mermaid(&script).await?;
-        return Ok(());
-    }
-
-    let client = MermaidClient::new(Some(global.server.clone()));
-
-    match global.format {
-        OutputFormat::Svg => {
-            let svg = client.render_svg(&diagram, &render_options).await?;
-            output_handler.write_svg(&svg).await?;
-        }
-        OutputFormat::Png => {
-            let png = client.render_png(&diagram, &render_options).await?;
-            output_handler.write_png(&png).await?;
-        }
-        OutputFormat::Mermaid => unreachable!(),
-    }
+    let diagram = build_diagram(&args, global.strict, global.input_format).await?;
 
-    Ok(())
+    run_render(diagram, |diagram| &mut diagram.config, global).await
 }
 
-async fn build_diagram(args: &RequirementArgs) -> Result<RequirementDiagram, MermaidError> {
-    if let Some(path) = &args.input.input {
-        let content = tokio::fs::read_to_string(path).await?;
-        let ext = path
-            .extension()
-            .and_then(std::ffi::OsStr::to_str)
-            .unwrap_or("yaml");
-        return parse_diagram(&content, ext);
-    }
-
-    if args.input.stdin {
-        use tokio::io::AsyncReadExt;
-        let mut buffer = String::new();
-        tokio::io::stdin().read_to_string(&mut buffer).await?;
-        let ext = if buffer.trim_start().starts_with('{') {
-            "json"
-        } else {
-            "yaml"
-        };
-        return parse_diagram(&buffer, ext);
+async fn build_diagram(
+    args: &RequirementArgs,
+    strict: bool,
+    input_format: Option<InputFormat>,
+) -> Result<RequirementDiagram, MermaidError> {
+    if let Some((content, format)) = read_input(&args.input, input_format).await? {
+        return parse_input(&content, &format, parse_diagram);
     }
 
     if let Some(mermaid_str) = &args.input.mermaid {
@@ -113,7 +59,7 @@ async fn build_diagram(args: &RequirementArgs) -> Result<RequirementDiagram, Mer
 
     // Parse requirements
     for req_spec in &args.requirement {
-        let req = parse_requirement_spec(req_spec)?;
+        let req = parse_requirement_spec(req_spec, strict)?;
         builder = builder.requirement(req);
     }
 
@@ -125,7 +71,7 @@ async fn build_diagram(args: &RequirementArgs) -> Result<RequirementDiagram, Mer
 
     // Parse relationships
     for rel_spec in &args.relationship {
-        let rel = parse_relationship_spec(rel_spec)?;
+        let rel = parse_relationship_spec(rel_spec, strict)?;
         builder = builder.relationship(rel);
     }
 
@@ -133,18 +79,10 @@ async fn build_diagram(args: &RequirementArgs) -> Result<RequirementDiagram, Mer
 }
 
 fn parse_diagram(content: &str, format: &str) -> Result<RequirementDiagram, MermaidError> {
-    match format.to_lowercase().as_str() {
-        "json" => RequirementDiagram::from_json(content),
-        "yaml" | "yml" => RequirementDiagram::from_yaml(content),
-        "toml" => RequirementDiagram::from_toml(content),
-        _ => Err(MermaidError::InvalidInput(format!(
-            "Unsupported format: {}",
-            format
-        ))),
-    }
+    parse_spec(content, format, RequirementDiagram::from_raw_mermaid)
 }
 
-fn parse_requirement_spec(spec: &str) -> Result<Requirement, MermaidError> {
+fn parse_requirement_spec(spec: &str, strict: bool) -> Result<Requirement, MermaidError> {
     // Format: "id:name:text:risk:verify"
     let parts: Vec<&str> = spec.splitn(5, ':').collect();
     if parts.len() < 2 {
@@ -164,12 +102,15 @@ fn parse_requirement_spec(spec: &str) -> Result<Requirement, MermaidError> {
     }
 
     if parts.len() > 3 && !parts[3].is_empty() {
-        let risk = Risk::parse(parts[3].trim()).unwrap_or_default();
+        let token = parts[3].trim();
+        let risk = resolve_token(Risk::parse(token), "risk", token, strict)?.unwrap_or_default();
         req = req.with_risk(risk);
     }
 
     if parts.len() > 4 && !parts[4].is_empty() {
-        let verify = VerifyMethod::parse(parts[4].trim()).unwrap_or_default();
+        let token = parts[4].trim();
+        let verify = resolve_token(VerifyMethod::parse(token), "verify method", token, strict)?
+            .unwrap_or_default();
         req = req.with_verify_method(verify);
     }
 
@@ -189,7 +130,7 @@ fn parse_element_spec(spec: &str) -> Result<Element, MermaidError> {
     Ok(Element::new(parts[0].trim(), parts[1].trim()))
 }
 
-fn parse_relationship_spec(spec: &str) -> Result<ReqRelationship, MermaidError> {
+fn parse_relationship_spec(spec: &str, strict: bool) -> Result<ReqRelationship, MermaidError> {
     // Format: "from->to:type"
     let arrow_pos = spec.find("->").ok_or_else(|| {
         MermaidError::InvalidInput(format!(
@@ -224,6 +165,12 @@ fn parse_relationship_spec(spec: &str) -> Result<ReqRelationship, MermaidError>
         "copies" => ReqRelationship::copies(from, to),
         "refines" => ReqRelationship::refines(from, to),
         "traces" => ReqRelationship::traces(from, to),
+        _ if strict => {
+            return Err(MermaidError::InvalidInput(format!(
+                "Unknown relationship type '{}'",
+                rel_type
+            )))
+        }
         _ => ReqRelationship::satisfies(from, to),
     };
 
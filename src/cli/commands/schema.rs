@@ -0,0 +1,16 @@
+use clap::Parser;
+
+use crate::core::{DiagramKind, MermaidError};
+use crate::diagrams::schema::diagram_schema;
+
+#[derive(Parser, Debug)]
+pub struct SchemaArgs {
+    /// Diagram type (flowchart, sequence, state, er, pie, journey, mindmap, requirement)
+    pub diagram_type: DiagramKind,
+}
+
+pub fn run(args: SchemaArgs) -> Result<(), MermaidError> {
+    let schema = diagram_schema(args.diagram_type);
+    println!("{}", serde_json::to_string_pretty(&schema)?);
+    Ok(())
+}
@@ -1,11 +1,13 @@
 use clap::Parser;
 
-use crate::cli::{GlobalOptions, InputOptions, OutputFormat, OutputHandler};
-use crate::core::{Config, Diagram, MermaidError};
+use crate::cli::commands::{
+    parse_input, parse_spec, read_input, resolve_token, run_render, split_spec,
+};
+use crate::cli::{GlobalOptions, InputFormat, InputOptions};
+use crate::core::MermaidError;
 use crate::diagrams::sequence::{
-    Message, MessageType, Note, NotePosition, Participant, SequenceDiagram,
+    Message, MessageType, Note, NotePosition, Participant, ParticipantType, SequenceDiagram,
 };
-use crate::render::{MermaidClient, RenderOptions};
 
 #[derive(Parser, Debug)]
 pub struct SequenceArgs {
@@ -16,15 +18,15 @@ pub struct SequenceArgs {
     #[arg(short, long, value_name = "SPEC")]
     pub actor: Vec<String>,
 
-    /// Add participant: "id:label"
+    /// Add participant: "id:label" or "id:label:actor" (declared in the order given)
     #[arg(short, long, value_name = "SPEC")]
     pub participant: Vec<String>,
 
-    /// Add message: "from->to:type:text"
+    /// Add message: "from->to:type:text" (\: is a literal colon)
     #[arg(short, long, value_name = "SPEC")]
     pub message: Vec<String>,
 
-    /// Add note: "position:over:text"
+    /// Add note: "position:A:text", or "over:A,B:text" to span participants
     #[arg(long, value_name = "SPEC")]
     pub note: Vec<String>,
 
@@ -38,72 +40,18 @@ pub struct SequenceArgs {
 }
 
 pub async fn run(args: SequenceArgs, global: &GlobalOptions) -> Result<(), MermaidError> {
-    let mut diagram = build_diagram(&args).await?;
-
-    // Apply mode's theme to diagram config
-    let config = diagram.config.get_or_insert_with(Config::default);
-    config.theme = global.mode.theme();
-
-    let render_options = RenderOptions {
-        width: global.width,
-        height: global.height,
-        scale: global.scale,
-        background_color: global
-            .background_color
-            .clone()
-            .or_else(|| global.mode.background_color().map(String::from)),
-    };
-
-    let output_handler = OutputHandler::new(
-        global.output.clone(),
-        global.stdout,
-        global.clipboard,
-        global.open,
-    );
-
-    if matches!(global.format, OutputFormat::Mermaid) {
-        let script = diagram.build_script();
-        output_handler.write_mermaid(&script).await?;
-        return Ok(());
-    }
+    let diagram = build_diagram(&args, global.strict, global.input_format).await?;
 
-    let client = MermaidClient::new(Some(global.server.clone()));
-
-    match global.format {
-        OutputFormat::Svg => {
-            let svg = client.render_svg(&diagram, &render_options).await?;
-            output_handler.write_svg(&svg).await?;
-        }
-        OutputFormat::Png => {
-            let png = client.render_png(&diagram, &render_options).await?;
-            output_handler.write_png(&png).await?;
-        }
-        OutputFormat::Mermaid => unreachable!(),
-    }
-
-    Ok(())
+    run_render(diagram, |diagram| &mut diagram.config, global).await
 }
 
-async fn build_diagram(args: &SequenceArgs) -> Result<SequenceDiagram, MermaidError> {
-    if let Some(path) = &args.input.input {
-        let content = tokio::fs::read_to_string(path).await?;
-        let ext = path
-            .extension()
-            .and_then(std::ffi::OsStr::to_str)
-            .unwrap_or("yaml");
-        return parse_diagram(&content, ext);
-    }
-
-    if args.input.stdin {
-        use tokio::io::AsyncReadExt;
-        let mut buffer = String::new();
-        tokio::io::stdin().read_to_string(&mut buffer).await?;
-        let ext = if buffer.trim_start().starts_with('{') {
-            "json"
-        } else {
-            "yaml"
-        };
-        return parse_diagram(&buffer, ext);
+async fn build_diagram(
+    args: &SequenceArgs,
+    strict: bool,
+    input_format: Option<InputFormat>,
+) -> Result<SequenceDiagram, MermaidError> {
+    if let Some((content, format)) = read_input(&args.input, input_format).await? {
+        return parse_input(&content, &format, parse_diagram);
     }
 
     if let Some(mermaid_str) = &args.input.mermaid {
@@ -135,13 +83,13 @@ async fn build_diagram(args: &SequenceArgs) -> Result<SequenceDiagram, MermaidEr
 
     // Parse messages
     for msg_spec in &args.message {
-        let message = parse_message_spec(msg_spec)?;
+        let message = parse_message_spec(msg_spec, strict)?;
         builder = builder.message(message);
     }
 
     // Parse notes
     for note_spec in &args.note {
-        let note = parse_note_spec(note_spec)?;
+        let note = parse_note_spec(note_spec, strict)?;
         builder = builder.note(note);
     }
 
@@ -149,42 +97,42 @@ async fn build_diagram(args: &SequenceArgs) -> Result<SequenceDiagram, MermaidEr
 }
 
 fn parse_diagram(content: &str, format: &str) -> Result<SequenceDiagram, MermaidError> {
-    match format.to_lowercase().as_str() {
-        "json" => SequenceDiagram::from_json(content),
-        "yaml" | "yml" => SequenceDiagram::from_yaml(content),
-        "toml" => SequenceDiagram::from_toml(content),
-        _ => Err(MermaidError::InvalidInput(format!(
-            "Unsupported format: {}",
-            format
-        ))),
-    }
+    parse_spec(content, format, SequenceDiagram::from_raw_mermaid)
 }
 
 fn parse_participant_spec(spec: &str, is_actor: bool) -> Result<Participant, MermaidError> {
-    // Format: "id:label"
-    let parts: Vec<&str> = spec.splitn(2, ':').collect();
-    if parts.is_empty() {
-        return Err(MermaidError::InvalidInput(format!(
-            "Invalid participant spec '{}'. Expected format: 'id:label'",
-            spec
-        )));
+    // Format: "id:label" or "id:label:type" (type: actor, participant)
+    let mut parts = split_spec(spec, usize::MAX);
+
+    // A trailing ":actor"/":participant" sets the type; any other colon stays in the label
+    let mut participant_type = if is_actor {
+        ParticipantType::Actor
+    } else {
+        ParticipantType::Participant
+    };
+    if parts.len() > 2 {
+        if let Some(parsed) = parts.last().and_then(|t| ParticipantType::parse(t.trim())) {
+            participant_type = parsed;
+            parts.pop();
+        }
     }
 
     let id = parts[0].trim().to_string();
-    let participant = if is_actor {
-        Participant::actor(&id)
-    } else {
-        Participant::non_actor(&id)
+    let label = parts[1..].join(":");
+    let label = label.trim();
+    let participant = match participant_type {
+        ParticipantType::Actor => Participant::actor(&id),
+        ParticipantType::Participant => Participant::non_actor(&id),
     };
 
-    if parts.len() > 1 && !parts[1].is_empty() {
-        Ok(participant.with_label(parts[1].trim()))
-    } else {
+    if label.is_empty() {
         Ok(participant)
+    } else {
+        Ok(participant.with_label(label))
     }
 }
 
-fn parse_message_spec(spec: &str) -> Result<Message, MermaidError> {
+fn parse_message_spec(spec: &str, strict: bool) -> Result<Message, MermaidError> {
     // Format: "from->to:type:text"
     let arrow_pos = spec.find("->").ok_or_else(|| {
         MermaidError::InvalidInput(format!(
@@ -193,10 +141,11 @@ fn parse_message_spec(spec: &str) -> Result<Message, MermaidError> {
         ))
     })?;
 
-    let from = spec[..arrow_pos].trim().to_string();
+    // A single part: just unescape `\:`
+    let from = split_spec(&spec[..arrow_pos], 1)[0].trim().to_string();
     let rest = &spec[arrow_pos + 2..];
 
-    let parts: Vec<&str> = rest.splitn(3, ':').collect();
+    let parts = split_spec(rest, 3);
     if parts.is_empty() {
         return Err(MermaidError::InvalidInput(format!(
             "Invalid message spec '{}'. Expected format: 'from->to:type:text'",
@@ -208,7 +157,10 @@ fn parse_message_spec(spec: &str) -> Result<Message, MermaidError> {
     let mut message = Message::new(from, to);
 
     if parts.len() > 1 && !parts[1].is_empty() {
-        if let Some(msg_type) = MessageType::parse(parts[1].trim()) {
+        let token = parts[1].trim();
+        if let Some(msg_type) =
+            resolve_token(MessageType::parse(token), "message type", token, strict)?
+        {
             message = message.with_type(msg_type);
         }
     }
@@ -220,19 +172,36 @@ fn parse_message_spec(spec: &str) -> Result<Message, MermaidError> {
     Ok(message)
 }
 
-fn parse_note_spec(spec: &str) -> Result<Note, MermaidError> {
-    // Format: "position:over:text"
-    let parts: Vec<&str> = spec.splitn(3, ':').collect();
+fn parse_note_spec(spec: &str, strict: bool) -> Result<Note, MermaidError> {
+    // Format: "position:A:text" or "over:A,B:text"
+    let parts = split_spec(spec, 3);
     if parts.len() < 3 {
         return Err(MermaidError::InvalidInput(format!(
-            "Invalid note spec '{}'. Expected format: 'position:over:text'",
+            "Invalid note spec '{}'. Expected format: 'position:A:text' or 'over:A,B:text'",
             spec
         )));
     }
 
-    let position = NotePosition::parse(parts[0].trim()).unwrap_or(NotePosition::Over);
-    let over = parts[1].trim().to_string();
-    let text = parts[2].trim().to_string();
+    let token = parts[0].trim();
+    let position = resolve_token(NotePosition::parse(token), "note position", token, strict)?
+        .unwrap_or(NotePosition::Over);
+    let over: Vec<String> = parts[1]
+        .split(',')
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect();
+    if over.is_empty() {
+        return Err(MermaidError::InvalidInput(format!(
+            "Note spec '{}' names no participant",
+            spec
+        )));
+    }
+    if over.len() > 1 && position != NotePosition::Over {
+        return Err(MermaidError::InvalidInput(format!(
+            "Note spec '{}': only 'over' notes can span participants",
+            spec
+        )));
+    }
 
-    Ok(Note::over_participant(position, over, text))
+    Ok(Note::over_participants(position, over, parts[2].trim()))
 }
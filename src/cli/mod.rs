@@ -2,5 +2,8 @@ pub mod args;
 pub mod commands;
 pub mod output;
 
-pub use args::{Cli, Commands, CompletionsArgs, GlobalOptions, InputOptions, OutputFormat};
-pub use output::{OutputHandler, OutputTarget};
+pub use args::{
+    Cli, Commands, CompletionsArgs, GlobalOptions, IndentKind, InputFormat, InputOptions, OnError,
+    OutputFormat,
+};
+pub use output::{gallery_html, write_gallery, GalleryEntry, OutputHandler, OutputTarget};
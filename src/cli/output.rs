@@ -1,10 +1,15 @@
-use std::path::PathBuf;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 use arboard::Clipboard;
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use tokio::fs;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
+use super::OutputFormat;
 use crate::core::MermaidError;
+use crate::render::escape_xml;
 
 #[derive(Debug, Clone)]
 pub enum OutputTarget {
@@ -14,6 +19,7 @@ pub enum OutputTarget {
     Browser,
 }
 
+#[derive(Clone)]
 pub struct OutputHandler {
     targets: Vec<OutputTarget>,
 }
@@ -43,10 +49,49 @@ impl OutputHandler {
         Self { targets }
     }
 
+    /// Copy of this handler with file targets renamed to the given extension
+    pub fn with_extension(&self, extension: &str) -> Self {
+        let targets = self
+            .targets
+            .iter()
+            .map(|target| match target {
+                OutputTarget::File(path) => OutputTarget::File(path.with_extension(extension)),
+                other => other.clone(),
+            })
+            .collect();
+        Self { targets }
+    }
+
+    pub fn writes_to_stdout(&self) -> bool {
+        self.targets
+            .iter()
+            .any(|t| matches!(t, OutputTarget::Stdout))
+    }
+
     pub fn targets(&self) -> &[OutputTarget] {
         &self.targets
     }
 
+    /// First file target, which `--open` reuses instead of writing a temp file
+    fn file_path(&self) -> Option<&PathBuf> {
+        self.targets.iter().find_map(|t| match t {
+            OutputTarget::File(path) => Some(path),
+            _ => None,
+        })
+    }
+
+    /// Path to show in the viewer, writing a temp file when there is no file target
+    async fn viewer_path(&self, content: &[u8], extension: &str) -> Result<PathBuf, MermaidError> {
+        match self.file_path() {
+            Some(path) => Ok(path.clone()),
+            None => {
+                let path = viewer_temp_path(extension);
+                fs::write(&path, content).await?;
+                Ok(path)
+            }
+        }
+    }
+
     pub async fn write_svg(&self, content: &str) -> Result<(), MermaidError> {
         for target in &self.targets {
             match target {
@@ -64,10 +109,10 @@ impl OutputHandler {
                         .map_err(|e| MermaidError::ClipboardError(e.to_string()))?;
                 }
                 OutputTarget::Browser => {
-                    // Write to temp file and open
-                    let temp_path = std::env::temp_dir().join("mermaid-output.svg");
-                    fs::write(&temp_path, content).await?;
-                    open::that(&temp_path)?;
+                    let path = self
+                        .viewer_path(content.as_bytes(), OutputFormat::Svg.extension())
+                        .await?;
+                    open_in_viewer(&path);
                 }
             }
         }
@@ -85,20 +130,33 @@ impl OutputHandler {
                     std::io::stdout().write_all(content)?;
                 }
                 OutputTarget::Clipboard => {
-                    // PNG to clipboard requires image crate integration
-                    // For now, warn the user
-                    eprintln!("Warning: PNG clipboard not yet implemented");
+                    // Clipboards are often unavailable (headless, SSH); don't fail the write
+                    if let Err(e) = copy_png_to_clipboard(content) {
+                        eprintln!("Warning: could not copy PNG to clipboard: {}", e);
+                    }
                 }
                 OutputTarget::Browser => {
-                    let temp_path = std::env::temp_dir().join("mermaid-output.png");
-                    fs::write(&temp_path, content).await?;
-                    open::that(&temp_path)?;
+                    let path = self
+                        .viewer_path(content, OutputFormat::Png.extension())
+                        .await?;
+                    open_in_viewer(&path);
                 }
             }
         }
         Ok(())
     }
 
+    /// Write rendered bytes to an arbitrary writer instead of the configured targets
+    pub async fn write_to<W: AsyncWrite + Unpin>(
+        &self,
+        content: &[u8],
+        writer: &mut W,
+    ) -> Result<(), MermaidError> {
+        writer.write_all(content).await?;
+        writer.flush().await?;
+        Ok(())
+    }
+
     pub async fn write_mermaid(&self, content: &str) -> Result<(), MermaidError> {
         for target in &self.targets {
             match target {
@@ -119,7 +177,10 @@ impl OutputHandler {
                     // Open mermaid.live with the diagram
                     let encoded = URL_SAFE_NO_PAD.encode(content.as_bytes());
                     let url = format!("https://mermaid.live/edit#base64:{}", encoded);
-                    open::that(&url)?;
+                    if let Err(e) = launch(&url) {
+                        eprintln!("Warning: could not open browser: {}", e);
+                        eprintln!("Open this URL to view the diagram: {}", url);
+                    }
                 }
             }
         }
@@ -127,10 +188,154 @@ impl OutputHandler {
     }
 }
 
+/// A rendered file shown on a gallery page
+#[derive(Debug, Clone)]
+pub struct GalleryEntry {
+    pub path: PathBuf,
+    pub caption: String,
+}
+
+impl GalleryEntry {
+    pub fn new(path: impl Into<PathBuf>, caption: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            caption: caption.into(),
+        }
+    }
+
+    /// Entry captioned with the file stem, e.g. `orders` for `out/orders.svg`
+    pub fn from_path(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let caption = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self { path, caption }
+    }
+}
+
+/// An `index.html` page embedding each SVG/PNG entry with its caption.
+///
+/// SVGs use `<object>` so their text stays selectable; PNGs use `<img>`.
+/// Paths are written as given, so pass them relative to the page.
+pub fn gallery_html(entries: &[GalleryEntry]) -> String {
+    let mut html = String::from(concat!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n",
+        "<title>Diagrams</title>\n<style>\n",
+        "figure { display: inline-block; margin: 1em; vertical-align: top; }\n",
+        "figure object, figure img { max-width: 480px; }\n",
+        "</style>\n</head>\n<body>\n"
+    ));
+    for entry in entries {
+        let src = escape_xml(&entry.path.to_string_lossy());
+        let caption = escape_xml(&entry.caption);
+        let extension = entry.path.extension().and_then(OsStr::to_str).unwrap_or("");
+        let media = match OutputFormat::from_extension(extension) {
+            Some(OutputFormat::Svg) => format!(
+                "<object data=\"{}\" type=\"{}\">{}</object>",
+                src,
+                OutputFormat::Svg.mime_type(),
+                caption
+            ),
+            _ => format!("<img src=\"{}\" alt=\"{}\">", src, caption),
+        };
+        html.push_str(&format!(
+            "<figure>\n<a href=\"{}\">{}</a>\n<figcaption>{}</figcaption>\n</figure>\n",
+            src, media, caption
+        ));
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Write `index.html` for the entries into `dir`, returning its path
+pub async fn write_gallery(dir: &Path, entries: &[GalleryEntry]) -> Result<PathBuf, MermaidError> {
+    let path = dir.join("index.html");
+    fs::write(&path, gallery_html(entries)).await?;
+    Ok(path)
+}
+
+/// Per-process temp file for `--open`, so concurrent runs don't clobber each other
+pub fn viewer_temp_path(extension: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("mermaid-{}.{}", std::process::id(), extension))
+}
+
+/// Commands the OS default opener would try for a path, in order
+pub fn open_commands(path: &Path) -> Vec<Command> {
+    open::commands(path)
+}
+
+/// Whether a graphical session is available to show a viewer in
+fn has_display() -> bool {
+    if cfg!(any(
+        target_os = "linux",
+        target_os = "freebsd",
+        target_os = "openbsd"
+    )) {
+        std::env::var_os("DISPLAY").is_some() || std::env::var_os("WAYLAND_DISPLAY").is_some()
+    } else {
+        true
+    }
+}
+
+fn launch(target: impl AsRef<OsStr>) -> std::io::Result<()> {
+    if !has_display() {
+        return Err(std::io::Error::other("no display available"));
+    }
+    open::that_detached(target)
+}
+
+/// Open a rendered file in the default viewer; headless sessions only get a warning
+fn open_in_viewer(path: &Path) {
+    if let Err(e) = launch(path) {
+        eprintln!("Warning: could not open {}: {}", path.display(), e);
+        eprintln!("Rendered output written to {}", path.display());
+    }
+}
+
+#[cfg(feature = "clipboard-image")]
+fn copy_png_to_clipboard(content: &[u8]) -> Result<(), MermaidError> {
+    let image = decode_png(content)?;
+    let mut clipboard =
+        Clipboard::new().map_err(|e| MermaidError::ClipboardError(e.to_string()))?;
+    clipboard
+        .set_image(image)
+        .map_err(|e| MermaidError::ClipboardError(e.to_string()))
+}
+
+#[cfg(not(feature = "clipboard-image"))]
+fn copy_png_to_clipboard(_content: &[u8]) -> Result<(), MermaidError> {
+    Err(MermaidError::ClipboardError(
+        "built without the clipboard-image feature".to_string(),
+    ))
+}
+
+/// Decode PNG bytes into the RGBA pixel data arboard expects
+#[cfg(feature = "clipboard-image")]
+fn decode_png(content: &[u8]) -> Result<arboard::ImageData<'static>, MermaidError> {
+    let rgba = image::load_from_memory_with_format(content, image::ImageFormat::Png)
+        .map_err(|e| MermaidError::ClipboardError(e.to_string()))?
+        .to_rgba8();
+    let (width, height) = rgba.dimensions();
+    Ok(arboard::ImageData {
+        width: width as usize,
+        height: height as usize,
+        bytes: rgba.into_raw().into(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn output_handler_write_to_writer() {
+        let handler = OutputHandler::new(None, false, false, false);
+        let mut buffer: Vec<u8> = Vec::new();
+        handler.write_to(b"<svg></svg>", &mut buffer).await.unwrap();
+        assert_eq!(buffer, b"<svg></svg>");
+    }
+
     #[test]
     fn output_handler_default_to_stdout() {
         let handler = OutputHandler::new(None, false, false, false);
@@ -143,4 +348,86 @@ mod tests {
         let handler = OutputHandler::new(Some(PathBuf::from("test.svg")), true, true, false);
         assert_eq!(handler.targets().len(), 3);
     }
+
+    #[test]
+    fn output_handler_with_extension() {
+        let handler = OutputHandler::new(Some(PathBuf::from("out/chart.svg")), true, false, false);
+        let png = handler.with_extension("png");
+        assert!(matches!(
+            &png.targets()[0],
+            OutputTarget::File(p) if p == &PathBuf::from("out/chart.png")
+        ));
+        assert!(png.writes_to_stdout());
+    }
+
+    #[tokio::test]
+    async fn open_without_output_writes_temp_file() {
+        let handler = OutputHandler::new(None, false, false, true);
+        assert!(matches!(handler.targets()[0], OutputTarget::Browser));
+
+        let path = handler.viewer_path(b"<svg></svg>", "svg").await.unwrap();
+        assert_eq!(path, viewer_temp_path("svg"));
+        assert_eq!(std::fs::read(&path).unwrap(), b"<svg></svg>");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn open_with_output_reuses_file() {
+        let handler = OutputHandler::new(Some(PathBuf::from("out/chart.png")), false, false, true);
+        let path = handler.viewer_path(b"png", "png").await.unwrap();
+        assert_eq!(path, PathBuf::from("out/chart.png"));
+    }
+
+    #[tokio::test]
+    async fn gallery_references_outputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let entries = vec![
+            GalleryEntry::from_path("orders.svg"),
+            GalleryEntry::new("pets.png", "Pets & <friends>"),
+        ];
+
+        let path = write_gallery(dir.path(), &entries).await.unwrap();
+        assert_eq!(path, dir.path().join("index.html"));
+
+        let html = std::fs::read_to_string(path).unwrap();
+        assert!(html.contains("<object data=\"orders.svg\" type=\"image/svg+xml\">orders</object>"));
+        assert!(html.contains("<figcaption>orders</figcaption>"));
+        assert!(html.contains("<img src=\"pets.png\" alt=\"Pets &amp; &lt;friends&gt;\">"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn open_commands_pass_path() {
+        let path = viewer_temp_path("svg");
+        assert!(path.starts_with(std::env::temp_dir()));
+        assert!(path.to_string_lossy().ends_with(".svg"));
+
+        let commands = open_commands(&path);
+        assert!(!commands.is_empty());
+        for command in &commands {
+            assert!(command.get_args().any(|arg| arg == path.as_os_str()));
+        }
+    }
+
+    #[cfg(feature = "clipboard-image")]
+    #[test]
+    fn png_clipboard_decodes_image() {
+        let mut png = Vec::new();
+        image::RgbaImage::from_pixel(2, 1, image::Rgba([255, 0, 0, 255]))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let handler = OutputHandler::new(None, false, true, false);
+        assert!(matches!(handler.targets()[0], OutputTarget::Clipboard));
+
+        let data = decode_png(&png).unwrap();
+        assert_eq!((data.width, data.height), (2, 1));
+        assert_eq!(&data.bytes[..4], &[255, 0, 0, 255]);
+    }
+
+    #[cfg(feature = "clipboard-image")]
+    #[test]
+    fn png_clipboard_rejects_non_png() {
+        assert!(decode_png(b"<svg></svg>").is_err());
+    }
 }
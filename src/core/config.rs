@@ -1,17 +1,28 @@
 use clap::ValueEnum;
+
+use crate::core::MermaidError;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::str::FromStr;
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Config {
     #[serde(default)]
     pub theme: Theme,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(alias = "themeVariables", skip_serializing_if = "Option::is_none")]
     pub theme_variables: Option<ThemeVariables>,
+    /// Flowchart layout settings (curve, spacing, html labels)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flowchart: Option<FlowchartConfig>,
+    /// Layout engine; dagre is mermaid's default and is never emitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layout: Option<Layout>,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum Theme {
     #[default]
@@ -56,25 +67,51 @@ impl FromStr for Theme {
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum Mode {
     Light,
     #[default]
     Dark,
+    /// Light or Dark, detected from the terminal background (`COLORFGBG`)
+    Auto,
 }
 
 impl Mode {
-    pub fn theme(&self) -> Theme {
+    /// Resolve `Auto` to Light or Dark; falls back to Dark when undetectable
+    pub fn resolve(&self) -> Mode {
         match self {
+            Self::Auto => std::env::var("COLORFGBG")
+                .ok()
+                .and_then(|value| Self::from_colorfgbg(&value))
+                .unwrap_or(Self::Dark),
+            mode => *mode,
+        }
+    }
+
+    /// Interpret a `COLORFGBG` value ("fg;bg" or "fg;default;bg").
+    ///
+    /// Background colors 0-6 and 8 are dark; 7 and 9-15 are light.
+    pub fn from_colorfgbg(value: &str) -> Option<Mode> {
+        let bg: u8 = value.rsplit(';').next()?.trim().parse().ok()?;
+        match bg {
+            0..=6 | 8 => Some(Self::Dark),
+            7 | 9..=15 => Some(Self::Light),
+            _ => None,
+        }
+    }
+
+    pub fn theme(&self) -> Theme {
+        match self.resolve() {
             Self::Light => Theme::Default,
-            Self::Dark => Theme::Dark,
+            _ => Theme::Dark,
         }
     }
 
     pub fn background_color(&self) -> Option<&'static str> {
-        match self {
+        match self.resolve() {
             Self::Light => None,
-            Self::Dark => Some("#1e1e1e"),
+            _ => Some("#1e1e1e"),
         }
     }
 
@@ -82,6 +119,7 @@ impl Mode {
         match self {
             Self::Light => "light",
             Self::Dark => "dark",
+            Self::Auto => "auto",
         }
     }
 }
@@ -99,12 +137,17 @@ impl FromStr for Mode {
         match s.to_lowercase().as_str() {
             "light" => Ok(Self::Light),
             "dark" => Ok(Self::Dark),
-            _ => Err(format!("Invalid mode: {}. Use 'light' or 'dark'", s)),
+            "auto" => Ok(Self::Auto),
+            _ => Err(format!(
+                "Invalid mode: {}. Use 'light', 'dark' or 'auto'",
+                s
+            )),
         }
     }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ThemeVariables {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -117,6 +160,168 @@ pub struct ThemeVariables {
     pub primary_text_color: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub line_color: Option<String>,
+    /// Pie slice colors, emitted as `pie1`, `pie2`, ... in slice order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pie_colors: Vec<String>,
+    /// Any other theme variable, keyed by its mermaid name (e.g. `fontFamily`)
+    #[serde(flatten, default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl ThemeVariables {
+    /// Set a variable by its mermaid name; unknown names go to `extra`
+    pub fn set(&mut self, key: &str, value: impl Into<String>) {
+        let value = value.into();
+        match key {
+            "primaryColor" | "primary_color" => self.primary_color = Some(value),
+            "secondaryColor" | "secondary_color" => self.secondary_color = Some(value),
+            "tertiaryColor" | "tertiary_color" => self.tertiary_color = Some(value),
+            "primaryTextColor" | "primary_text_color" => self.primary_text_color = Some(value),
+            "lineColor" | "line_color" => self.line_color = Some(value),
+            _ => {
+                self.extra
+                    .insert(key.to_string(), serde_json::Value::String(value));
+            }
+        }
+    }
+}
+
+/// How `build_script` embeds title and config in the generated script
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ScriptStyle {
+    /// `%%{init}%%` directive (works with older mermaid and GitHub)
+    #[default]
+    InitDirective,
+    /// `---` frontmatter block with a `config:` key
+    Frontmatter,
+}
+
+/// Layout engine used to position nodes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Layout {
+    #[default]
+    Dagre,
+    Elk,
+}
+
+impl Layout {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Dagre => "dagre",
+            Self::Elk => "elk",
+        }
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Layout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dagre" => Ok(Self::Dagre),
+            "elk" => Ok(Self::Elk),
+            _ => Err(format!("Invalid layout: {}. Use 'dagre' or 'elk'", s)),
+        }
+    }
+}
+
+/// Edge curve interpolation for flowchart links
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum Curve {
+    Basis,
+    BumpX,
+    BumpY,
+    Cardinal,
+    CatmullRom,
+    Linear,
+    MonotoneX,
+    MonotoneY,
+    Natural,
+    Step,
+    StepAfter,
+    StepBefore,
+}
+
+impl Curve {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Basis => "basis",
+            Self::BumpX => "bumpX",
+            Self::BumpY => "bumpY",
+            Self::Cardinal => "cardinal",
+            Self::CatmullRom => "catmullRom",
+            Self::Linear => "linear",
+            Self::MonotoneX => "monotoneX",
+            Self::MonotoneY => "monotoneY",
+            Self::Natural => "natural",
+            Self::Step => "step",
+            Self::StepAfter => "stepAfter",
+            Self::StepBefore => "stepBefore",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "basis" => Some(Self::Basis),
+            "bumpx" => Some(Self::BumpX),
+            "bumpy" => Some(Self::BumpY),
+            "cardinal" => Some(Self::Cardinal),
+            "catmullrom" => Some(Self::CatmullRom),
+            "linear" => Some(Self::Linear),
+            "monotonex" => Some(Self::MonotoneX),
+            "monotoney" => Some(Self::MonotoneY),
+            "natural" => Some(Self::Natural),
+            "step" => Some(Self::Step),
+            "stepafter" => Some(Self::StepAfter),
+            "stepbefore" => Some(Self::StepBefore),
+            _ => None,
+        }
+    }
+}
+
+/// Flowchart-specific config (`%%{init: {'flowchart': {...}}}%%`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FlowchartConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub curve: Option<Curve>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html_labels: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_spacing: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rank_spacing: Option<u32>,
+}
+
+impl FlowchartConfig {
+    /// (key, value) pairs for the options that are set, in mermaid's camelCase
+    fn entries(&self) -> Vec<(&'static str, String)> {
+        let mut entries = Vec::new();
+        if let Some(curve) = &self.curve {
+            entries.push(("curve", format!("'{}'", curve.as_str())));
+        }
+        if let Some(html_labels) = self.html_labels {
+            entries.push(("htmlLabels", html_labels.to_string()));
+        }
+        if let Some(spacing) = self.node_spacing {
+            entries.push(("nodeSpacing", spacing.to_string()));
+        }
+        if let Some(spacing) = self.rank_spacing {
+            entries.push(("rankSpacing", spacing.to_string()));
+        }
+        entries
+    }
 }
 
 impl Config {
@@ -134,6 +339,36 @@ impl Config {
         self
     }
 
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.layout = Some(layout);
+        self
+    }
+
+    /// The layout to emit, if it differs from mermaid's dagre default
+    fn layout_override(&self) -> Option<Layout> {
+        self.layout.filter(|l| *l != Layout::Dagre)
+    }
+
+    pub fn with_flowchart(mut self, flowchart: FlowchartConfig) -> Self {
+        self.flowchart = Some(flowchart);
+        self
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, MermaidError> {
+        let config: Self = serde_json::from_str(json)?;
+        Ok(config)
+    }
+
+    pub fn from_yaml(yaml: &str) -> Result<Self, MermaidError> {
+        let config: Self = serde_yaml::from_str(yaml)?;
+        Ok(config)
+    }
+
+    pub fn from_toml(toml: &str) -> Result<Self, MermaidError> {
+        let config: Self = toml::from_str(toml)?;
+        Ok(config)
+    }
+
     pub fn to_yaml(&self) -> String {
         let mut yaml = String::new();
         yaml.push_str(&format!("theme: {}\n", self.theme.as_str()));
@@ -154,10 +389,39 @@ impl Config {
             if let Some(c) = &vars.line_color {
                 yaml.push_str(&format!("  lineColor: \"{}\"\n", c));
             }
+            for (i, c) in vars.pie_colors.iter().enumerate() {
+                yaml.push_str(&format!("  pie{}: \"{}\"\n", i + 1, c));
+            }
+            for (key, value) in &vars.extra {
+                // JSON scalars are valid YAML flow scalars
+                yaml.push_str(&format!("  {}: {}\n", key, value));
+            }
+        }
+        if let Some(layout) = self.layout_override() {
+            yaml.push_str(&format!("layout: {}\n", layout.as_str()));
+        }
+        if let Some(flowchart) = &self.flowchart {
+            let entries = flowchart.entries();
+            if !entries.is_empty() {
+                yaml.push_str("flowchart:\n");
+                for (key, value) in entries {
+                    yaml.push_str(&format!("  {}: {}\n", key, value.replace('\'', "")));
+                }
+            }
         }
         yaml
     }
 
+    /// Generate a `---` frontmatter block with this config under `config:`
+    pub fn to_frontmatter(&self) -> String {
+        let mut frontmatter = String::from("---\nconfig:\n");
+        for line in self.to_yaml().lines() {
+            frontmatter.push_str(&format!("  {}\n", line));
+        }
+        frontmatter.push_str("---");
+        frontmatter
+    }
+
     /// Generate %%{init}%% directive for mermaid.ink compatibility
     pub fn to_init_directive(&self) -> String {
         let mut parts = vec![format!("'theme': '{}'", self.theme.as_str())];
@@ -165,29 +429,68 @@ impl Config {
         if let Some(vars) = &self.theme_variables {
             let mut var_parts = Vec::new();
             if let Some(c) = &vars.primary_color {
-                var_parts.push(format!("'primaryColor': '{}'", c));
+                var_parts.push(format!("'primaryColor': {}", init_string(c)));
             }
             if let Some(c) = &vars.secondary_color {
-                var_parts.push(format!("'secondaryColor': '{}'", c));
+                var_parts.push(format!("'secondaryColor': {}", init_string(c)));
             }
             if let Some(c) = &vars.tertiary_color {
-                var_parts.push(format!("'tertiaryColor': '{}'", c));
+                var_parts.push(format!("'tertiaryColor': {}", init_string(c)));
             }
             if let Some(c) = &vars.primary_text_color {
-                var_parts.push(format!("'primaryTextColor': '{}'", c));
+                var_parts.push(format!("'primaryTextColor': {}", init_string(c)));
             }
             if let Some(c) = &vars.line_color {
-                var_parts.push(format!("'lineColor': '{}'", c));
+                var_parts.push(format!("'lineColor': {}", init_string(c)));
+            }
+            for (i, c) in vars.pie_colors.iter().enumerate() {
+                var_parts.push(format!("'pie{}': {}", i + 1, init_string(c)));
+            }
+            for (key, value) in &vars.extra {
+                var_parts.push(format!("'{}': {}", key, init_value(value)));
             }
             if !var_parts.is_empty() {
                 parts.push(format!("'themeVariables': {{{}}}", var_parts.join(", ")));
             }
         }
 
+        let mut flow_parts: Vec<String> = self
+            .flowchart
+            .iter()
+            .flat_map(|f| f.entries())
+            .map(|(key, value)| format!("'{}': {}", key, value))
+            .collect();
+        if let Some(layout) = self.layout_override() {
+            flow_parts.push(format!("'defaultRenderer': '{}'", layout.as_str()));
+        }
+        if !flow_parts.is_empty() {
+            parts.push(format!("'flowchart': {{{}}}", flow_parts.join(", ")));
+        }
+
         format!("%%{{init: {{{}}}}}%%", parts.join(", "))
     }
 }
 
+/// Quote a string for an init directive.
+///
+/// Mermaid swaps every `'` for `"` before parsing the directive as JSON, so a
+/// literal quote is written as a `\u0027` escape instead.
+fn init_string(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\'', "\\u0027");
+    format!("'{}'", escaped)
+}
+
+/// Render a theme variable of any JSON type for an init directive
+fn init_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => init_string(s),
+        other => other.to_string().replace('\'', "\\u0027"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -210,4 +513,124 @@ mod tests {
         let config = Config::new().with_theme(Theme::Forest);
         assert!(config.to_yaml().contains("theme: forest"));
     }
+
+    #[test]
+    fn config_from_yaml_theme_variables() {
+        let yaml = "theme: base\nthemeVariables:\n  primaryColor: \"#ff0000\"\n";
+        let config = Config::from_yaml(yaml).unwrap();
+        assert_eq!(config.theme, Theme::Base);
+        let directive = config.to_init_directive();
+        assert!(directive.contains("'theme': 'base'"));
+        assert!(directive.contains("'primaryColor': '#ff0000'"));
+    }
+
+    #[test]
+    fn theme_variables_set_known_and_extra() {
+        let mut vars = ThemeVariables::default();
+        vars.set("primaryColor", "#ff0000");
+        vars.set("fontFamily", "monospace");
+        assert_eq!(vars.primary_color.as_deref(), Some("#ff0000"));
+        assert_eq!(vars.extra.get("fontFamily"), Some(&"monospace".into()));
+
+        let config = Config::new().with_theme_variables(vars);
+        let directive = config.to_init_directive();
+        assert!(directive.contains("'primaryColor': '#ff0000', 'fontFamily': 'monospace'"));
+        assert!(config.to_yaml().contains("  fontFamily: \"monospace\"\n"));
+
+        let yaml = "themeVariables:\n  fontFamily: monospace\n";
+        let config = Config::from_yaml(yaml).unwrap();
+        let vars = config.theme_variables.unwrap();
+        assert_eq!(vars.extra.get("fontFamily"), Some(&"monospace".into()));
+    }
+
+    #[test]
+    fn theme_variables_accept_non_string_values() {
+        let yaml = "themeVariables:\n  darkMode: true\n  fontSize: 16\n";
+        let config = Config::from_yaml(yaml).unwrap();
+        let directive = config.to_init_directive();
+        assert!(directive.contains("'darkMode': true, 'fontSize': 16"));
+        assert!(config
+            .to_yaml()
+            .contains("  darkMode: true\n  fontSize: 16\n"));
+    }
+
+    #[test]
+    fn init_directive_escapes_quotes() {
+        let mut vars = ThemeVariables::default();
+        vars.set("fontFamily", "'Fira Code', monospace");
+        vars.set("primaryColor", "#f00");
+        let directive = Config::new().with_theme_variables(vars).to_init_directive();
+        assert!(directive.contains("'primaryColor': '#f00'"));
+        assert!(directive.contains("'fontFamily': '\\u0027Fira Code\\u0027, monospace'"));
+    }
+
+    #[test]
+    fn config_from_json_and_toml() {
+        let json = r##"{"theme": "forest", "theme_variables": {"lineColor": "#333"}}"##;
+        let config = Config::from_json(json).unwrap();
+        assert_eq!(config.theme, Theme::Forest);
+        assert!(config.to_init_directive().contains("'lineColor': '#333'"));
+
+        let toml = "theme = \"neutral\"\n";
+        assert_eq!(Config::from_toml(toml).unwrap().theme, Theme::Neutral);
+    }
+
+    #[test]
+    fn config_flowchart_in_init_directive() {
+        let config = Config::new().with_flowchart(FlowchartConfig {
+            curve: Some(Curve::Basis),
+            html_labels: Some(false),
+            node_spacing: Some(40),
+            ..Default::default()
+        });
+        let directive = config.to_init_directive();
+        assert!(directive
+            .contains("'flowchart': {'curve': 'basis', 'htmlLabels': false, 'nodeSpacing': 40}"));
+        assert!(config.to_yaml().contains("flowchart:\n  curve: basis\n"));
+    }
+
+    #[test]
+    fn config_flowchart_from_yaml() {
+        let yaml = "flowchart:\n  curve: stepBefore\n  rankSpacing: 80\n";
+        let config = Config::from_yaml(yaml).unwrap();
+        let flowchart = config.flowchart.unwrap();
+        assert_eq!(flowchart.curve, Some(Curve::StepBefore));
+        assert_eq!(flowchart.rank_spacing, Some(80));
+    }
+
+    #[test]
+    fn config_layout_elk() {
+        let config = Config::new().with_layout(Layout::Elk);
+        assert!(config
+            .to_init_directive()
+            .contains("'flowchart': {'defaultRenderer': 'elk'}"));
+        assert_eq!(
+            config.to_frontmatter(),
+            "---\nconfig:\n  theme: default\n  layout: elk\n---"
+        );
+    }
+
+    #[test]
+    fn config_layout_defaults_to_dagre_omitted() {
+        for config in [Config::new(), Config::new().with_layout(Layout::Dagre)] {
+            assert!(!config.to_init_directive().contains("flowchart"));
+            assert!(!config.to_yaml().contains("layout"));
+        }
+    }
+
+    #[test]
+    fn mode_from_colorfgbg() {
+        assert_eq!(Mode::from_colorfgbg("15;0"), Some(Mode::Dark));
+        assert_eq!(Mode::from_colorfgbg("0;15"), Some(Mode::Light));
+        assert_eq!(Mode::from_colorfgbg("0;default;15"), Some(Mode::Light));
+        assert_eq!(Mode::from_colorfgbg("garbage"), None);
+    }
+
+    #[test]
+    fn mode_explicit_ignores_detection() {
+        assert_eq!(Mode::Light.resolve(), Mode::Light);
+        assert_eq!(Mode::Dark.resolve(), Mode::Dark);
+        assert_eq!(Mode::Light.theme(), Theme::Default);
+        assert_eq!(Mode::Dark.background_color(), Some("#1e1e1e"));
+    }
 }
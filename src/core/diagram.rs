@@ -1,10 +1,19 @@
-use crate::core::{Config, MermaidError};
+use std::borrow::Cow;
+
+use crate::core::{
+    embeds_config, statement_count, yaml_scalar, Config, FormatOptions, MermaidError, ScriptStyle,
+};
 
 /// Trait implemented by all diagram types
 pub trait Diagram: Send + Sync {
     /// Returns the mermaid syntax string for this diagram
     fn to_mermaid(&self) -> String;
 
+    /// Returns the mermaid syntax laid out according to `options`
+    fn to_mermaid_with(&self, options: &FormatOptions) -> String {
+        options.apply(&self.to_mermaid())
+    }
+
     /// Returns the diagram type identifier (e.g., "flowchart", "sequenceDiagram")
     fn diagram_type(&self) -> &'static str;
 
@@ -14,17 +23,101 @@ pub trait Diagram: Send + Sync {
     /// Returns optional configuration
     fn config(&self) -> Option<&Config>;
 
-    /// Builds the complete mermaid script including init directive
+    /// Returns true when the diagram has nothing to draw beyond its header.
+    ///
+    /// The default counts the statements in [`Self::to_mermaid`]; diagram types
+    /// that model their elements check their collections instead.
+    fn is_empty(&self) -> bool {
+        statement_count(&self.to_mermaid()) == 0
+    }
+
+    /// Returns a rough size score, for warning before rendering very large diagrams.
+    ///
+    /// Diagram types that model their elements count them (nodes, edges,
+    /// participants, ...); the default is the length of [`Self::to_mermaid`].
+    /// Scores are only comparable between diagrams of the same type.
+    fn estimated_complexity(&self) -> usize {
+        self.to_mermaid().len()
+    }
+
+    /// Returns comments rendered as `%%` lines after the diagram-type line
+    fn comments(&self) -> &[String] {
+        &[]
+    }
+
+    /// Returns a title to emit in a `---` frontmatter block rather than in the body
+    fn frontmatter_title(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the config embedded in the script; defaults to [`Self::config`]
+    fn script_config(&self) -> Option<Cow<'_, Config>> {
+        self.config().map(Cow::Borrowed)
+    }
+
+    /// Builds the complete mermaid script including frontmatter and init directive
     fn build_script(&self) -> String {
+        self.build_script_with(ScriptStyle::default())
+    }
+
+    /// Builds the complete mermaid script, embedding config as `style` dictates.
+    ///
+    /// Mermaid that already carries frontmatter or an init directive (e.g. from
+    /// `from_raw_mermaid`) is left as written.
+    fn build_script_with(&self, style: ScriptStyle) -> String {
         let mut script = String::new();
+        let mermaid = self.to_mermaid();
+        let embedded = embeds_config(&mermaid);
+        let title = self.frontmatter_title().filter(|_| !embedded);
+        let config = self.script_config().filter(|_| !embedded);
+
+        match style {
+            ScriptStyle::InitDirective => {
+                if let Some(title) = title {
+                    script.push_str(&format!("---\ntitle: {}\n---\n", yaml_scalar(title)));
+                }
+
+                // Add %%{init}%% directive for config (mermaid.ink compatible)
+                if let Some(config) = &config {
+                    script.push_str(&config.to_init_directive());
+                    script.push('\n');
+                }
+            }
+            ScriptStyle::Frontmatter => {
+                if title.is_some() || config.is_some() {
+                    script.push_str("---\n");
+                    if let Some(title) = title {
+                        script.push_str(&format!("title: {}\n", yaml_scalar(title)));
+                    }
+                    if let Some(config) = &config {
+                        script.push_str("config:\n");
+                        for line in config.to_yaml().lines() {
+                            script.push_str(&format!("  {}\n", line));
+                        }
+                    }
+                    script.push_str("---\n");
+                }
+            }
+        }
 
-        // Add %%{init}%% directive for config (mermaid.ink compatible)
-        if let Some(config) = self.config() {
-            script.push_str(&config.to_init_directive());
-            script.push('\n');
+        let comments = self.comments();
+        if comments.is_empty() {
+            script.push_str(&mermaid);
+            return script;
         }
 
-        script.push_str(&self.to_mermaid());
+        // Comments go right after the diagram-type line; multi-line comments
+        // become one `%%` line each
+        let (header, body) = mermaid.split_once('\n').unwrap_or((&mermaid, ""));
+        script.push_str(header);
+        for line in comments.iter().flat_map(|c| c.lines()) {
+            script.push_str("\n%% ");
+            script.push_str(line);
+        }
+        if !body.is_empty() {
+            script.push('\n');
+            script.push_str(body);
+        }
         script
     }
 }
@@ -63,6 +156,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn default_is_empty_counts_statements() {
+        let diagram = TestDiagram {
+            title: None,
+            config: None,
+        };
+        assert!(!diagram.is_empty());
+    }
+
     #[test]
     fn build_script_without_frontmatter() {
         let diagram = TestDiagram {
@@ -73,6 +175,46 @@ mod tests {
         assert_eq!(script, "graph TD\n  A --> B");
     }
 
+    struct CommentedDiagram {
+        comments: Vec<String>,
+    }
+
+    impl Diagram for CommentedDiagram {
+        fn to_mermaid(&self) -> String {
+            "graph TD\n  A --> B".to_string()
+        }
+
+        fn diagram_type(&self) -> &'static str {
+            "flowchart"
+        }
+
+        fn title(&self) -> Option<&str> {
+            None
+        }
+
+        fn config(&self) -> Option<&Config> {
+            None
+        }
+
+        fn comments(&self) -> &[String] {
+            &self.comments
+        }
+    }
+
+    #[test]
+    fn build_script_with_comments() {
+        let diagram = CommentedDiagram {
+            comments: vec![
+                "generated by mermaid-rs".to_string(),
+                "source: a.yaml\nrev: 2".to_string(),
+            ],
+        };
+        assert_eq!(
+            diagram.build_script(),
+            "graph TD\n%% generated by mermaid-rs\n%% source: a.yaml\n%% rev: 2\n  A --> B"
+        );
+    }
+
     #[test]
     fn build_script_with_config() {
         let diagram = TestDiagram {
@@ -84,4 +226,29 @@ mod tests {
         assert!(script.contains("'theme': 'dark'"));
         assert!(script.contains("graph TD"));
     }
+
+    #[test]
+    fn estimated_complexity_defaults_to_length() {
+        let diagram = TestDiagram {
+            title: None,
+            config: None,
+        };
+        assert_eq!(diagram.estimated_complexity(), "graph TD\n  A --> B".len());
+    }
+
+    #[test]
+    fn build_script_with_frontmatter_style() {
+        let diagram = TestDiagram {
+            title: None,
+            config: Some(Config::new().with_theme(crate::core::Theme::Dark)),
+        };
+
+        let script = diagram.build_script_with(ScriptStyle::Frontmatter);
+        assert!(script.starts_with("---\nconfig:\n  theme: dark\n---\ngraph TD"));
+        assert!(!script.contains("%%{init"));
+
+        let script = diagram.build_script_with(ScriptStyle::InitDirective);
+        assert!(script.starts_with("%%{init:"));
+        assert!(!script.contains("config:"));
+    }
 }
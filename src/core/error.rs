@@ -11,6 +11,9 @@ pub enum MermaidError {
     #[error("HTTP request failed: {0}")]
     HttpError(#[from] reqwest::Error),
 
+    #[error("Network unreachable: {0} (check your network connection or the server URL)")]
+    NetworkUnreachable(String),
+
     #[error("Render failed: {0}")]
     RenderFailed(String),
 
@@ -20,6 +23,15 @@ pub enum MermaidError {
     #[error("Invalid input: {0}")]
     InvalidInput(String),
 
+    #[error("Unknown node shape '{0}'")]
+    UnknownShape(String),
+
+    #[error("Unsupported format: {0}")]
+    UnknownFormat(String),
+
+    #[error("{kind} '{id}' is referenced but not defined")]
+    DanglingReference { kind: String, id: String },
+
     #[error("Clipboard error: {0}")]
     ClipboardError(String),
 
@@ -32,3 +44,42 @@ pub enum MermaidError {
     #[error("TOML parse error: {0}")]
     TomlError(#[from] toml::de::Error),
 }
+
+impl MermaidError {
+    /// Process exit code for this error's category.
+    ///
+    /// 2 for invalid arguments and specs (shared with clap's usage errors, since
+    /// both mean the command line needs fixing), 3 for render/network errors,
+    /// 4 for IO errors, 5 for definition files that fail to parse, and 1 for
+    /// anything else.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::ConfigError(_)
+            | Self::InvalidInput(_)
+            | Self::UnknownShape(_)
+            | Self::UnknownFormat(_)
+            | Self::DanglingReference { .. } => 2,
+            Self::ParseError(_) | Self::JsonError(_) | Self::YamlError(_) | Self::TomlError(_) => 5,
+            Self::HttpError(_) | Self::NetworkUnreachable(_) | Self::RenderFailed(_) => 3,
+            Self::IoError(_) => 4,
+            Self::ClipboardError(_) => 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_code_categories() {
+        assert_eq!(MermaidError::InvalidInput("x".into()).exit_code(), 2);
+        assert_eq!(MermaidError::UnknownShape("x".into()).exit_code(), 2);
+        assert_eq!(MermaidError::RenderFailed("x".into()).exit_code(), 3);
+        assert_eq!(MermaidError::NetworkUnreachable("x".into()).exit_code(), 3);
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        assert_eq!(MermaidError::from(io).exit_code(), 4);
+        assert_eq!(MermaidError::ClipboardError("x".into()).exit_code(), 1);
+        assert_eq!(MermaidError::ParseError("x".into()).exit_code(), 5);
+    }
+}
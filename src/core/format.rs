@@ -0,0 +1,129 @@
+/// Indentation unit for generated mermaid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentStyle {
+    Tabs,
+    Spaces(usize),
+}
+
+impl IndentStyle {
+    /// The string emitted for one indentation level
+    pub fn unit(&self) -> String {
+        match self {
+            Self::Tabs => "\t".to_string(),
+            Self::Spaces(n) => " ".repeat(*n),
+        }
+    }
+}
+
+/// Options controlling how mermaid text is laid out
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FormatOptions {
+    /// Indentation to use; `None` keeps each diagram's native indentation
+    pub indent: Option<IndentStyle>,
+    /// Order nodes, edges, entities and participants by id instead of insertion
+    /// order, for diagrams that support it
+    pub sort_stable: bool,
+}
+
+impl FormatOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn indent(mut self, indent: IndentStyle) -> Self {
+        self.indent = Some(indent);
+        self
+    }
+
+    pub fn sort_stable(mut self, sort_stable: bool) -> Self {
+        self.sort_stable = sort_stable;
+        self
+    }
+
+    /// Apply these options to already-generated mermaid text
+    pub fn apply(&self, mermaid: &str) -> String {
+        match self.indent {
+            Some(style) => reindent(mermaid, style),
+            None => mermaid.to_string(),
+        }
+    }
+}
+
+/// Rewrite leading indentation, treating each tab or run of four spaces as one level.
+///
+/// A leading `---` frontmatter block and `%%{init}%%` lines are left as written,
+/// since they are YAML and JSON rather than diagram body.
+pub fn reindent(mermaid: &str, style: IndentStyle) -> String {
+    let unit = style.unit();
+    let mut in_frontmatter = false;
+    mermaid
+        .split('\n')
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 && line.trim_end() == "---" {
+                in_frontmatter = true;
+                return line.to_string();
+            }
+            if in_frontmatter {
+                in_frontmatter = line.trim_end() != "---";
+                return line.to_string();
+            }
+            if line.trim_start().starts_with("%%{") {
+                return line.to_string();
+            }
+
+            let mut rest = line;
+            let mut depth = 0;
+            loop {
+                if let Some(r) = rest.strip_prefix('\t') {
+                    rest = r;
+                } else if let Some(r) = rest.strip_prefix("    ") {
+                    rest = r;
+                } else {
+                    break;
+                }
+                depth += 1;
+            }
+            format!("{}{}", unit.repeat(depth), rest)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reindent_spaces_to_tabs() {
+        let input = "flowchart TB\n    a\n        b\n";
+        assert_eq!(
+            reindent(input, IndentStyle::Tabs),
+            "flowchart TB\n\ta\n\t\tb\n"
+        );
+    }
+
+    #[test]
+    fn reindent_tabs_to_spaces() {
+        let input = "pie\n\t\"Dogs\" : 10\n";
+        assert_eq!(
+            reindent(input, IndentStyle::Spaces(2)),
+            "pie\n  \"Dogs\" : 10\n"
+        );
+    }
+
+    #[test]
+    fn reindent_skips_frontmatter_and_init() {
+        let input = "---\nconfig:\n  themeVariables:\n    primaryColor: '#f00'\n---\n%%{init: {'theme': 'dark'}}%%\nflowchart TB\n    a\n";
+        assert_eq!(
+            reindent(input, IndentStyle::Tabs),
+            "---\nconfig:\n  themeVariables:\n    primaryColor: '#f00'\n---\n%%{init: {'theme': 'dark'}}%%\nflowchart TB\n\ta\n"
+        );
+    }
+
+    #[test]
+    fn format_options_default_is_unchanged() {
+        let input = "erDiagram\n\tA ||--o{ B : has\n";
+        assert_eq!(FormatOptions::new().apply(input), input);
+    }
+}
@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// The kinds of diagram supported by this crate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum DiagramKind {
+    Flowchart,
+    Sequence,
+    State,
+    #[serde(rename = "er")]
+    ER,
+    Pie,
+    Journey,
+    Mindmap,
+    Requirement,
+}
+
+impl DiagramKind {
+    /// All diagram kinds, in CLI subcommand order
+    pub const ALL: [DiagramKind; 8] = [
+        Self::ER,
+        Self::Flowchart,
+        Self::Journey,
+        Self::Mindmap,
+        Self::Pie,
+        Self::Requirement,
+        Self::Sequence,
+        Self::State,
+    ];
+
+    /// Short name, matching the CLI subcommand
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Flowchart => "flowchart",
+            Self::Sequence => "sequence",
+            Self::State => "state",
+            Self::ER => "er",
+            Self::Pie => "pie",
+            Self::Journey => "journey",
+            Self::Mindmap => "mindmap",
+            Self::Requirement => "requirement",
+        }
+    }
+
+    /// Default file name stem for this kind's output, e.g. `sequence-diagram.svg`
+    pub fn file_stub(&self) -> &'static str {
+        match self {
+            Self::Flowchart => "flowchart",
+            Self::Sequence => "sequence-diagram",
+            Self::State => "state-diagram",
+            Self::ER => "er-diagram",
+            Self::Pie => "pie-chart",
+            Self::Journey => "journey",
+            Self::Mindmap => "mindmap",
+            Self::Requirement => "requirement-diagram",
+        }
+    }
+
+    /// The keyword that opens a mermaid script of this kind
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            Self::Flowchart => "flowchart",
+            Self::Sequence => "sequenceDiagram",
+            Self::State => "stateDiagram-v2",
+            Self::ER => "erDiagram",
+            Self::Pie => "pie",
+            Self::Journey => "journey",
+            Self::Mindmap => "mindmap",
+            Self::Requirement => "requirementDiagram",
+        }
+    }
+
+    /// Match the leading keyword of a mermaid script (e.g. "graph", "stateDiagram")
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "flowchart" | "graph" => Some(Self::Flowchart),
+            "sequenceDiagram" => Some(Self::Sequence),
+            "stateDiagram" | "stateDiagram-v2" => Some(Self::State),
+            "erDiagram" => Some(Self::ER),
+            "pie" => Some(Self::Pie),
+            "journey" => Some(Self::Journey),
+            "mindmap" => Some(Self::Mindmap),
+            "requirementDiagram" => Some(Self::Requirement),
+            _ => None,
+        }
+    }
+
+    /// Parse a short name or mermaid keyword (case-insensitive)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "flowchart" | "graph" => Some(Self::Flowchart),
+            "sequence" | "sequencediagram" => Some(Self::Sequence),
+            "state" | "statediagram" | "statediagram-v2" => Some(Self::State),
+            "er" | "erdiagram" => Some(Self::ER),
+            "pie" => Some(Self::Pie),
+            "journey" => Some(Self::Journey),
+            "mindmap" => Some(Self::Mindmap),
+            "requirement" | "requirementdiagram" => Some(Self::Requirement),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DiagramKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for DiagramKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).ok_or_else(|| format!("Invalid diagram type: {}", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kind_parse() {
+        assert_eq!(
+            DiagramKind::parse("flowchart"),
+            Some(DiagramKind::Flowchart)
+        );
+        assert_eq!(
+            DiagramKind::parse("sequenceDiagram"),
+            Some(DiagramKind::Sequence)
+        );
+        assert_eq!(DiagramKind::parse("ER"), Some(DiagramKind::ER));
+        assert_eq!(DiagramKind::parse("invalid"), None);
+    }
+
+    #[test]
+    fn kind_file_stub() {
+        assert_eq!(DiagramKind::Flowchart.file_stub(), "flowchart");
+        assert_eq!(DiagramKind::ER.file_stub(), "er-diagram");
+        for kind in DiagramKind::ALL {
+            assert!(!kind.file_stub().contains(char::is_whitespace));
+        }
+    }
+
+    #[test]
+    fn kind_from_keyword() {
+        assert_eq!(
+            DiagramKind::from_keyword("graph"),
+            Some(DiagramKind::Flowchart)
+        );
+        assert_eq!(
+            DiagramKind::from_keyword("stateDiagram"),
+            Some(DiagramKind::State)
+        );
+        assert_eq!(DiagramKind::from_keyword("sequence"), None);
+    }
+}
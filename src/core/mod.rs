@@ -2,12 +2,21 @@ mod config;
 mod diagram;
 mod direction;
 mod error;
+mod format;
+mod kind;
 mod style;
 mod utils;
 
-pub use config::{Config, Mode, Theme, ThemeVariables};
+pub use config::{
+    Config, Curve, FlowchartConfig, Layout, Mode, ScriptStyle, Theme, ThemeVariables,
+};
 pub use diagram::{Diagram, FromConfig};
 pub use direction::Direction;
 pub use error::MermaidError;
+pub use format::{reindent, FormatOptions, IndentStyle};
+pub use kind::DiagramKind;
 pub use style::Style;
-pub use utils::normalize_id;
+pub(crate) use utils::{
+    accessibility_lines, embeds_config, render_id, statement_count, yaml_scalar,
+};
+pub use utils::{escape_text, normalize_id};
@@ -17,6 +17,128 @@ pub fn normalize_id(text: &str) -> String {
         .collect()
 }
 
+/// Render an id for output: normalized, or case-preserving when `preserve` is set.
+///
+/// Preserved ids still have characters mermaid can't read in an id replaced with `_`.
+pub(crate) fn render_id(text: &str, preserve: bool) -> String {
+    if !preserve {
+        return normalize_id(text);
+    }
+    text.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Escape free text for mermaid statements that use `:` or `;` as separators.
+///
+/// `:` and `;` are replaced with mermaid entity codes (`#58;`, `#59;`) so the
+/// text cannot be mistaken for syntax. `#` is encoded as `#35;` only where it
+/// would start an entity code (`#` followed by alphanumerics and `;`).
+pub fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for (i, c) in text.char_indices() {
+        match c {
+            '#' if starts_entity(&text[i + 1..]) => escaped.push_str("#35;"),
+            ':' => escaped.push_str("#58;"),
+            ';' => escaped.push_str("#59;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Whether the text after a `#` would complete an entity code like `#35;`
+fn starts_entity(rest: &str) -> bool {
+    let name_len = rest
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(rest.len());
+    name_len > 0 && rest[name_len..].starts_with(';')
+}
+
+/// Count the statements in a mermaid script: non-blank, non-comment lines after the header.
+///
+/// Used as the complexity of raw-mermaid diagrams, whose elements aren't modeled.
+pub(crate) fn statement_count(script: &str) -> usize {
+    script
+        .lines()
+        .map(str::trim)
+        .skip(1)
+        .filter(|l| !l.is_empty() && !l.starts_with("%%"))
+        .count()
+}
+
+/// Whether a script starts with frontmatter or contains an init directive
+pub(crate) fn embeds_config(mermaid: &str) -> bool {
+    mermaid.trim_start().starts_with("---") || mermaid.contains("%%{init")
+}
+
+/// Render a string as a YAML scalar, double-quoting it when a plain scalar
+/// would be misread (`: `, ` #`, leading indicators, booleans, numbers, ...).
+pub(crate) fn yaml_scalar(text: &str) -> String {
+    let plain = !text.is_empty()
+        && text.trim() == text
+        && !text.starts_with(|c: char| "-?:,[]{}#&*!|>'\"%@`".contains(c))
+        && !text.contains(": ")
+        && !text.contains(" #")
+        && !text.ends_with(':')
+        && !text.contains(|c: char| c.is_control())
+        && !matches!(
+            text.to_lowercase().as_str(),
+            "true" | "false" | "yes" | "no" | "on" | "off" | "null" | "~"
+        )
+        && text.parse::<f64>().is_err();
+    if plain {
+        return text.to_string();
+    }
+
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Render `accTitle`/`accDescr` accessibility lines, each prefixed with `indent`.
+///
+/// A multi-line description uses the `accDescr { ... }` block form.
+pub(crate) fn accessibility_lines(
+    acc_title: Option<&str>,
+    acc_descr: Option<&str>,
+    indent: &str,
+) -> String {
+    let mut output = String::new();
+    if let Some(title) = acc_title {
+        output.push_str(&format!("{}accTitle: {}\n", indent, title));
+    }
+    if let Some(descr) = acc_descr {
+        if descr.contains('\n') {
+            output.push_str(&format!("{}accDescr {{\n", indent));
+            for line in descr.lines() {
+                output.push_str(&format!("{}    {}\n", indent, line));
+            }
+            output.push_str(&format!("{}}}\n", indent));
+        } else {
+            output.push_str(&format!("{}accDescr: {}\n", indent, descr));
+        }
+    }
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -46,9 +168,40 @@ mod tests {
         assert_eq!(normalize_id("hello!world"), "hello_world");
     }
 
+    #[test]
+    fn escape_separators() {
+        assert_eq!(escape_text("GET /a:b"), "GET /a#58;b");
+        assert_eq!(escape_text("issue #4; done"), "issue #35;4#59; done");
+        assert_eq!(escape_text("plain text"), "plain text");
+        assert_eq!(escape_text("C# and #1"), "C# and #1");
+        assert_eq!(escape_text("#amp;"), "#35;amp#59;");
+    }
+
+    #[test]
+    fn yaml_scalar_quotes_when_needed() {
+        assert_eq!(yaml_scalar("Orders"), "Orders");
+        assert_eq!(yaml_scalar("Step 1: Build"), "\"Step 1: Build\"");
+        assert_eq!(yaml_scalar("say \"hi\" #1"), "\"say \\\"hi\\\" #1\"");
+        assert_eq!(yaml_scalar("true"), "\"true\"");
+        assert_eq!(yaml_scalar("42"), "\"42\"");
+    }
+
     #[test]
     fn normalize_mixed_case_with_spaces() {
         assert_eq!(normalize_id("User Authentication"), "user_authentication");
         assert_eq!(normalize_id("API Gateway"), "api_gateway");
     }
+
+    #[test]
+    fn accessibility_lines_single_and_multi_line() {
+        assert_eq!(accessibility_lines(None, None, "    "), "");
+        assert_eq!(
+            accessibility_lines(Some("Title"), Some("One line"), "\t"),
+            "\taccTitle: Title\n\taccDescr: One line\n"
+        );
+        assert_eq!(
+            accessibility_lines(None, Some("First\nSecond"), ""),
+            "accDescr {\n    First\n    Second\n}\n"
+        );
+    }
 }
@@ -0,0 +1,235 @@
+use crate::core::{
+    Config, Diagram, DiagramKind, FormatOptions, FromConfig, MermaidError, ScriptStyle,
+};
+
+use super::{
+    ERDiagram, FlowChart, Journey, Mindmap, PieChart, RequirementDiagram, SequenceDiagram,
+    StateDiagram,
+};
+
+/// Any concrete diagram, for code that needs to match on the kind without downcasting
+#[derive(Debug, Clone)]
+pub enum AnyDiagram {
+    Flowchart(FlowChart),
+    Sequence(SequenceDiagram),
+    State(StateDiagram),
+    ER(ERDiagram),
+    Pie(PieChart),
+    Journey(Journey),
+    Mindmap(Mindmap),
+    Requirement(RequirementDiagram),
+}
+
+impl AnyDiagram {
+    /// The kind of the wrapped diagram
+    pub fn kind(&self) -> DiagramKind {
+        match self {
+            Self::Flowchart(_) => DiagramKind::Flowchart,
+            Self::Sequence(_) => DiagramKind::Sequence,
+            Self::State(_) => DiagramKind::State,
+            Self::ER(_) => DiagramKind::ER,
+            Self::Pie(_) => DiagramKind::Pie,
+            Self::Journey(_) => DiagramKind::Journey,
+            Self::Mindmap(_) => DiagramKind::Mindmap,
+            Self::Requirement(_) => DiagramKind::Requirement,
+        }
+    }
+
+    pub fn from_json(kind: DiagramKind, json: &str) -> Result<Self, MermaidError> {
+        Self::parse(kind, json, "json")
+    }
+
+    pub fn from_yaml(kind: DiagramKind, yaml: &str) -> Result<Self, MermaidError> {
+        Self::parse(kind, yaml, "yaml")
+    }
+
+    pub fn from_toml(kind: DiagramKind, toml: &str) -> Result<Self, MermaidError> {
+        Self::parse(kind, toml, "toml")
+    }
+
+    /// Wrap a mermaid script as a raw passthrough diagram of `kind`
+    pub fn from_raw_mermaid(kind: DiagramKind, script: String) -> Self {
+        match kind {
+            DiagramKind::Flowchart => Self::Flowchart(FlowChart::from_raw_mermaid(script)),
+            DiagramKind::Sequence => Self::Sequence(SequenceDiagram::from_raw_mermaid(script)),
+            DiagramKind::State => Self::State(StateDiagram::from_raw_mermaid(script)),
+            DiagramKind::ER => Self::ER(ERDiagram::from_raw_mermaid(script)),
+            DiagramKind::Pie => Self::Pie(PieChart::from_raw_mermaid(script)),
+            DiagramKind::Journey => Self::Journey(Journey::from_raw_mermaid(script)),
+            DiagramKind::Mindmap => Self::Mindmap(Mindmap::from_raw_mermaid(script)),
+            DiagramKind::Requirement => {
+                Self::Requirement(RequirementDiagram::from_raw_mermaid(script))
+            }
+        }
+    }
+
+    fn parse(kind: DiagramKind, content: &str, format: &str) -> Result<Self, MermaidError> {
+        Ok(match kind {
+            DiagramKind::Flowchart => Self::Flowchart(parse_as(content, format)?),
+            DiagramKind::Sequence => Self::Sequence(parse_as(content, format)?),
+            DiagramKind::State => Self::State(parse_as(content, format)?),
+            DiagramKind::ER => Self::ER(parse_as(content, format)?),
+            DiagramKind::Pie => Self::Pie(parse_as(content, format)?),
+            DiagramKind::Journey => Self::Journey(parse_as(content, format)?),
+            DiagramKind::Mindmap => Self::Mindmap(parse_as(content, format)?),
+            DiagramKind::Requirement => Self::Requirement(parse_as(content, format)?),
+        })
+    }
+
+    fn inner(&self) -> &dyn Diagram {
+        match self {
+            Self::Flowchart(d) => d,
+            Self::Sequence(d) => d,
+            Self::State(d) => d,
+            Self::ER(d) => d,
+            Self::Pie(d) => d,
+            Self::Journey(d) => d,
+            Self::Mindmap(d) => d,
+            Self::Requirement(d) => d,
+        }
+    }
+}
+
+fn parse_as<D: FromConfig>(content: &str, format: &str) -> Result<D, MermaidError> {
+    match format {
+        "json" => D::from_json(content),
+        "toml" => D::from_toml(content),
+        _ => D::from_yaml(content),
+    }
+}
+
+impl Diagram for AnyDiagram {
+    fn to_mermaid(&self) -> String {
+        self.inner().to_mermaid()
+    }
+
+    fn to_mermaid_with(&self, options: &FormatOptions) -> String {
+        self.inner().to_mermaid_with(options)
+    }
+
+    fn diagram_type(&self) -> &'static str {
+        self.inner().diagram_type()
+    }
+
+    fn title(&self) -> Option<&str> {
+        self.inner().title()
+    }
+
+    fn config(&self) -> Option<&Config> {
+        self.inner().config()
+    }
+
+    fn estimated_complexity(&self) -> usize {
+        self.inner().estimated_complexity()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.inner().is_empty()
+    }
+
+    fn comments(&self) -> &[String] {
+        self.inner().comments()
+    }
+
+    fn frontmatter_title(&self) -> Option<&str> {
+        self.inner().frontmatter_title()
+    }
+
+    fn build_script(&self) -> String {
+        self.inner().build_script()
+    }
+
+    fn build_script_with(&self, style: ScriptStyle) -> String {
+        self.inner().build_script_with(style)
+    }
+}
+
+impl From<FlowChart> for AnyDiagram {
+    fn from(diagram: FlowChart) -> Self {
+        Self::Flowchart(diagram)
+    }
+}
+
+impl From<SequenceDiagram> for AnyDiagram {
+    fn from(diagram: SequenceDiagram) -> Self {
+        Self::Sequence(diagram)
+    }
+}
+
+impl From<StateDiagram> for AnyDiagram {
+    fn from(diagram: StateDiagram) -> Self {
+        Self::State(diagram)
+    }
+}
+
+impl From<ERDiagram> for AnyDiagram {
+    fn from(diagram: ERDiagram) -> Self {
+        Self::ER(diagram)
+    }
+}
+
+impl From<PieChart> for AnyDiagram {
+    fn from(diagram: PieChart) -> Self {
+        Self::Pie(diagram)
+    }
+}
+
+impl From<Journey> for AnyDiagram {
+    fn from(diagram: Journey) -> Self {
+        Self::Journey(diagram)
+    }
+}
+
+impl From<Mindmap> for AnyDiagram {
+    fn from(diagram: Mindmap) -> Self {
+        Self::Mindmap(diagram)
+    }
+}
+
+impl From<RequirementDiagram> for AnyDiagram {
+    fn from(diagram: RequirementDiagram) -> Self {
+        Self::Requirement(diagram)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn any_diagram_delegates_to_pie() {
+        let pie = PieChart::builder().title("Pets").data("Dogs", 10.0).build();
+        let any = AnyDiagram::Pie(pie.clone());
+
+        assert_eq!(any.kind(), DiagramKind::Pie);
+        assert_eq!(any.diagram_type(), "pie");
+        assert_eq!(any.title(), Some("Pets"));
+        assert_eq!(any.to_mermaid(), pie.to_mermaid());
+        assert_eq!(any.build_script(), pie.build_script());
+    }
+
+    #[test]
+    fn any_diagram_from_yaml() {
+        let yaml = "title: Pets\ndata:\n  - label: Dogs\n    value: 10\n";
+        let any = AnyDiagram::from_yaml(DiagramKind::Pie, yaml).unwrap();
+        assert!(matches!(any, AnyDiagram::Pie(_)));
+        assert!(any.to_mermaid().contains("\"Dogs\" : 10"));
+
+        let yaml = "nodes:\n  - id: A\n    label: Start\n";
+        let any = AnyDiagram::from_yaml(DiagramKind::Flowchart, yaml).unwrap();
+        assert_eq!(any.kind(), DiagramKind::Flowchart);
+        assert_eq!(any.diagram_type(), "flowchart");
+    }
+
+    #[test]
+    fn any_diagram_from_json_and_toml() {
+        let json = r#"{"messages": [{"from": "A", "to": "B", "text": "Hi"}]}"#;
+        let any = AnyDiagram::from_json(DiagramKind::Sequence, json).unwrap();
+        assert_eq!(any.kind(), DiagramKind::Sequence);
+        assert!(any.to_mermaid().contains("A->>B: Hi"));
+
+        let toml = "direction = \"LR\"\n";
+        let any = AnyDiagram::from_toml(DiagramKind::State, toml).unwrap();
+        assert_eq!(any.kind(), DiagramKind::State);
+    }
+}
@@ -0,0 +1,330 @@
+use crate::core::{embeds_config, Diagram, DiagramKind, FromConfig, MermaidError};
+
+use super::{
+    AnyDiagram, ERDiagram, FlowChart, Journey, Mindmap, PieChart, RequirementDiagram,
+    SequenceDiagram, StateDiagram,
+};
+
+/// Detect the diagram kind of a definition file or mermaid script.
+///
+/// Checks for a top-level `type` field (JSON/YAML/TOML) first, then falls back to
+/// the first mermaid keyword after any frontmatter, directives and comments.
+pub fn detect_diagram_type(content: &str) -> Option<DiagramKind> {
+    type_field(content)
+        .and_then(|t| DiagramKind::parse(&t))
+        .or_else(|| detect_from_keyword(content))
+}
+
+/// Build the concrete diagram described by `content`, detecting its kind.
+///
+/// `format` is a file extension: json, yaml/yml, toml, or mmd/mermaid for raw scripts.
+pub fn load_diagram(content: &str, format: &str) -> Result<Box<dyn Diagram>, MermaidError> {
+    let format = format.to_lowercase();
+    let Some(kind) = detect_diagram_type(content) else {
+        // A document that doesn't parse is reported as such, not as a missing type
+        match format.as_str() {
+            "json" => drop(serde_json::from_str::<serde_json::Value>(content)?),
+            "yaml" | "yml" => drop(serde_yaml::from_str::<serde_yaml::Value>(content)?),
+            "toml" => drop(toml::from_str::<toml::Table>(content)?),
+            _ => {}
+        }
+        return Err(MermaidError::InvalidInput(
+            "Could not detect diagram type. Add a top-level 'type' field (e.g. 'type: flowchart')"
+                .to_string(),
+        ));
+    };
+
+    if matches!(format.as_str(), "mmd" | "mermaid") {
+        return Ok(Box::new(AnyDiagram::from_raw_mermaid(
+            kind,
+            content.to_string(),
+        )));
+    }
+
+    match kind {
+        DiagramKind::Flowchart => parse_as::<FlowChart>(content, &format),
+        DiagramKind::Sequence => parse_as::<SequenceDiagram>(content, &format),
+        DiagramKind::State => parse_as::<StateDiagram>(content, &format),
+        DiagramKind::ER => parse_as::<ERDiagram>(content, &format),
+        DiagramKind::Pie => parse_as::<PieChart>(content, &format),
+        DiagramKind::Journey => parse_as::<Journey>(content, &format),
+        DiagramKind::Mindmap => parse_as::<Mindmap>(content, &format),
+        DiagramKind::Requirement => parse_as::<RequirementDiagram>(content, &format),
+    }
+}
+
+/// Parse a mermaid script, detecting its kind from the leading keyword.
+///
+/// Pie scripts are parsed into a structured [`PieChart`] when possible; every
+/// other kind, pie scripts the parser can't read, and pie scripts carrying
+/// their own frontmatter or init directive are kept as raw mermaid.
+pub fn parse(script: &str) -> Result<AnyDiagram, MermaidError> {
+    let kind = detect_from_keyword(script).ok_or_else(|| {
+        MermaidError::ParseError("No recognized mermaid diagram keyword found".to_string())
+    })?;
+    if kind == DiagramKind::Pie && !embeds_config(script) {
+        if let Ok(chart) = PieChart::parse_mermaid(script) {
+            return Ok(AnyDiagram::Pie(chart));
+        }
+    }
+    Ok(AnyDiagram::from_raw_mermaid(kind, script.to_string()))
+}
+
+/// Pull the body of every fenced ```` ```mermaid ```` (or `~~~mermaid`) block out of markdown.
+///
+/// Fence lines are dropped; a block closes at a fence of the same character that
+/// is at least as long as the opening one. An unclosed block runs to the end.
+pub fn extract_mermaid_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current: Option<(char, usize, Vec<&str>)> = None;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        let fence_char = trimmed.chars().next().filter(|c| *c == '`' || *c == '~');
+        let fence_len = fence_char.map_or(0, |c| trimmed.chars().take_while(|x| *x == c).count());
+
+        match &mut current {
+            Some((c, len, body)) => {
+                if fence_char == Some(*c)
+                    && fence_len >= *len
+                    && trimmed[fence_len..].trim().is_empty()
+                {
+                    blocks.push(body.join("\n"));
+                    current = None;
+                } else {
+                    body.push(line);
+                }
+            }
+            None => {
+                if let Some(c) = fence_char.filter(|_| fence_len >= 3) {
+                    let info = trimmed[fence_len..].trim();
+                    if info.split_whitespace().next() == Some("mermaid") {
+                        current = Some((c, fence_len, Vec::new()));
+                    }
+                }
+            }
+        }
+    }
+    if let Some((_, _, body)) = current {
+        blocks.push(body.join("\n"));
+    }
+    blocks
+}
+
+fn parse_as<D: FromConfig + 'static>(
+    content: &str,
+    format: &str,
+) -> Result<Box<dyn Diagram>, MermaidError> {
+    let diagram = match format {
+        "json" => D::from_json(content)?,
+        "yaml" | "yml" => D::from_yaml(content)?,
+        "toml" => D::from_toml(content)?,
+        _ => return Err(MermaidError::UnknownFormat(format.to_string())),
+    };
+    Ok(Box::new(diagram))
+}
+
+/// Read a top-level `type` string from a JSON/YAML or TOML document
+fn type_field(content: &str) -> Option<String> {
+    if let Ok(serde_yaml::Value::Mapping(map)) = serde_yaml::from_str(content) {
+        if let Some(t) = map.get("type").and_then(|v| v.as_str()) {
+            return Some(t.to_string());
+        }
+    }
+    if let Ok(table) = toml::from_str::<toml::Table>(content) {
+        if let Some(t) = table.get("type").and_then(|v| v.as_str()) {
+            return Some(t.to_string());
+        }
+    }
+    None
+}
+
+/// Find the diagram keyword on the first meaningful line of a mermaid script
+fn detect_from_keyword(content: &str) -> Option<DiagramKind> {
+    let mut lines = content.lines().map(str::trim).peekable();
+
+    // Skip a leading `---` frontmatter block
+    while lines.peek().is_some_and(|l| l.is_empty()) {
+        lines.next();
+    }
+    if lines.peek() == Some(&"---") {
+        lines.next();
+        for line in lines.by_ref() {
+            if line == "---" {
+                break;
+            }
+        }
+    }
+
+    lines
+        .find(|l| !l.is_empty() && !l.starts_with("%%"))
+        .and_then(|l| l.split_whitespace().next())
+        .and_then(DiagramKind::from_keyword)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_flowchart_yaml() {
+        let yaml = "type: flowchart\ndirection: LR\nnodes:\n  - id: A\n    label: Start\n";
+        assert_eq!(detect_diagram_type(yaml), Some(DiagramKind::Flowchart));
+    }
+
+    #[test]
+    fn detect_sequence_yaml() {
+        let yaml = "type: sequence\nparticipants:\n  - id: Alice\n";
+        assert_eq!(detect_diagram_type(yaml), Some(DiagramKind::Sequence));
+    }
+
+    #[test]
+    fn detect_pie_yaml() {
+        let yaml = "type: pie\ntitle: Pets\ndata:\n  - label: Dogs\n    value: 10\n";
+        assert_eq!(detect_diagram_type(yaml), Some(DiagramKind::Pie));
+    }
+
+    #[test]
+    fn detect_json_and_toml() {
+        let json = r#"{"type": "erDiagram", "entities": []}"#;
+        assert_eq!(detect_diagram_type(json), Some(DiagramKind::ER));
+
+        let toml = "type = \"state\"\ndirection = \"LR\"\n";
+        assert_eq!(detect_diagram_type(toml), Some(DiagramKind::State));
+    }
+
+    #[test]
+    fn detect_mermaid_keyword() {
+        assert_eq!(
+            detect_diagram_type("graph TD\n    A --> B"),
+            Some(DiagramKind::Flowchart)
+        );
+        assert_eq!(
+            detect_diagram_type(
+                "---\ntitle: Test\n---\n%% comment\nsequenceDiagram\n    A->>B: Hi"
+            ),
+            Some(DiagramKind::Sequence)
+        );
+        assert_eq!(
+            detect_diagram_type("%%{init: {'theme': 'dark'}}%%\npie title Pets"),
+            Some(DiagramKind::Pie)
+        );
+    }
+
+    #[test]
+    fn detect_unknown() {
+        assert_eq!(detect_diagram_type("nodes: []"), None);
+        assert_eq!(detect_diagram_type(""), None);
+    }
+
+    #[test]
+    fn load_dispatches_to_concrete_type() {
+        let yaml = "type: pie\ntitle: Pets\ndata:\n  - label: Dogs\n    value: 10\n";
+        let diagram = load_diagram(yaml, "yaml").unwrap();
+        assert_eq!(diagram.diagram_type(), "pie");
+        assert!(diagram.to_mermaid().contains("\"Dogs\" : 10"));
+
+        let yaml = "type: flowchart\nnodes:\n  - id: A\n    label: Start\n";
+        let diagram = load_diagram(yaml, "yml").unwrap();
+        assert_eq!(diagram.diagram_type(), "flowchart");
+    }
+
+    #[test]
+    fn load_raw_mermaid() {
+        let raw = "sequenceDiagram\n    Alice->>Bob: Hello";
+        let diagram = load_diagram(raw, "mmd").unwrap();
+        assert_eq!(diagram.diagram_type(), "sequenceDiagram");
+        assert_eq!(diagram.to_mermaid(), raw);
+    }
+
+    #[test]
+    fn parse_detects_each_keyword() {
+        let cases = [
+            ("flowchart LR\n    A --> B", DiagramKind::Flowchart),
+            ("graph TD\n    A --> B", DiagramKind::Flowchart),
+            ("sequenceDiagram\n    A->>B: Hi", DiagramKind::Sequence),
+            ("stateDiagram-v2\n    [*] --> A", DiagramKind::State),
+            ("erDiagram\n    A ||--o{ B : has", DiagramKind::ER),
+            ("pie title Pets\n    \"Dogs\" : 10", DiagramKind::Pie),
+            ("journey\n    title Day", DiagramKind::Journey),
+            ("mindmap\n    root", DiagramKind::Mindmap),
+            ("requirementDiagram\n", DiagramKind::Requirement),
+        ];
+        for (script, kind) in cases {
+            let diagram = parse(script).unwrap();
+            assert_eq!(diagram.kind(), kind, "{}", script);
+        }
+    }
+
+    #[test]
+    fn parse_keeps_raw_script() {
+        let raw = "---\ntitle: Flow\n---\ngraph TD\n    A --> B";
+        let diagram = parse(raw).unwrap();
+        assert!(matches!(diagram, AnyDiagram::Flowchart(_)));
+        assert_eq!(diagram.to_mermaid(), raw);
+    }
+
+    #[test]
+    fn parse_pie_is_structured() {
+        let diagram = parse("pie title Pets\n    \"Dogs\" : 10").unwrap();
+        match diagram {
+            AnyDiagram::Pie(chart) => {
+                assert_eq!(chart.title.as_deref(), Some("Pets"));
+                assert_eq!(chart.data.len(), 1);
+            }
+            other => panic!("expected pie, got {:?}", other.kind()),
+        }
+    }
+
+    #[test]
+    fn parse_pie_keeps_embedded_config() {
+        for script in [
+            "%%{init: {'theme': 'dark'}}%%\npie title Pets\n    \"Dogs\" : 10",
+            "---\nconfig:\n  theme: dark\n---\npie title Pets\n    \"Dogs\" : 10",
+        ] {
+            let diagram = parse(script).unwrap();
+            assert!(matches!(diagram, AnyDiagram::Pie(_)));
+            assert_eq!(diagram.build_script(), script);
+        }
+    }
+
+    #[test]
+    fn parse_unknown_keyword_fails() {
+        assert!(matches!(
+            parse("gantt\n    title Plan"),
+            Err(MermaidError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn load_undetectable_fails() {
+        assert!(load_diagram("nodes: []", "yaml").is_err());
+    }
+
+    #[test]
+    fn load_unsupported_format() {
+        let yaml = "type: pie\ndata: []\n";
+        assert!(matches!(
+            load_diagram(yaml, "xml"),
+            Err(MermaidError::UnknownFormat(f)) if f == "xml"
+        ));
+    }
+
+    #[test]
+    fn extract_mermaid_blocks_strips_fences() {
+        let markdown = "# Docs\n\n```mermaid\nflowchart LR\n    a --> b\n```\n\n```rust\nfn main() {}\n```\n\n~~~mermaid\npie\n    \"A\" : 1\n~~~\n";
+        let blocks = extract_mermaid_blocks(markdown);
+        assert_eq!(
+            blocks,
+            vec!["flowchart LR\n    a --> b", "pie\n    \"A\" : 1"]
+        );
+    }
+
+    #[test]
+    fn extract_mermaid_blocks_nested_fence() {
+        // A shorter fence inside a longer one doesn't close the block
+        let markdown = "````mermaid\nflowchart LR\n```\n````\n```mermaid\nunclosed";
+        let blocks = extract_mermaid_blocks(markdown);
+        assert_eq!(blocks, vec!["flowchart LR\n```", "unclosed"]);
+    }
+}
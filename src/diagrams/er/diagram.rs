@@ -1,10 +1,13 @@
 use serde::{Deserialize, Serialize};
 
-use crate::core::{Config, Diagram, MermaidError, Theme};
+use crate::core::{
+    statement_count, Config, Diagram, FormatOptions, FromConfig, MermaidError, Theme,
+};
 
 use super::{Attribute, AttributeKey, AttributeType, Entity, Relationship};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ERDiagram {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
@@ -12,12 +15,19 @@ pub struct ERDiagram {
     pub entities: Vec<Entity>,
     #[serde(default)]
     pub relationships: Vec<Relationship>,
+    /// Emit `{}` for entities without attributes (mermaid-py behavior)
+    #[serde(default = "default_always_braces")]
+    pub always_braces: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config: Option<Config>,
     #[serde(skip)]
     raw_mermaid: Option<String>,
 }
 
+fn default_always_braces() -> bool {
+    true
+}
+
 impl ERDiagram {
     pub fn builder() -> ERDiagramBuilder {
         ERDiagramBuilder::new()
@@ -28,11 +38,35 @@ impl ERDiagram {
             title: None,
             entities: Vec::new(),
             relationships: Vec::new(),
+            always_braces: true,
             config: None,
             raw_mermaid: Some(script),
         }
     }
 
+    /// Check that entity and attribute names are valid mermaid identifiers
+    pub fn validate(&self) -> Result<(), MermaidError> {
+        for entity in &self.entities {
+            if !is_entity_name(&entity.name) {
+                return Err(MermaidError::InvalidInput(format!(
+                    "Entity name '{}' is not a valid identifier; use a name like '{}' with alias \"{}\"",
+                    entity.name,
+                    entity.name.split_whitespace().collect::<String>(),
+                    entity.name
+                )));
+            }
+            for attr in &entity.attributes {
+                if !is_attribute_name(&attr.name) {
+                    return Err(MermaidError::InvalidInput(format!(
+                        "Attribute name '{}' on entity '{}' is not a valid identifier",
+                        attr.name, entity.name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn from_json(json: &str) -> Result<Self, MermaidError> {
         serde_json::from_str(json).map_err(|e| MermaidError::ParseError(e.to_string()))
     }
@@ -46,11 +80,37 @@ impl ERDiagram {
     }
 }
 
+/// Entity names: letters, digits, `_` and `-`, not starting with a digit or `-`
+fn is_entity_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Attribute names additionally allow `[]` and `()`, e.g. `tags[]`
+fn is_attribute_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || "_-[]()".contains(c))
+}
+
 impl Diagram for ERDiagram {
     fn diagram_type(&self) -> &'static str {
         "erDiagram"
     }
 
+    fn to_mermaid_with(&self, options: &FormatOptions) -> String {
+        if !options.sort_stable || self.raw_mermaid.is_some() {
+            return options.apply(&self.to_mermaid());
+        }
+        let mut sorted = self.clone();
+        sorted.entities.sort_by(|a, b| a.name.cmp(&b.name));
+        sorted
+            .relationships
+            .sort_by(|a, b| (&a.from, &a.to).cmp(&(&b.from, &b.to)));
+        options.apply(&sorted.to_mermaid())
+    }
+
     fn title(&self) -> Option<&str> {
         self.title.as_deref()
     }
@@ -59,6 +119,17 @@ impl Diagram for ERDiagram {
         self.config.as_ref()
     }
 
+    fn is_empty(&self) -> bool {
+        if let Some(raw) = &self.raw_mermaid {
+            return statement_count(raw) == 0;
+        }
+        self.entities.is_empty() && self.relationships.is_empty()
+    }
+
+    fn frontmatter_title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
     fn to_mermaid(&self) -> String {
         if let Some(raw) = &self.raw_mermaid {
             return raw.clone();
@@ -68,7 +139,10 @@ impl Diagram for ERDiagram {
 
         // Entities
         for entity in &self.entities {
-            output.push_str(&format!("\n\t{}", entity.to_mermaid()));
+            output.push_str(&format!(
+                "\n\t{}",
+                entity.to_mermaid_braced(self.always_braces)
+            ));
         }
 
         // Relationships
@@ -79,16 +153,51 @@ impl Diagram for ERDiagram {
         output.push('\n');
         output
     }
+
+    fn estimated_complexity(&self) -> usize {
+        if let Some(raw) = &self.raw_mermaid {
+            return statement_count(raw);
+        }
+        let attributes: usize = self.entities.iter().map(|e| e.attributes.len()).sum();
+        self.entities.len() + attributes + self.relationships.len()
+    }
+}
+
+impl FromConfig for ERDiagram {
+    fn from_json(json: &str) -> Result<Self, MermaidError> {
+        Self::from_json(json)
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Self, MermaidError> {
+        Self::from_yaml(yaml)
+    }
+
+    fn from_toml(toml: &str) -> Result<Self, MermaidError> {
+        Self::from_toml(toml)
+    }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct ERDiagramBuilder {
     title: Option<String>,
     entities: Vec<Entity>,
     relationships: Vec<Relationship>,
+    always_braces: bool,
     config: Option<Config>,
 }
 
+impl Default for ERDiagramBuilder {
+    fn default() -> Self {
+        Self {
+            title: None,
+            entities: Vec::new(),
+            relationships: Vec::new(),
+            always_braces: true,
+            config: None,
+        }
+    }
+}
+
 impl ERDiagramBuilder {
     pub fn new() -> Self {
         Self::default()
@@ -173,6 +282,12 @@ impl ERDiagramBuilder {
         self
     }
 
+    /// Render attribute-less entities as `User{\n}` (default) or as a bare `User`
+    pub fn always_braces(mut self, always_braces: bool) -> Self {
+        self.always_braces = always_braces;
+        self
+    }
+
     pub fn theme(mut self, theme: Theme) -> Self {
         let config = self.config.get_or_insert_with(Config::default);
         config.theme = theme;
@@ -184,6 +299,7 @@ impl ERDiagramBuilder {
             title: self.title,
             entities: self.entities,
             relationships: self.relationships,
+            always_braces: self.always_braces,
             config: self.config,
             raw_mermaid: None,
         }
@@ -193,6 +309,25 @@ impl ERDiagramBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::ScriptStyle;
+
+    #[test]
+    fn er_diagram_empty_entity_braces() {
+        let braced = ERDiagram::builder().entity_simple("User").build();
+        assert!(braced.to_mermaid().contains("\tUser{\n}"));
+
+        let bare = ERDiagram::builder()
+            .entity_simple("User")
+            .always_braces(false)
+            .build();
+        assert_eq!(bare.to_mermaid(), "erDiagram\n\tUser\n");
+    }
+
+    #[test]
+    fn er_diagram_always_braces_defaults_on_deserialize() {
+        let diagram = ERDiagram::from_yaml("entities:\n  - name: User\n").unwrap();
+        assert!(diagram.always_braces);
+    }
 
     #[test]
     fn er_diagram_basic() {
@@ -241,4 +376,45 @@ entities:
         let diagram = ERDiagram::from_raw_mermaid(raw.to_string());
         assert_eq!(diagram.to_mermaid(), raw);
     }
+
+    #[test]
+    fn er_validate_flags_spaced_entity_name() {
+        let diagram = ERDiagram::builder().entity_simple("Order Item").build();
+        match diagram.validate() {
+            Err(MermaidError::InvalidInput(msg)) => {
+                assert!(msg.contains("'Order Item'"));
+                assert!(msg.contains("'OrderItem' with alias"));
+            }
+            other => panic!("expected InvalidInput, got {:?}", other),
+        }
+
+        let diagram = ERDiagram::builder()
+            .entity(Entity::new("OrderItem").with_alias("Order Item"))
+            .build();
+        assert!(diagram.validate().is_ok());
+    }
+
+    #[test]
+    fn er_validate_flags_attribute_name() {
+        let diagram = ERDiagram::builder()
+            .entity(
+                Entity::new("Order")
+                    .with_attribute(Attribute::new(AttributeType::String, "ship to"))
+                    .with_attribute(Attribute::new(AttributeType::String, "tags[]")),
+            )
+            .build();
+        let err = diagram.validate().unwrap_err();
+        assert!(err.to_string().contains("Attribute name 'ship to'"));
+    }
+
+    #[test]
+    fn er_build_script_frontmatter() {
+        let diagram = ERDiagram::builder()
+            .title("Orders")
+            .theme(Theme::Dark)
+            .entity_simple("ORDER")
+            .build();
+        let script = diagram.build_script_with(ScriptStyle::Frontmatter);
+        assert!(script.starts_with("---\ntitle: Orders\nconfig:\n  theme: dark\n---\nerDiagram\n"));
+    }
 }
@@ -1,8 +1,12 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Entity {
     pub name: String,
+    /// Display name shown instead of `name`, e.g. `Order Item` for `ORDER_ITEM`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
     #[serde(default)]
     pub attributes: Vec<Attribute>,
 }
@@ -11,10 +15,17 @@ impl Entity {
     pub fn new(name: impl Into<String>) -> Self {
         Self {
             name: name.into(),
+            alias: None,
             attributes: Vec::new(),
         }
     }
 
+    /// Show `alias` in the diagram while relationships keep using `name`
+    pub fn with_alias(mut self, alias: impl Into<String>) -> Self {
+        self.alias = Some(alias.into());
+        self
+    }
+
     pub fn with_attribute(mut self, attr: Attribute) -> Self {
         self.attributes.push(attr);
         self
@@ -27,7 +38,20 @@ impl Entity {
 
     pub fn to_mermaid(&self) -> String {
         // mermaid-py always outputs braces, even for empty entities
-        let mut output = format!("{}{{\n", self.name);
+        self.to_mermaid_braced(true)
+    }
+
+    /// Render the entity; with `always_braces` off an entity without attributes is a bare name
+    pub fn to_mermaid_braced(&self, always_braces: bool) -> String {
+        let name = match &self.alias {
+            Some(alias) => format!("{}[\"{}\"]", self.name, alias.replace('"', "#quot;")),
+            None => self.name.clone(),
+        };
+        if !always_braces && self.attributes.is_empty() {
+            return name;
+        }
+
+        let mut output = format!("{}{{\n", name);
         for attr in &self.attributes {
             output.push_str(&format!("\t{}\n", attr.to_mermaid()));
         }
@@ -37,10 +61,14 @@ impl Entity {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Attribute {
     #[serde(rename = "type")]
     pub attr_type: AttributeType,
     pub name: String,
+    /// Type length, rendered as `string(255)`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub length: Option<u32>,
     #[serde(default)]
     pub key: AttributeKey,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -52,11 +80,17 @@ impl Attribute {
         Self {
             attr_type,
             name: name.into(),
+            length: None,
             key: AttributeKey::default(),
             comment: None,
         }
     }
 
+    pub fn with_length(mut self, length: u32) -> Self {
+        self.length = Some(length);
+        self
+    }
+
     pub fn with_key(mut self, key: AttributeKey) -> Self {
         self.key = key;
         self
@@ -68,21 +102,27 @@ impl Attribute {
     }
 
     pub fn to_mermaid(&self) -> String {
-        let mut parts = vec![self.attr_type.to_string(), self.name.clone()];
+        let attr_type = match self.length {
+            Some(length) => format!("{}({})", self.attr_type, length),
+            None => self.attr_type.to_string(),
+        };
+        let mut parts = vec![attr_type, self.name.clone()];
 
         if self.key != AttributeKey::None {
             parts.push(self.key.to_string());
         }
 
         if let Some(comment) = &self.comment {
-            parts.push(format!("\"{}\"", comment));
+            // Comments are double-quoted, so inner quotes become entity codes
+            parts.push(format!("\"{}\"", comment.replace('"', "#quot;")));
         }
 
         parts.join(" ")
     }
 }
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum AttributeType {
     #[default]
@@ -95,6 +135,9 @@ pub enum AttributeType {
     Text,
     Uuid,
     Enum,
+    /// Any other type, rendered verbatim (e.g. `jsonb`, `numeric(10,2)`)
+    #[serde(untagged)]
+    Custom(String),
 }
 
 impl std::fmt::Display for AttributeType {
@@ -109,6 +152,7 @@ impl std::fmt::Display for AttributeType {
             Self::Text => write!(f, "text"),
             Self::Uuid => write!(f, "uuid"),
             Self::Enum => write!(f, "enum"),
+            Self::Custom(name) => write!(f, "{}", name),
         }
     }
 }
@@ -128,9 +172,15 @@ impl AttributeType {
             _ => None,
         }
     }
+
+    /// Parse a known type, keeping anything else as `Custom`
+    pub fn parse_or_custom(s: &str) -> Self {
+        Self::parse(s).unwrap_or_else(|| Self::Custom(s.to_string()))
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum AttributeKey {
     #[default]
@@ -180,10 +230,66 @@ mod tests {
         assert!(mermaid.contains("string name"));
     }
 
+    #[test]
+    fn entity_with_alias() {
+        let entity = Entity::new("ORDER_ITEM")
+            .with_alias("Order Item")
+            .with_attribute(Attribute::new(AttributeType::Int, "qty"));
+        assert_eq!(
+            entity.to_mermaid(),
+            "ORDER_ITEM[\"Order Item\"]{\n\tint qty\n}"
+        );
+        assert_eq!(
+            Entity::new("ORDER_ITEM")
+                .with_alias("Order Item")
+                .to_mermaid_braced(false),
+            "ORDER_ITEM[\"Order Item\"]"
+        );
+    }
+
+    #[test]
+    fn attribute_comment_escapes_quotes() {
+        let attr = Attribute::new(AttributeType::String, "status")
+            .with_comment("one of \"new\", \"paid\"");
+        assert_eq!(
+            attr.to_mermaid(),
+            "string status \"one of #quot;new#quot;, #quot;paid#quot;\""
+        );
+    }
+
     #[test]
     fn attribute_type_parse() {
         assert_eq!(AttributeType::parse("string"), Some(AttributeType::String));
         assert_eq!(AttributeType::parse("int"), Some(AttributeType::Int));
         assert_eq!(AttributeType::parse("invalid"), None);
     }
+
+    #[test]
+    fn attribute_with_length() {
+        let attr = Attribute::new(AttributeType::String, "name").with_length(255);
+        assert_eq!(attr.to_mermaid(), "string(255) name");
+    }
+
+    #[test]
+    fn attribute_custom_type() {
+        assert_eq!(
+            AttributeType::parse_or_custom("jsonb"),
+            AttributeType::Custom("jsonb".to_string())
+        );
+        assert_eq!(AttributeType::parse_or_custom("INT"), AttributeType::Int);
+
+        let attr = Attribute::new(AttributeType::parse_or_custom("jsonb"), "payload");
+        assert_eq!(attr.to_mermaid(), "jsonb payload");
+    }
+
+    #[test]
+    fn attribute_custom_type_serde() {
+        let attr: Attribute = serde_yaml::from_str("type: jsonb\nname: payload\n").unwrap();
+        assert_eq!(attr.attr_type, AttributeType::Custom("jsonb".to_string()));
+
+        let attr: Attribute =
+            serde_yaml::from_str("type: string\nname: email\nlength: 255\n").unwrap();
+        assert_eq!(attr.attr_type, AttributeType::String);
+        assert_eq!(attr.to_mermaid(), "string(255) email");
+    }
 }
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Relationship {
     pub from: String,
     pub to: String,
@@ -8,6 +9,12 @@ pub struct Relationship {
     pub to_cardinality: Cardinality,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
+    /// Role of `from` in the relationship; composed into the single label
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_role: Option<String>,
+    /// Role of `to` in the relationship; composed into the single label
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_role: Option<String>,
     /// mermaid-py: dotted=False by default, meaning identifying (solid --) is the default
     #[serde(default = "default_identifying")]
     pub identifying: bool,
@@ -25,6 +32,8 @@ impl Relationship {
             from_cardinality: Cardinality::ExactlyOne,
             to_cardinality: Cardinality::ExactlyOne,
             label: None,
+            from_role: None,
+            to_role: None,
             identifying: true, // mermaid-py default
         }
     }
@@ -36,6 +45,8 @@ impl Relationship {
             from_cardinality: Cardinality::ExactlyOne,
             to_cardinality: Cardinality::ZeroOrMore,
             label: None,
+            from_role: None,
+            to_role: None,
             identifying: true, // mermaid-py default
         }
     }
@@ -47,6 +58,8 @@ impl Relationship {
             from_cardinality: Cardinality::ZeroOrMore,
             to_cardinality: Cardinality::ExactlyOne,
             label: None,
+            from_role: None,
+            to_role: None,
             identifying: true, // mermaid-py default
         }
     }
@@ -58,6 +71,8 @@ impl Relationship {
             from_cardinality: Cardinality::ZeroOrMore,
             to_cardinality: Cardinality::ZeroOrMore,
             label: None,
+            from_role: None,
+            to_role: None,
             identifying: true, // mermaid-py default
         }
     }
@@ -73,29 +88,53 @@ impl Relationship {
         self
     }
 
+    /// Annotate both ends; Mermaid allows one label, so roles render as `from / to`
+    /// and take precedence over an explicit label
+    pub fn with_roles(mut self, from_role: impl Into<String>, to_role: impl Into<String>) -> Self {
+        self.from_role = Some(from_role.into());
+        self.to_role = Some(to_role.into());
+        self
+    }
+
+    /// The label written to the diagram, composed from roles when any are set
+    pub fn display_label(&self) -> Option<String> {
+        match (&self.from_role, &self.to_role) {
+            (Some(from), Some(to)) => Some(format!("{} / {}", from, to)),
+            (Some(role), None) | (None, Some(role)) => Some(role.clone()),
+            (None, None) => self.label.clone(),
+        }
+    }
+
     pub fn identifying(mut self) -> Self {
         self.identifying = true;
         self
     }
 
-    pub fn to_mermaid(&self) -> String {
-        let from_sym = self.from_cardinality.symbol_left();
-        let to_sym = self.to_cardinality.symbol_right();
+    /// The relationship arrow, e.g. `||--o{` or `}o..o{`
+    pub fn arrow_syntax(&self) -> String {
         // mermaid-py: identifying = solid (--), non-identifying = dotted (..)
         let line = if self.identifying { "--" } else { ".." };
+        format!(
+            "{}{}{}",
+            self.from_cardinality.symbol_left(),
+            line,
+            self.to_cardinality.symbol_right()
+        )
+    }
 
-        // Format: {from}{left_sym}{line}{right_sym}{to} : "{label}"
-        match &self.label {
-            Some(label) => format!(
-                "{}{}{}{}{} : \"{}\"",
-                self.from, from_sym, line, to_sym, self.to, label
-            ),
-            None => format!("{}{}{}{}{}", self.from, from_sym, line, to_sym, self.to),
+    pub fn to_mermaid(&self) -> String {
+        let arrow = self.arrow_syntax();
+
+        // Format: {from}{arrow}{to} : "{label}"
+        match self.display_label() {
+            Some(label) => format!("{}{}{} : \"{}\"", self.from, arrow, self.to, label),
+            None => format!("{}{}{}", self.from, arrow, self.to),
         }
     }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub enum Cardinality {
     #[default]
@@ -124,14 +163,30 @@ impl Cardinality {
         }
     }
 
+    /// Accepted spec tokens, canonical name first for each cardinality
+    pub const ALIASES: &'static [(&'static str, Self)] = &[
+        ("exactly-one", Self::ExactlyOne),
+        ("one", Self::ExactlyOne),
+        ("||", Self::ExactlyOne),
+        ("zero-or-one", Self::ZeroOrOne),
+        ("optional", Self::ZeroOrOne),
+        ("|o", Self::ZeroOrOne),
+        ("o|", Self::ZeroOrOne),
+        ("zero-or-more", Self::ZeroOrMore),
+        ("many", Self::ZeroOrMore),
+        ("}o", Self::ZeroOrMore),
+        ("o{", Self::ZeroOrMore),
+        ("one-or-more", Self::OneOrMore),
+        ("}|", Self::OneOrMore),
+        ("|{", Self::OneOrMore),
+    ];
+
     pub fn parse(s: &str) -> Option<Self> {
-        match s.to_lowercase().as_str() {
-            "exactly-one" | "one" | "||" => Some(Self::ExactlyOne),
-            "zero-or-one" | "optional" | "|o" | "o|" => Some(Self::ZeroOrOne),
-            "zero-or-more" | "many" | "}o" | "o{" => Some(Self::ZeroOrMore),
-            "one-or-more" | "}|" | "|{" => Some(Self::OneOrMore),
-            _ => None,
-        }
+        let s = s.to_lowercase();
+        Self::ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == s)
+            .map(|(_, value)| *value)
     }
 }
 
@@ -155,6 +210,63 @@ mod tests {
         assert!(mermaid.contains("o{"));
     }
 
+    #[test]
+    fn relationship_arrow_syntax() {
+        let rel = Relationship::one_to_many("User", "Order").with_label("places");
+        assert_eq!(rel.arrow_syntax(), "||--o{");
+        assert_eq!(rel.to_mermaid(), "User||--o{Order : \"places\"");
+
+        let rel = Relationship::many_to_many("Student", "Course").with_label("enrolls");
+        assert_eq!(rel.arrow_syntax(), "}o--o{");
+        assert_eq!(rel.to_mermaid(), "Student}o--o{Course : \"enrolls\"");
+    }
+
+    #[test]
+    fn relationship_with_roles() {
+        let rel = Relationship::one_to_many("Customer", "Order").with_roles("places", "placed by");
+        assert_eq!(rel.display_label().as_deref(), Some("places / placed by"));
+        assert_eq!(
+            rel.to_mermaid(),
+            "Customer||--o{Order : \"places / placed by\""
+        );
+    }
+
+    #[test]
+    fn relationship_roles_take_precedence_over_label() {
+        let rel = Relationship::one_to_many("Customer", "Order")
+            .with_label("has")
+            .with_roles("places", "placed by");
+        assert_eq!(
+            rel.to_mermaid(),
+            "Customer||--o{Order : \"places / placed by\""
+        );
+
+        let mut rel = Relationship::new("A", "B").with_label("has");
+        rel.to_role = Some("owned by".to_string());
+        assert_eq!(rel.display_label().as_deref(), Some("owned by"));
+    }
+
+    #[test]
+    fn relationship_arrow_syntax_non_identifying() {
+        let mut rel = Relationship::new("Person", "Passport")
+            .with_cardinality(Cardinality::ExactlyOne, Cardinality::ZeroOrOne);
+        rel.identifying = false;
+        assert_eq!(rel.arrow_syntax(), "||..o|");
+    }
+
+    #[test]
+    fn cardinality_symbols_mirror() {
+        for (card, left, right) in [
+            (Cardinality::ExactlyOne, "||", "||"),
+            (Cardinality::ZeroOrOne, "|o", "o|"),
+            (Cardinality::ZeroOrMore, "}o", "o{"),
+            (Cardinality::OneOrMore, "}|", "|{"),
+        ] {
+            assert_eq!(card.symbol_left(), left);
+            assert_eq!(card.symbol_right(), right);
+        }
+    }
+
     #[test]
     fn cardinality_parse() {
         assert_eq!(Cardinality::parse("one"), Some(Cardinality::ExactlyOne));
@@ -4,6 +4,7 @@ use crate::core::Style;
 
 /// Defines a CSS class that can be applied to nodes
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ClassDef {
     pub name: String,
     #[serde(flatten)]
@@ -27,6 +28,7 @@ impl ClassDef {
 
 /// Assigns a class to one or more nodes
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ClassAssignment {
     pub class_name: String,
     pub nodes: Vec<String>,
@@ -46,24 +48,104 @@ impl ClassAssignment {
     }
 }
 
-/// Link styling for specific links by index
+/// Which links a `linkStyle` directive applies to
+///
+/// Serialized as `"default"`, a single index, or a list of indices.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(from = "LinkStyleTargetRepr", into = "LinkStyleTargetRepr")]
+pub enum LinkStyleTarget {
+    /// Every link without its own style (`linkStyle default`)
+    Default,
+    /// Zero-based link indices (`linkStyle 0,1`)
+    Indices(Vec<usize>),
+}
+
+impl LinkStyleTarget {
+    pub fn to_mermaid(&self) -> String {
+        match self {
+            Self::Default => "default".to_string(),
+            Self::Indices(indices) => indices
+                .iter()
+                .map(usize::to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+enum LinkStyleTargetRepr {
+    Index(usize),
+    Indices(Vec<usize>),
+    Keyword(DefaultKeyword),
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+enum DefaultKeyword {
+    Default,
+}
+
+impl From<LinkStyleTargetRepr> for LinkStyleTarget {
+    fn from(repr: LinkStyleTargetRepr) -> Self {
+        match repr {
+            LinkStyleTargetRepr::Index(index) => Self::Indices(vec![index]),
+            LinkStyleTargetRepr::Indices(indices) => Self::Indices(indices),
+            LinkStyleTargetRepr::Keyword(DefaultKeyword::Default) => Self::Default,
+        }
+    }
+}
+
+impl From<LinkStyleTarget> for LinkStyleTargetRepr {
+    fn from(target: LinkStyleTarget) -> Self {
+        match target {
+            LinkStyleTarget::Default => Self::Keyword(DefaultKeyword::Default),
+            LinkStyleTarget::Indices(indices) if indices.len() == 1 => Self::Index(indices[0]),
+            LinkStyleTarget::Indices(indices) => Self::Indices(indices),
+        }
+    }
+}
+
+/// Link styling for specific links by index, or for all links by default
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct LinkStyleDef {
-    /// Zero-based index of the link to style
-    pub index: usize,
+    /// Links to style: `"default"`, a zero-based index, or a list of indices
+    #[serde(rename = "index")]
+    pub target: LinkStyleTarget,
     #[serde(flatten)]
     pub style: Style,
 }
 
 impl LinkStyleDef {
     pub fn new(index: usize, style: Style) -> Self {
-        Self { index, style }
+        Self::many(vec![index], style)
+    }
+
+    /// Style several links with one directive
+    pub fn many(indices: Vec<usize>, style: Style) -> Self {
+        Self {
+            target: LinkStyleTarget::Indices(indices),
+            style,
+        }
+    }
+
+    /// Style every link that has no style of its own
+    pub fn default_style(style: Style) -> Self {
+        Self {
+            target: LinkStyleTarget::Default,
+            style,
+        }
     }
 
     /// Renders the linkStyle directive in mermaid syntax
     pub fn to_mermaid(&self) -> String {
         let css = self.style.to_css();
-        format!("linkStyle {} {}", self.index, css)
+        format!("linkStyle {} {}", self.target.to_mermaid(), css)
     }
 }
 
@@ -71,6 +153,41 @@ impl LinkStyleDef {
 mod tests {
     use super::*;
 
+    #[test]
+    fn link_style_targets() {
+        let style = Style::new().stroke("#f00");
+        assert_eq!(
+            LinkStyleDef::default_style(style.clone()).to_mermaid(),
+            "linkStyle default stroke:#f00"
+        );
+        assert_eq!(
+            LinkStyleDef::many(vec![0, 1], style.clone()).to_mermaid(),
+            "linkStyle 0,1 stroke:#f00"
+        );
+        assert_eq!(
+            LinkStyleDef::new(2, style).to_mermaid(),
+            "linkStyle 2 stroke:#f00"
+        );
+    }
+
+    #[test]
+    fn link_style_target_serde() {
+        let def: LinkStyleDef =
+            serde_json::from_str(r##"{"index": 3, "stroke": "#f00"}"##).unwrap();
+        assert_eq!(def.target, LinkStyleTarget::Indices(vec![3]));
+
+        let def: LinkStyleDef =
+            serde_json::from_str(r##"{"index": "default", "stroke": "#f00"}"##).unwrap();
+        assert_eq!(def.target, LinkStyleTarget::Default);
+
+        let def: LinkStyleDef =
+            serde_json::from_str(r##"{"index": [0, 2], "stroke": "#f00"}"##).unwrap();
+        assert_eq!(def.target, LinkStyleTarget::Indices(vec![0, 2]));
+        assert!(serde_json::to_string(&def)
+            .unwrap()
+            .contains(r##""index":[0,2]"##));
+    }
+
     #[test]
     fn class_def_basic() {
         let class_def = ClassDef::new(
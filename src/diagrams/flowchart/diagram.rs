@@ -1,13 +1,23 @@
 use serde::{Deserialize, Serialize};
 
-use crate::core::{Config, Diagram, Direction, FromConfig, MermaidError, Style, Theme};
+use crate::core::{
+    render_id, statement_count, Config, Curve, Diagram, Direction, FormatOptions, FromConfig,
+    MermaidError, Style, Theme,
+};
 
-use super::{ClassAssignment, ClassDef, Link, LinkStyle, LinkStyleDef, Node, NodeShape, Subgraph};
+use super::{
+    ClassAssignment, ClassDef, Link, LinkStyle, LinkStyleDef, LinkStyleTarget, Node, NodeShape,
+    Subgraph,
+};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FlowChart {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
+    /// Leading keyword: `flowchart` (default) or the legacy `graph`
+    #[serde(default)]
+    pub keyword: FlowchartKeyword,
     #[serde(default)]
     pub direction: Direction,
     #[serde(default)]
@@ -27,6 +37,15 @@ pub struct FlowChart {
     /// Link styling (linkStyle 0 stroke:#f00)
     #[serde(default)]
     pub link_styles: Vec<LinkStyleDef>,
+    /// Comments emitted as `%%` lines after the diagram-type line
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<String>,
+    /// Keep node/link/subgraph ids as written instead of lowercasing them
+    #[serde(default)]
+    pub preserve_ids: bool,
+    /// Shape for nodes that don't set one
+    #[serde(default)]
+    pub default_shape: NodeShape,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config: Option<Config>,
     /// Raw mermaid passthrough (if set, ignores other fields)
@@ -34,7 +53,35 @@ pub struct FlowChart {
     raw_mermaid: Option<String>,
 }
 
+/// The keyword a flowchart script starts with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum FlowchartKeyword {
+    #[default]
+    Flowchart,
+    Graph,
+}
+
+impl FlowchartKeyword {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Flowchart => "flowchart",
+            Self::Graph => "graph",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "flowchart" => Some(Self::Flowchart),
+            "graph" => Some(Self::Graph),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct NodeStyle {
     pub target: String,
     #[serde(flatten)]
@@ -72,10 +119,158 @@ impl FlowChart {
         Ok(chart)
     }
 
+    /// Append another chart's nodes, links, subgraphs, styles, classes and comments.
+    ///
+    /// `linkStyle` indices from `other` are offset by this chart's link count so
+    /// they keep pointing at the same links. Title, direction and config are kept
+    /// from `self`. Duplicate node ids are not rejected here; see [`Self::validate`].
+    pub fn merge(&mut self, other: FlowChart) {
+        let offset = self.links.len();
+
+        self.nodes.extend(other.nodes);
+        self.links.extend(other.links);
+        self.subgraphs.extend(other.subgraphs);
+        self.styles.extend(other.styles);
+        self.class_defs.extend(other.class_defs);
+        self.class_assignments.extend(other.class_assignments);
+        self.comments.extend(other.comments);
+        self.link_styles
+            .extend(other.link_styles.into_iter().map(|mut link_style| {
+                if let LinkStyleTarget::Indices(indices) = &mut link_style.target {
+                    for index in indices {
+                        *index += offset;
+                    }
+                }
+                link_style
+            }));
+    }
+
+    /// Check the chart for problems that would render incorrectly.
+    ///
+    /// Rejects node ids that collide after normalization and links whose
+    /// endpoints are neither declared nodes nor subgraph ids.
+    pub fn validate(&self) -> Result<(), MermaidError> {
+        let mut seen: Vec<(String, &str)> = Vec::new();
+        for node in &self.nodes {
+            let id = self.id_key(&node.id);
+            if let Some((_, first)) = seen.iter().find(|(key, _)| *key == id) {
+                if *first == node.id {
+                    return Err(MermaidError::InvalidInput(format!(
+                        "Duplicate node id '{}'",
+                        node.id
+                    )));
+                }
+                return Err(MermaidError::InvalidInput(format!(
+                    "Node ids '{}' and '{}' both render as '{}'; set preserve_ids to keep their case",
+                    first, node.id, id
+                )));
+            }
+            seen.push((id, &node.id));
+        }
+
+        let mut subgraphs = Vec::new();
+        self.collect_subgraph_keys(&self.subgraphs, &mut subgraphs);
+
+        for link in &self.links {
+            for endpoint in [&link.from, &link.to] {
+                let key = self.id_key(endpoint);
+                if !seen.iter().any(|(id, _)| *id == key) && !subgraphs.contains(&key) {
+                    return Err(MermaidError::DanglingReference {
+                        kind: "node".to_string(),
+                        id: endpoint.clone(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Move every node whose id starts with `prefix` into a new subgraph.
+    ///
+    /// See [`Self::group_where`].
+    pub fn group_by_prefix(
+        &mut self,
+        prefix: &str,
+        subgraph_id: impl Into<String>,
+        title: impl Into<String>,
+    ) {
+        self.group_where(subgraph_id, title, |node| node.id.starts_with(prefix));
+    }
+
+    /// Move every node matching `predicate` into a new top-level subgraph.
+    ///
+    /// Matching nodes are removed from any subgraph they already belonged to.
+    pub fn group_where(
+        &mut self,
+        subgraph_id: impl Into<String>,
+        title: impl Into<String>,
+        predicate: impl Fn(&Node) -> bool,
+    ) {
+        let members: Vec<String> = self
+            .nodes
+            .iter()
+            .filter(|node| predicate(node))
+            .map(|node| node.id.clone())
+            .collect();
+
+        let keys: Vec<String> = members.iter().map(|id| self.id_key(id)).collect();
+        remove_members(&mut self.subgraphs, &keys, self.preserve_ids);
+
+        self.subgraphs.push(
+            Subgraph::new(subgraph_id)
+                .with_title(title)
+                .with_nodes(members),
+        );
+    }
+
+    /// A copy with nodes, links and subgraphs ordered by id.
+    ///
+    /// `linkStyle` indices are remapped to follow their links.
+    fn sorted(&self) -> Self {
+        let mut chart = self.clone();
+        chart.nodes.sort_by(|a, b| a.id.cmp(&b.id));
+        chart.subgraphs.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut order: Vec<usize> = (0..self.links.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (&self.links[a], &self.links[b]);
+            (&a.from, &a.to).cmp(&(&b.from, &b.to))
+        });
+        chart.links = order.iter().map(|&i| self.links[i].clone()).collect();
+        for link_style in &mut chart.link_styles {
+            if let LinkStyleTarget::Indices(indices) = &mut link_style.target {
+                for index in indices {
+                    if let Some(pos) = order.iter().position(|&i| i == *index) {
+                        *index = pos;
+                    }
+                }
+            }
+        }
+        chart
+    }
+
+    /// The id as rendered, honoring `preserve_ids`
+    fn id_key(&self, id: &str) -> String {
+        render_id(id, self.preserve_ids)
+    }
+
+    /// Rendered ids of `subgraphs` and everything nested inside them
+    fn collect_subgraph_keys(&self, subgraphs: &[Subgraph], keys: &mut Vec<String>) {
+        for subgraph in subgraphs {
+            keys.push(self.id_key(&subgraph.id));
+            self.collect_subgraph_keys(&subgraph.subgraphs, keys);
+        }
+    }
+
     /// Render a subgraph with its nodes and nested subgraphs
     fn render_subgraph_with_nodes(&self, subgraph: &Subgraph, base_indent: &str) -> String {
         let title = subgraph.title.as_deref().unwrap_or(&subgraph.id);
-        let mut output = format!("{}subgraph {} [\"{}\"]\n", base_indent, subgraph.id, title);
+        let mut output = format!(
+            "{}subgraph {} [\"{}\"]\n",
+            base_indent,
+            self.id_key(&subgraph.id),
+            title
+        );
 
         let inner_indent = format!("{}    ", base_indent);
 
@@ -83,10 +278,15 @@ impl FlowChart {
             output.push_str(&format!("{}direction {}\n", inner_indent, dir));
         }
 
-        // Render nodes belonging to this subgraph
+        // Render nodes belonging to this subgraph (matched on normalized ids)
+        let members: Vec<String> = subgraph.nodes.iter().map(|id| self.id_key(id)).collect();
         for node in &self.nodes {
-            if subgraph.nodes.contains(&node.id) {
-                output.push_str(&format!("{}{}\n", inner_indent, node.to_mermaid()));
+            if members.contains(&self.id_key(&node.id)) {
+                output.push_str(&format!(
+                    "{}{}\n",
+                    inner_indent,
+                    node.to_mermaid_with_defaults(self.preserve_ids, self.default_shape)
+                ));
             }
         }
 
@@ -100,6 +300,16 @@ impl FlowChart {
     }
 }
 
+/// Drop the given (rendered) node ids from subgraph membership, recursively
+fn remove_members(subgraphs: &mut [Subgraph], ids: &[String], preserve_ids: bool) {
+    for subgraph in subgraphs {
+        subgraph
+            .nodes
+            .retain(|id| !ids.contains(&render_id(id, preserve_ids)));
+        remove_members(&mut subgraph.subgraphs, ids, preserve_ids);
+    }
+}
+
 impl Diagram for FlowChart {
     fn to_mermaid(&self) -> String {
         // If raw mermaid was provided, return it directly
@@ -109,19 +319,22 @@ impl Diagram for FlowChart {
 
         let mut output = String::new();
 
-        // Start with flowchart and direction
-        output.push_str(&format!("flowchart {}\n", self.direction));
+        // Start with the keyword and direction
+        output.push_str(&format!("{} {}\n", self.keyword.as_str(), self.direction));
 
         // Collect nodes that are in subgraphs
         let mut nodes_in_subgraphs: Vec<String> = Vec::new();
         for sg in &self.subgraphs {
-            nodes_in_subgraphs.extend(sg.nodes.clone());
+            nodes_in_subgraphs.extend(sg.nodes.iter().map(|id| self.id_key(id)));
         }
 
         // Render nodes not in any subgraph
         for node in &self.nodes {
-            if !nodes_in_subgraphs.contains(&node.id) {
-                output.push_str(&format!("    {}\n", node.to_mermaid()));
+            if !nodes_in_subgraphs.contains(&self.id_key(&node.id)) {
+                output.push_str(&format!(
+                    "    {}\n",
+                    node.to_mermaid_with_defaults(self.preserve_ids, self.default_shape)
+                ));
             }
         }
 
@@ -132,7 +345,10 @@ impl Diagram for FlowChart {
 
         // Render links
         for link in &self.links {
-            output.push_str(&format!("    {}\n", link.to_mermaid()));
+            output.push_str(&format!(
+                "    {}\n",
+                link.to_mermaid_with_ids(self.preserve_ids)
+            ));
         }
 
         // Render styles
@@ -153,14 +369,38 @@ impl Diagram for FlowChart {
             output.push_str(&format!("    {}\n", class_assignment.to_mermaid()));
         }
 
+        // Classes set on links themselves, targeted by edge id
+        for statement in self
+            .links
+            .iter()
+            .filter_map(|link| link.class_statement(self.preserve_ids))
+        {
+            output.push_str(&format!("    {}\n", statement));
+        }
+
         // Render link styles
         for link_style in &self.link_styles {
             output.push_str(&format!("    {}\n", link_style.to_mermaid()));
         }
 
+        // Styles set on links themselves, indexed by position
+        for (index, link) in self.links.iter().enumerate() {
+            let css = link.style_props.as_ref().map(Style::to_css);
+            if let Some(css) = css.filter(|css| !css.is_empty()) {
+                output.push_str(&format!("    linkStyle {} {}\n", index, css));
+            }
+        }
+
         output
     }
 
+    fn to_mermaid_with(&self, options: &FormatOptions) -> String {
+        if options.sort_stable && self.raw_mermaid.is_none() {
+            return options.apply(&self.sorted().to_mermaid());
+        }
+        options.apply(&self.to_mermaid())
+    }
+
     fn diagram_type(&self) -> &'static str {
         "flowchart"
     }
@@ -172,6 +412,28 @@ impl Diagram for FlowChart {
     fn config(&self) -> Option<&Config> {
         self.config.as_ref()
     }
+
+    fn is_empty(&self) -> bool {
+        if let Some(raw) = &self.raw_mermaid {
+            return statement_count(raw) == 0;
+        }
+        self.nodes.is_empty() && self.links.is_empty() && self.subgraphs.is_empty()
+    }
+
+    fn frontmatter_title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    fn comments(&self) -> &[String] {
+        &self.comments
+    }
+
+    fn estimated_complexity(&self) -> usize {
+        if let Some(raw) = &self.raw_mermaid {
+            return statement_count(raw);
+        }
+        self.nodes.len() + self.links.len() + self.subgraphs.len()
+    }
 }
 
 impl FromConfig for FlowChart {
@@ -191,6 +453,7 @@ impl FromConfig for FlowChart {
 #[derive(Debug, Default)]
 pub struct FlowChartBuilder {
     title: Option<String>,
+    keyword: FlowchartKeyword,
     direction: Direction,
     nodes: Vec<Node>,
     links: Vec<Link>,
@@ -199,6 +462,9 @@ pub struct FlowChartBuilder {
     class_defs: Vec<ClassDef>,
     class_assignments: Vec<ClassAssignment>,
     link_styles: Vec<LinkStyleDef>,
+    comments: Vec<String>,
+    preserve_ids: bool,
+    default_shape: NodeShape,
     config: Option<Config>,
 }
 
@@ -212,6 +478,11 @@ impl FlowChartBuilder {
         self
     }
 
+    pub fn keyword(mut self, keyword: FlowchartKeyword) -> Self {
+        self.keyword = keyword;
+        self
+    }
+
     pub fn direction(mut self, direction: Direction) -> Self {
         self.direction = direction;
         self
@@ -223,7 +494,7 @@ impl FlowChartBuilder {
     }
 
     pub fn node_simple(mut self, id: impl Into<String>, label: impl Into<String>) -> Self {
-        self.nodes.push(Node::new(id, label, NodeShape::default()));
+        self.nodes.push(Node::simple(id, label));
         self
     }
 
@@ -237,6 +508,16 @@ impl FlowChartBuilder {
         self
     }
 
+    pub fn node_with_icon(
+        mut self,
+        id: impl Into<String>,
+        label: impl Into<String>,
+        icon: impl Into<String>,
+    ) -> Self {
+        self.nodes.push(Node::simple(id, label).with_icon(icon));
+        self
+    }
+
     pub fn link(mut self, link: Link) -> Self {
         self.links.push(link);
         self
@@ -286,6 +567,13 @@ impl FlowChartBuilder {
         self
     }
 
+    /// Style every node without a class of its own (`classDef default ...`)
+    pub fn default_class(mut self, style: Style) -> Self {
+        self.class_defs.retain(|def| def.name != "default");
+        self.class_defs.push(ClassDef::new("default", style));
+        self
+    }
+
     /// Assign a class to one or more nodes (class A,B className)
     pub fn class_assignment(mut self, class_name: impl Into<String>, nodes: Vec<String>) -> Self {
         self.class_assignments
@@ -299,20 +587,58 @@ impl FlowChartBuilder {
         self
     }
 
+    /// `linkStyle default ...`: style every link without its own linkStyle
+    pub fn link_style_default(mut self, style: Style) -> Self {
+        self.link_styles.push(LinkStyleDef::default_style(style));
+        self
+    }
+
+    /// `linkStyle 0,1,2 ...`: style several links with one directive
+    pub fn link_style_many(mut self, indices: Vec<usize>, style: Style) -> Self {
+        self.link_styles.push(LinkStyleDef::many(indices, style));
+        self
+    }
+
+    /// Keep ids as written (e.g. `UserService`) instead of lowercasing them
+    pub fn preserve_ids(mut self, preserve: bool) -> Self {
+        self.preserve_ids = preserve;
+        self
+    }
+
+    /// Shape for nodes added without one (e.g. via `node_simple`)
+    pub fn default_shape(mut self, shape: NodeShape) -> Self {
+        self.default_shape = shape;
+        self
+    }
+
     pub fn theme(mut self, theme: Theme) -> Self {
         let config = self.config.get_or_insert_with(Config::default);
         config.theme = theme;
         self
     }
 
+    /// Add a `%%` comment line (e.g. generator or source provenance)
+    pub fn comment(mut self, comment: impl Into<String>) -> Self {
+        self.comments.push(comment.into());
+        self
+    }
+
     pub fn config(mut self, config: Config) -> Self {
         self.config = Some(config);
         self
     }
 
+    /// Set the edge curve style (`%%{init: {'flowchart': {'curve': ...}}}%%`)
+    pub fn curve(mut self, curve: Curve) -> Self {
+        let config = self.config.get_or_insert_with(Config::default);
+        config.flowchart.get_or_insert_with(Default::default).curve = Some(curve);
+        self
+    }
+
     pub fn build(self) -> FlowChart {
         FlowChart {
             title: self.title,
+            keyword: self.keyword,
             direction: self.direction,
             nodes: self.nodes,
             links: self.links,
@@ -321,6 +647,9 @@ impl FlowChartBuilder {
             class_defs: self.class_defs,
             class_assignments: self.class_assignments,
             link_styles: self.link_styles,
+            comments: self.comments,
+            preserve_ids: self.preserve_ids,
+            default_shape: self.default_shape,
             config: self.config,
             raw_mermaid: None,
         }
@@ -330,6 +659,7 @@ impl FlowChartBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::ScriptStyle;
 
     #[test]
     fn flowchart_basic() {
@@ -540,4 +870,418 @@ links:
         // Should have two end statements
         assert_eq!(mermaid.matches("end").count(), 2);
     }
+
+    #[test]
+    fn flowchart_with_node_icon() {
+        let chart = FlowChart::builder()
+            .node_with_icon("A", "Start", "fa:fa-book")
+            .build();
+        assert!(chart.to_mermaid().contains("a[\"fa:fa-book Start\"]"));
+    }
+
+    #[test]
+    fn flowchart_with_comments() {
+        let chart = FlowChart::builder()
+            .comment("generated by mermaid-rs")
+            .comment("source: flow.yaml\nrev: 3")
+            .node_simple("A", "Start")
+            .build();
+        let script = chart.build_script();
+        assert!(script.starts_with(
+            "flowchart TB\n%% generated by mermaid-rs\n%% source: flow.yaml\n%% rev: 3\n    a[\"Start\"]"
+        ));
+        assert!(!chart.to_mermaid().contains("%%"));
+    }
+
+    #[test]
+    fn flowchart_subgraph_id_normalized() {
+        let sg = Subgraph::new("Backend Services")
+            .with_title("Backend")
+            .with_nodes(vec!["API Server".to_string()]);
+        let chart = FlowChart::builder()
+            .node_simple("api server", "API")
+            .node_simple("Client", "Client")
+            .subgraph(sg)
+            .link_simple("Client", "Backend Services")
+            .build();
+
+        let mermaid = chart.to_mermaid();
+        assert!(mermaid.contains(
+            "    subgraph backend_services [\"Backend\"]\n        api_server[\"API\"]\n    end"
+        ));
+        // Member node is rendered only inside the subgraph
+        assert_eq!(mermaid.matches("api_server[").count(), 1);
+        assert!(mermaid.contains("client --> backend_services"));
+    }
+
+    #[test]
+    fn flowchart_keyword() {
+        let chart = FlowChart::builder()
+            .direction(Direction::LeftRight)
+            .node_simple("A", "Start")
+            .build();
+        assert!(chart.to_mermaid().starts_with("flowchart LR\n"));
+
+        let chart = FlowChart::builder()
+            .keyword(FlowchartKeyword::Graph)
+            .direction(Direction::TopBottom)
+            .node_simple("A", "Start")
+            .build();
+        assert!(chart.to_mermaid().starts_with("graph TB\n"));
+    }
+
+    #[test]
+    fn flowchart_keyword_from_yaml() {
+        let chart = FlowChart::from_yaml("keyword: graph\ndirection: LR\n").unwrap();
+        assert_eq!(chart.keyword, FlowchartKeyword::Graph);
+        assert!(chart.to_mermaid().starts_with("graph LR"));
+    }
+
+    #[test]
+    fn flowchart_with_curve() {
+        let chart = FlowChart::builder()
+            .curve(Curve::Basis)
+            .node_simple("A", "Start")
+            .build();
+        let script = chart.build_script();
+        assert!(
+            script.starts_with("%%{init: {'theme': 'default', 'flowchart': {'curve': 'basis'}}}%%")
+        );
+    }
+
+    #[test]
+    fn flowchart_link_class_statements() {
+        let chart = FlowChart::builder()
+            .node_simple("A", "A")
+            .node_simple("B", "B")
+            .link(Link::new("A", "B").with_id("e1").with_class("animate"))
+            .link_simple("B", "A")
+            .build();
+
+        assert_eq!(
+            chart.to_mermaid(),
+            "flowchart TB\n    a[\"A\"]\n    b[\"B\"]\n    a e1@--> b\n    b --> a\n    class e1 animate\n"
+        );
+    }
+
+    #[test]
+    fn flowchart_merge() {
+        let mut base = FlowChart::builder()
+            .node_simple("A", "A")
+            .node_simple("B", "B")
+            .link_simple("A", "B")
+            .comment("base")
+            .build();
+        let other = FlowChart::builder()
+            .comment("other")
+            .node_simple("C", "C")
+            .node_simple("D", "D")
+            .link_simple("B", "C")
+            .link_simple("C", "D")
+            .link_style(1, Style::new().stroke("#f00"))
+            .build();
+
+        base.merge(other);
+
+        assert_eq!(base.nodes.len(), 4);
+        assert_eq!(base.links.len(), 3);
+        assert_eq!(
+            base.link_styles[0].target,
+            LinkStyleTarget::Indices(vec![2])
+        );
+        assert!(base.to_mermaid().contains("linkStyle 2 stroke:#f00"));
+        assert_eq!(base.comments, vec!["base", "other"]);
+        assert!(base.validate().is_ok());
+    }
+
+    #[test]
+    fn flowchart_merge_duplicate_ids_fail_validation() {
+        let mut base = FlowChart::builder().node_simple("A", "First").build();
+        let other = FlowChart::builder().node_simple("a", "Second").build();
+
+        base.merge(other);
+
+        assert_eq!(base.nodes.len(), 2);
+        assert!(base.validate().is_err());
+    }
+
+    #[test]
+    fn flowchart_subgraph_direction_line() {
+        let sg = Subgraph::new("sg1")
+            .with_title("Pipeline")
+            .with_direction(Direction::LeftRight)
+            .with_nodes(vec!["A".to_string()]);
+        let chart = FlowChart::builder()
+            .node_simple("A", "Build")
+            .subgraph(sg)
+            .build();
+
+        assert!(chart.to_mermaid().contains(
+            "    subgraph sg1 [\"Pipeline\"]\n        direction LR\n        a[\"Build\"]\n    end\n"
+        ));
+    }
+
+    #[test]
+    fn flowchart_validate_dangling_link() {
+        let chart = FlowChart::builder()
+            .node_simple("A", "Start")
+            .link_simple("A", "Missing")
+            .build();
+
+        match chart.validate() {
+            Err(MermaidError::DanglingReference { kind, id }) => {
+                assert_eq!(kind, "node");
+                assert_eq!(id, "Missing");
+            }
+            other => panic!("expected DanglingReference, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn flowchart_link_to_subgraph() {
+        let chart = FlowChart::builder()
+            .node_simple("a", "Start")
+            .node_simple("b", "Build")
+            .subgraph(Subgraph::new("sg1").with_title("Pipeline").with_node("b"))
+            .link_simple("a", "sg1")
+            .build();
+
+        assert!(chart.validate().is_ok());
+        assert!(chart.to_mermaid().contains("    a --> sg1\n"));
+    }
+
+    #[test]
+    fn flowchart_link_to_nested_subgraph() {
+        let inner = Subgraph::new("Inner").with_node("b");
+        let chart = FlowChart::builder()
+            .node_simple("a", "Start")
+            .node_simple("b", "Build")
+            .subgraph(Subgraph::new("outer").with_subgraph(inner))
+            .link_simple("Inner", "a")
+            .build();
+
+        assert!(chart.validate().is_ok());
+        assert!(chart.to_mermaid().contains("    inner --> a\n"));
+    }
+
+    #[test]
+    fn flowchart_validate_flags_normalization_collision() {
+        let chart = FlowChart::builder()
+            .node_simple("UserService", "Users")
+            .node_simple("userService", "Users (legacy)")
+            .build();
+
+        match chart.validate() {
+            Err(MermaidError::InvalidInput(msg)) => {
+                assert!(msg.contains("'UserService' and 'userService'"));
+                assert!(msg.contains("preserve_ids"));
+            }
+            other => panic!("expected InvalidInput, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn flowchart_preserve_ids() {
+        let chart = FlowChart::builder()
+            .preserve_ids(true)
+            .node_simple("UserService", "Users")
+            .node_simple("userService", "Users (legacy)")
+            .subgraph(Subgraph::new("Backend").with_node("UserService"))
+            .link_simple("userService", "UserService")
+            .build();
+
+        assert!(chart.validate().is_ok());
+        let mermaid = chart.to_mermaid();
+        assert!(mermaid.contains("    userService[\"Users (legacy)\"]\n"));
+        assert!(mermaid.contains(
+            "    subgraph Backend [\"Backend\"]\n        UserService[\"Users\"]\n    end\n"
+        ));
+        assert!(mermaid.contains("    userService --> UserService\n"));
+    }
+
+    #[test]
+    fn flowchart_default_shape() {
+        let chart = FlowChart::builder()
+            .default_shape(NodeShape::Rounded)
+            .node_simple("a", "Unset")
+            .node_with_shape("b", "Explicit", NodeShape::Rectangle)
+            .node(Node::simple("c", "Meta").with_meta("icon", "logos:aws"))
+            .build();
+
+        let mermaid = chart.to_mermaid();
+        assert!(mermaid.contains("    a(\"Unset\")\n"));
+        assert!(mermaid.contains("    b[\"Explicit\"]\n"));
+        assert!(mermaid.contains("c@{ shape: rounded"));
+    }
+
+    #[test]
+    fn flowchart_default_shape_from_yaml() {
+        let yaml = r#"
+default_shape: stadium
+nodes:
+  - id: a
+    label: Start
+  - id: b
+    label: Step
+    shape: rectangle
+"#;
+        let chart = FlowChart::from_yaml(yaml).unwrap();
+        assert_eq!(chart.nodes[0].shape, None);
+        assert_eq!(chart.nodes[1].shape, Some(NodeShape::Rectangle));
+
+        let mermaid = chart.to_mermaid();
+        assert!(mermaid.contains("    a([\"Start\"])\n"));
+        assert!(mermaid.contains("    b[\"Step\"]\n"));
+    }
+
+    #[test]
+    fn flowchart_group_by_prefix() {
+        let mut chart = FlowChart::builder()
+            .node_simple("svc_a", "Service A")
+            .node_simple("svc_b", "Service B")
+            .node_simple("db", "Database")
+            .subgraph(Subgraph::new("old").with_node("svc_a"))
+            .build();
+
+        chart.group_by_prefix("svc_", "services", "Services");
+
+        let services = chart.subgraphs.last().unwrap();
+        assert_eq!(services.nodes, vec!["svc_a", "svc_b"]);
+        assert!(chart.subgraphs[0].nodes.is_empty());
+
+        let mermaid = chart.to_mermaid();
+        let start = mermaid.find("subgraph services [\"Services\"]").unwrap();
+        let end = start + mermaid[start..].find("end").unwrap();
+        let a = mermaid.find("svc_a[").unwrap();
+        let b = mermaid.find("svc_b[").unwrap();
+        let db = mermaid.find("db[").unwrap();
+        assert!(start < a && a < end);
+        assert!(start < b && b < end);
+        assert!(db < start || db > end);
+    }
+
+    #[test]
+    fn flowchart_group_preserve_ids() {
+        let mut chart = FlowChart::builder()
+            .preserve_ids(true)
+            .node_simple("Svc", "New")
+            .node_simple("svc", "Legacy")
+            .subgraph(Subgraph::new("Legacy").with_node("svc"))
+            .build();
+
+        chart.group_where("Current", "Current", |node| node.id == "Svc");
+
+        assert_eq!(chart.subgraphs[0].nodes, vec!["svc"]);
+        let mermaid = chart.to_mermaid();
+        assert!(mermaid
+            .contains("    subgraph Legacy [\"Legacy\"]\n        svc[\"Legacy\"]\n    end\n"));
+        assert!(
+            mermaid.contains("    subgraph Current [\"Current\"]\n        Svc[\"New\"]\n    end\n")
+        );
+    }
+
+    #[test]
+    fn flowchart_link_color_uses_position() {
+        let chart = FlowChart::builder()
+            .link_simple("a", "b")
+            .link(Link::new("b", "c").with_color("#f00"))
+            .link(
+                Link::new("c", "d")
+                    .with_style_props(Style::new().stroke("#0a0").stroke_width("3px")),
+            )
+            .build();
+
+        let mermaid = chart.to_mermaid();
+        assert!(!mermaid.contains("linkStyle 0"));
+        assert!(mermaid.contains("    linkStyle 1 stroke:#f00\n"));
+        assert!(mermaid.contains("    linkStyle 2 stroke:#0a0,stroke-width:3px\n"));
+
+        // Indices follow the links when sorting reorders them
+        let chart = FlowChart::builder()
+            .link(Link::new("x", "y").with_color("#f00"))
+            .link_simple("a", "b")
+            .build();
+        assert!(chart.to_mermaid().contains("    linkStyle 0 stroke:#f00\n"));
+        let sorted = chart.to_mermaid_with(&FormatOptions::default().sort_stable(true));
+        assert!(sorted.contains("    linkStyle 1 stroke:#f00\n"));
+    }
+
+    #[test]
+    fn flowchart_default_class() {
+        let chart = FlowChart::builder()
+            .node_simple("A", "Plain")
+            .node_simple("B", "Special")
+            .default_class(Style::new().fill("#ddd"))
+            .default_class(Style::new().fill("#eee"))
+            .class_def("special", Style::new().fill("#f00"))
+            .class_assignment("special", vec!["B".to_string()])
+            .link_simple("A", "B")
+            .build();
+
+        let mermaid = chart.to_mermaid();
+        assert_eq!(mermaid.matches("classDef default").count(), 1);
+        let default = mermaid.find("classDef default fill:#eee").unwrap();
+        let assignment = mermaid.find("class B special").unwrap();
+        assert!(mermaid.find("a[\"Plain\"]").unwrap() < default);
+        assert!(default < assignment);
+    }
+
+    #[test]
+    fn flowchart_build_script_frontmatter() {
+        let diagram = FlowChart::builder()
+            .title("Orders")
+            .theme(Theme::Dark)
+            .node_simple("a", "A")
+            .build();
+        let script = diagram.build_script_with(ScriptStyle::Frontmatter);
+        assert!(
+            script.starts_with("---\ntitle: Orders\nconfig:\n  theme: dark\n---\nflowchart TB\n")
+        );
+    }
+
+    #[test]
+    fn flowchart_is_empty() {
+        assert!(FlowChart::builder().title("Nothing").build().is_empty());
+        assert!(!FlowChart::builder()
+            .node_simple("a", "A")
+            .build()
+            .is_empty());
+        assert!(FlowChart::from_raw_mermaid("flowchart TB\n    %% todo\n".to_string()).is_empty());
+        assert!(!FlowChart::from_raw_mermaid("flowchart TB\n    A --> B\n".to_string()).is_empty());
+    }
+
+    #[test]
+    fn flowchart_raw_mermaid_gets_config() {
+        let mut chart = FlowChart::from_raw_mermaid("flowchart LR\n    A --> B\n".to_string());
+        chart.config = Some(Config::new().with_theme(Theme::Dark));
+
+        assert_eq!(
+            chart.build_script(),
+            "%%{init: {'theme': 'dark'}}%%\nflowchart LR\n    A --> B\n"
+        );
+    }
+
+    #[test]
+    fn flowchart_raw_mermaid_keeps_own_init() {
+        let raw = "%%{init: {'theme': 'forest'}}%%\nflowchart LR\n    A --> B\n";
+        let mut chart = FlowChart::from_raw_mermaid(raw.to_string());
+        chart.config = Some(Config::new().with_theme(Theme::Dark));
+
+        assert_eq!(chart.build_script(), raw);
+        assert_eq!(chart.build_script_with(ScriptStyle::Frontmatter), raw);
+    }
+
+    #[test]
+    fn flowchart_frontmatter_title_with_colon() {
+        let chart = FlowChart::builder()
+            .title("Step 1: Build")
+            .node_simple("a", "A")
+            .build();
+        let script = chart.build_script();
+        assert!(script.starts_with("---\ntitle: \"Step 1: Build\"\n---\n"));
+
+        let frontmatter = script.split("---\n").nth(1).unwrap();
+        let yaml: serde_yaml::Value = serde_yaml::from_str(frontmatter).unwrap();
+        assert_eq!(yaml["title"], "Step 1: Build");
+    }
 }
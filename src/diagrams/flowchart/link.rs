@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::core::normalize_id;
+use crate::core::{render_id, Style};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Link {
     pub from: String,
     pub to: String,
@@ -14,6 +15,15 @@ pub struct Link {
     pub head: LinkHead,
     #[serde(default)]
     pub tail: LinkHead,
+    /// Edge id (mermaid 11), rendered as `a e1@--> b`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Class applied to the edge (`class e1 animate`); needs an `id`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class_name: Option<String>,
+    /// Stroke/width styling, emitted by the flowchart as `linkStyle <index> ...`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style_props: Option<Style>,
 }
 
 impl Link {
@@ -25,6 +35,9 @@ impl Link {
             label: None,
             head: LinkHead::Arrow,
             tail: LinkHead::None,
+            id: None,
+            class_name: None,
+            style_props: None,
         }
     }
 
@@ -48,20 +61,95 @@ impl Link {
         self
     }
 
+    /// Give the edge an id so it can be styled or animated
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Apply a class to the edge (e.g. `animate`); only rendered when the edge has an id
+    pub fn with_class(mut self, class_name: impl Into<String>) -> Self {
+        self.class_name = Some(class_name.into());
+        self
+    }
+
+    /// Color the edge; the flowchart emits the matching `linkStyle` for its index
+    pub fn with_color(mut self, color: impl Into<String>) -> Self {
+        self.style_props = Some(self.style_props.unwrap_or_default().stroke(color));
+        self
+    }
+
+    /// Style the edge without tracking its `linkStyle` index by hand
+    pub fn with_style_props(mut self, style: Style) -> Self {
+        self.style_props = Some(style);
+        self
+    }
+
     /// Renders the link in mermaid syntax
     pub fn to_mermaid(&self) -> String {
+        self.to_mermaid_with_ids(false)
+    }
+
+    /// Renders the link, keeping endpoint case when `preserve_ids` is set
+    pub fn to_mermaid_with_ids(&self, preserve_ids: bool) -> String {
         let arrow = self.style.arrow_syntax(&self.tail, &self.head);
         // Normalize IDs to match mermaid-py's text_to_snake_case()
-        let from = normalize_id(&self.from);
-        let to = normalize_id(&self.to);
-        match &self.label {
-            Some(label) => format!("{} {}|{}| {}", from, arrow, label, to),
-            None => format!("{} {} {}", from, arrow, to),
+        let from = render_id(&self.from, preserve_ids);
+        let to = render_id(&self.to, preserve_ids);
+        let edge_id = self
+            .id
+            .as_ref()
+            .map(|id| render_id(id, preserve_ids))
+            .map(|id| format!("{}@", id));
+        let prefix = edge_id.as_deref().unwrap_or("");
+        let inline = self.style.inline_label_syntax(&self.tail, &self.head);
+        match (&self.label, inline) {
+            // Open, dotted and thick links carry their text mid-arrow: `a -. text .-> b`
+            (Some(label), Some((open, close))) => {
+                let mut text = format_label(label);
+                if text.contains(['-', '.', '=']) && !text.starts_with('"') {
+                    text = format!("\"{}\"", text);
+                }
+                format!("{} {}{} {} {} {}", from, prefix, open, text, close, to)
+            }
+            (Some(label), None) => format!(
+                "{} {}{}|{}| {}",
+                from,
+                prefix,
+                arrow,
+                format_label(label),
+                to
+            ),
+            (None, _) => format!("{} {}{} {}", from, prefix, arrow, to),
         }
     }
+
+    /// The `class <id> <class>` statement for this edge, if it has both an id and a class
+    pub fn class_statement(&self, preserve_ids: bool) -> Option<String> {
+        let (id, class_name) = (self.id.as_ref()?, self.class_name.as_ref()?);
+        Some(format!(
+            "class {} {}",
+            render_id(id, preserve_ids),
+            class_name
+        ))
+    }
+}
+
+/// Format an edge label for the `|...|` slot.
+///
+/// Newlines become `<br>`. Labels with characters that would end the label or
+/// be read as syntax use the quoted form, with `"` encoded as `#quot;`.
+fn format_label(label: &str) -> String {
+    let label = label.replace("\r\n", "\n").replace('\n', "<br>");
+    if label.contains(['|', '"', '(', ')', '[', ']', '{', '}', '<', '>']) {
+        format!("\"{}\"", label.replace('"', "#quot;"))
+    } else {
+        label
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub enum LinkStyle {
     #[default]
@@ -80,6 +168,8 @@ impl LinkStyle {
         match self {
             Self::Arrow => format!("{}--{}", tail_sym, head_sym),
             Self::Dotted => format!("{}-.-{}", tail_sym, head_sym),
+            // An open thick link is `===`; `==` alone isn't a link
+            Self::Thick if head_sym.is_empty() => format!("{}===", tail_sym),
             Self::Thick => format!("{}=={}", tail_sym, head_sym),
             Self::Invisible => "~~~".to_string(),
             Self::Open => format!(
@@ -90,6 +180,28 @@ impl LinkStyle {
         }
     }
 
+    /// Opening and closing halves for a labeled link written `a <open> text <close> b`.
+    ///
+    /// `None` for styles that keep the `|text|` form (solid arrows, invisible links).
+    pub fn inline_label_syntax(
+        &self,
+        tail: &LinkHead,
+        head: &LinkHead,
+    ) -> Option<(String, String)> {
+        let tail_sym = tail.symbol_left();
+        let head_sym = head.symbol_right();
+
+        match self {
+            Self::Arrow | Self::Invisible => None,
+            Self::Dotted => Some((format!("{}-.", tail_sym), format!(".-{}", head_sym))),
+            Self::Thick if head_sym.is_empty() => {
+                Some((format!("{}==", tail_sym), "===".to_string()))
+            }
+            Self::Thick => Some((format!("{}==", tail_sym), format!("=={}", head_sym))),
+            Self::Open => Some(("--".to_string(), "---".to_string())),
+        }
+    }
+
     pub fn parse(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "arrow" | "solid" => Some(Self::Arrow),
@@ -103,6 +215,7 @@ impl LinkStyle {
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub enum LinkHead {
     Arrow,  // >
@@ -244,4 +357,103 @@ mod tests {
         let link = Link::new("A", "B").with_style(LinkStyle::Open);
         assert_eq!(link.to_mermaid(), "a --- b");
     }
+
+    #[test]
+    fn link_open_with_label() {
+        let link = Link::new("A", "B")
+            .with_style(LinkStyle::Open)
+            .with_label("This is the text");
+        assert_eq!(link.to_mermaid(), "a -- This is the text --- b");
+
+        let link = Link::new("A", "B")
+            .with_style(LinkStyle::Open)
+            .with_label("one-to-one");
+        assert_eq!(link.to_mermaid(), "a -- \"one-to-one\" --- b");
+
+        let link = Link::new("A", "B")
+            .with_style(LinkStyle::Open)
+            .with_label("a | b");
+        assert_eq!(link.to_mermaid(), "a -- \"a | b\" --- b");
+    }
+
+    #[test]
+    fn link_dotted_and_thick_with_label() {
+        let link = Link::new("A", "B")
+            .with_style(LinkStyle::Dotted)
+            .with_label("maybe");
+        assert_eq!(link.to_mermaid(), "a -. maybe .-> b");
+
+        let link = Link::new("A", "B")
+            .with_style(LinkStyle::Thick)
+            .with_label("always");
+        assert_eq!(link.to_mermaid(), "a == always ==> b");
+
+        let link = Link::new("A", "B")
+            .with_style(LinkStyle::Thick)
+            .with_tail(LinkHead::Arrow)
+            .with_label("sync");
+        assert_eq!(link.to_mermaid(), "a <== sync ==> b");
+
+        let link = Link::new("A", "B")
+            .with_style(LinkStyle::Thick)
+            .with_head(LinkHead::None)
+            .with_label("always");
+        assert_eq!(link.to_mermaid(), "a == always === b");
+
+        let link = Link::new("A", "B")
+            .with_style(LinkStyle::Thick)
+            .with_head(LinkHead::None);
+        assert_eq!(link.to_mermaid(), "a === b");
+    }
+
+    #[test]
+    fn link_with_edge_id_and_class() {
+        let link = Link::new("A", "B").with_id("e1").with_class("animate");
+        assert_eq!(link.to_mermaid(), "a e1@--> b");
+        assert_eq!(
+            link.class_statement(false).as_deref(),
+            Some("class e1 animate")
+        );
+
+        let link = Link::new("A", "B").with_id("e2").with_label("go");
+        assert_eq!(link.to_mermaid(), "a e2@-->|go| b");
+
+        let link = Link::new("A", "B")
+            .with_style(LinkStyle::Dotted)
+            .with_id("e3")
+            .with_label("maybe");
+        assert_eq!(link.to_mermaid(), "a e3@-. maybe .-> b");
+
+        // A class without an id has nothing to target
+        let link = Link::new("A", "B").with_class("animate");
+        assert_eq!(link.to_mermaid(), "a --> b");
+        assert_eq!(link.class_statement(false), None);
+    }
+
+    #[test]
+    fn link_arrow_keeps_pipe_label() {
+        let link = Link::new("A", "B").with_label("yes");
+        assert_eq!(link.to_mermaid(), "a -->|yes| b");
+    }
+
+    #[test]
+    fn link_multiline_label() {
+        let link = Link::new("A", "B").with_label("line1\nline2");
+        assert_eq!(link.to_mermaid(), "a -->|\"line1<br>line2\"| b");
+    }
+
+    #[test]
+    fn link_label_special_chars_quoted() {
+        let link = Link::new("A", "B").with_label("yes | no");
+        assert_eq!(link.to_mermaid(), "a -->|\"yes | no\"| b");
+
+        let link = Link::new("A", "B").with_label("say \"hi\"");
+        assert_eq!(link.to_mermaid(), "a -->|\"say #quot;hi#quot;\"| b");
+    }
+
+    #[test]
+    fn link_single_line_label_unchanged() {
+        let link = Link::new("A", "B").with_label("Yes");
+        assert_eq!(link.to_mermaid(), "a -->|Yes| b");
+    }
 }
@@ -4,8 +4,8 @@ mod link;
 mod node;
 mod subgraph;
 
-pub use class_def::{ClassAssignment, ClassDef, LinkStyleDef};
-pub use diagram::{FlowChart, FlowChartBuilder};
+pub use class_def::{ClassAssignment, ClassDef, LinkStyleDef, LinkStyleTarget};
+pub use diagram::{FlowChart, FlowChartBuilder, FlowchartKeyword};
 pub use link::{Link, LinkHead, LinkStyle};
 pub use node::{HrefType, Node, NodeShape};
 pub use subgraph::Subgraph;
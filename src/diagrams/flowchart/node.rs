@@ -1,13 +1,16 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-use crate::core::{normalize_id, Style};
+use crate::core::{render_id, Style};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Node {
     pub id: String,
     pub label: String,
-    #[serde(default)]
-    pub shape: NodeShape,
+    /// Explicit shape; unset nodes take the flowchart's `default_shape`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shape: Option<NodeShape>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub style: Option<Style>,
     /// Optional hyperlink for clickable nodes
@@ -16,9 +19,19 @@ pub struct Node {
     /// How the hyperlink opens (default: Blank for new tab)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub href_type: Option<HrefType>,
+    /// Tooltip shown when hovering a clickable node
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tooltip: Option<String>,
     /// CSS class name for the node (uses :::className shorthand syntax)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub class_name: Option<String>,
+    /// Font Awesome icon shown before the label (e.g. "fa:fa-book")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Mermaid v11 `@{ ... }` metadata (e.g. `icon: "logos:aws"`); any entry
+    /// switches the node to the `@{}` form
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub meta: BTreeMap<String, String>,
 }
 
 impl Node {
@@ -26,14 +39,35 @@ impl Node {
         Self {
             id: id.into(),
             label: label.into(),
-            shape,
+            shape: Some(shape),
             style: None,
             href: None,
             href_type: None,
+            tooltip: None,
             class_name: None,
+            icon: None,
+            meta: BTreeMap::new(),
         }
     }
 
+    /// Node without an explicit shape, rendered with the diagram's default shape
+    pub fn simple(id: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            shape: None,
+            ..Self::new(id, label, NodeShape::default())
+        }
+    }
+
+    pub fn with_shape(mut self, shape: NodeShape) -> Self {
+        self.shape = Some(shape);
+        self
+    }
+
+    /// The explicit shape, or `default_shape` when none is set
+    pub fn shape_or(&self, default_shape: NodeShape) -> NodeShape {
+        self.shape.unwrap_or(default_shape)
+    }
+
     pub fn with_style(mut self, style: Style) -> Self {
         self.style = Some(style);
         self
@@ -51,17 +85,60 @@ impl Node {
         self
     }
 
+    /// Set the hover tooltip for the hyperlink (only rendered with an href)
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Prefix the label with an icon, e.g. "fa:fa-book" or "fab:fa-github".
+    /// A bare "fa-book" is treated as "fa:fa-book".
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
     /// Add a CSS class name to this node (uses :::className shorthand)
     pub fn with_class(mut self, class_name: impl Into<String>) -> Self {
         self.class_name = Some(class_name.into());
         self
     }
 
+    /// Attach a `@{ key: value }` metadata entry; `shape` and `label` override the node's own
+    pub fn with_meta(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.meta.insert(key.into(), value.into());
+        self
+    }
+
     /// Renders the node in mermaid syntax
     pub fn to_mermaid(&self) -> String {
+        self.to_mermaid_with_ids(false)
+    }
+
+    /// Renders the node, keeping the id's case when `preserve_ids` is set
+    pub fn to_mermaid_with_ids(&self, preserve_ids: bool) -> String {
+        self.to_mermaid_with_defaults(preserve_ids, NodeShape::default())
+    }
+
+    /// Renders the node, falling back to `default_shape` when no shape is set
+    pub fn to_mermaid_with_defaults(&self, preserve_ids: bool, default_shape: NodeShape) -> String {
+        let shape = self.shape_or(default_shape);
         // Normalize ID to match mermaid-py's text_to_snake_case()
-        let normalized_id = normalize_id(&self.id);
-        let mut output = format!("{}{}", normalized_id, self.shape.wrap(&self.label));
+        let normalized_id = render_id(&self.id, preserve_ids);
+        let label = match &self.icon {
+            Some(icon) if icon.contains(':') => format!("{} {}", icon, self.label),
+            Some(icon) => format!("fa:{} {}", icon, self.label),
+            None => self.label.clone(),
+        };
+        let mut output = if self.meta.is_empty() {
+            format!("{}{}", normalized_id, shape.wrap(&label))
+        } else {
+            format!(
+                "{}@{{ {} }}",
+                normalized_id,
+                self.meta_entries(&label, shape).join(", ")
+            )
+        };
 
         // Add class shorthand if set
         if let Some(class_name) = &self.class_name {
@@ -71,20 +148,54 @@ impl Node {
         // Add click directive if href is set
         if let Some(href) = &self.href {
             let href_type = self.href_type.unwrap_or_default();
-            output.push_str(&format!(
-                "\n    click {} \"{}\" {}",
-                normalized_id,
-                href,
-                href_type.as_str()
-            ));
+            output.push_str(&format!("\n    click {} \"{}\"", normalized_id, href));
+            if let Some(tooltip) = &self.tooltip {
+                output.push_str(&format!(" \"{}\"", tooltip.replace('"', "#quot;")));
+            }
+            output.push_str(&format!(" {}", href_type.as_str()));
         }
 
         output
     }
+
+    /// `key: value` pairs for the `@{}` block: shape, label, then metadata by key
+    fn meta_entries(&self, label: &str, shape: NodeShape) -> Vec<String> {
+        let shape = self
+            .meta
+            .get("shape")
+            .map(String::as_str)
+            .unwrap_or(shape.v11_name());
+        let label = self.meta.get("label").map(String::as_str).unwrap_or(label);
+
+        let mut entries = vec![
+            format!("shape: {}", meta_value(shape)),
+            format!("label: \"{}\"", label.replace('"', "#quot;")),
+        ];
+        for (key, value) in &self.meta {
+            if key != "shape" && key != "label" {
+                entries.push(format!("{}: {}", key, meta_value(value)));
+            }
+        }
+        entries
+    }
+}
+
+/// Bare words stay unquoted in `@{}` blocks; anything else is double-quoted
+fn meta_value(value: &str) -> String {
+    let bare = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+    if bare {
+        value.to_string()
+    } else {
+        format!("\"{}\"", value.replace('"', "#quot;"))
+    }
 }
 
 /// How a hyperlink should open when clicked
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum HrefType {
     /// Open in new tab/window (_blank)
@@ -121,6 +232,7 @@ impl HrefType {
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub enum NodeShape {
     #[default]
@@ -160,31 +272,98 @@ impl NodeShape {
         }
     }
 
-    pub fn parse(s: &str) -> Option<Self> {
-        match s.to_lowercase().as_str() {
-            "rectangle" | "rect" => Some(Self::Rectangle),
-            "rounded" | "round" => Some(Self::Rounded),
-            "stadium" => Some(Self::Stadium),
-            "subroutine" => Some(Self::Subroutine),
-            "cylinder" | "db" | "database" => Some(Self::Cylinder),
-            "circle" => Some(Self::Circle),
-            "asymmetric" | "flag" => Some(Self::Asymmetric),
-            "rhombus" | "diamond" | "decision" => Some(Self::Rhombus),
-            "hexagon" | "hex" => Some(Self::Hexagon),
-            "parallelogram" | "para" => Some(Self::Parallelogram),
-            "parallelogram-alt" | "para-alt" => Some(Self::ParallelogramAlt),
-            "trapezoid" | "trap" => Some(Self::Trapezoid),
-            "trapezoid-alt" | "trap-alt" => Some(Self::TrapezoidAlt),
-            "double-circle" | "doublecircle" => Some(Self::DoubleCircle),
-            _ => None,
+    /// Short name used by mermaid v11's `@{ shape: ... }` syntax
+    pub fn v11_name(&self) -> &'static str {
+        match self {
+            Self::Rectangle => "rect",
+            Self::Rounded => "rounded",
+            Self::Stadium => "stadium",
+            Self::Subroutine => "subproc",
+            Self::Cylinder => "cyl",
+            Self::Circle => "circle",
+            Self::Asymmetric => "odd",
+            Self::Rhombus => "diam",
+            Self::Hexagon => "hex",
+            Self::Parallelogram => "lean-r",
+            Self::ParallelogramAlt => "lean-l",
+            Self::Trapezoid => "trap-b",
+            Self::TrapezoidAlt => "trap-t",
+            Self::DoubleCircle => "dbl-circ",
         }
     }
+
+    /// Accepted spec tokens, canonical name first for each shape
+    pub const ALIASES: &'static [(&'static str, Self)] = &[
+        ("rectangle", Self::Rectangle),
+        ("rect", Self::Rectangle),
+        ("rounded", Self::Rounded),
+        ("round", Self::Rounded),
+        ("stadium", Self::Stadium),
+        ("subroutine", Self::Subroutine),
+        ("cylinder", Self::Cylinder),
+        ("db", Self::Cylinder),
+        ("database", Self::Cylinder),
+        ("circle", Self::Circle),
+        ("asymmetric", Self::Asymmetric),
+        ("flag", Self::Asymmetric),
+        ("rhombus", Self::Rhombus),
+        ("diamond", Self::Rhombus),
+        ("decision", Self::Rhombus),
+        ("hexagon", Self::Hexagon),
+        ("hex", Self::Hexagon),
+        ("parallelogram", Self::Parallelogram),
+        ("para", Self::Parallelogram),
+        ("parallelogram-alt", Self::ParallelogramAlt),
+        ("para-alt", Self::ParallelogramAlt),
+        ("trapezoid", Self::Trapezoid),
+        ("trap", Self::Trapezoid),
+        ("trapezoid-alt", Self::TrapezoidAlt),
+        ("trap-alt", Self::TrapezoidAlt),
+        ("double-circle", Self::DoubleCircle),
+        ("doublecircle", Self::DoubleCircle),
+    ];
+
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.to_lowercase();
+        Self::ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == s)
+            .map(|(_, value)| *value)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn node_meta_single_block() {
+        let node = Node::new("A", "Lambda", NodeShape::Rounded)
+            .with_meta("icon", "logos:aws")
+            .with_meta("pos", "t");
+        assert_eq!(
+            node.to_mermaid(),
+            "a@{ shape: rounded, label: \"Lambda\", icon: \"logos:aws\", pos: t }"
+        );
+    }
+
+    #[test]
+    fn node_meta_overrides_shape_and_label() {
+        let node = Node::new("A", "Start", NodeShape::Rectangle)
+            .with_meta("shape", "doc")
+            .with_meta("label", "Say \"hi\"");
+        assert_eq!(
+            node.to_mermaid(),
+            "a@{ shape: doc, label: \"Say #quot;hi#quot;\" }"
+        );
+    }
+
+    #[test]
+    fn node_without_meta_uses_bracket_form() {
+        let node = Node::new("A", "Start", NodeShape::Stadium);
+        assert_eq!(node.to_mermaid(), "a([\"Start\"])");
+    }
+
     #[test]
     fn node_shapes_wrap_correctly() {
         assert_eq!(NodeShape::Rectangle.wrap("Test"), "[\"Test\"]");
@@ -233,6 +412,48 @@ mod tests {
         assert!(mermaid.contains("click docs \"https://docs.example.com\" _self"));
     }
 
+    #[test]
+    fn node_with_href_and_tooltip() {
+        let node = Node::new("docs", "Documentation", NodeShape::Rectangle)
+            .with_href("https://docs.example.com")
+            .with_tooltip("Open the docs");
+        assert_eq!(
+            node.to_mermaid(),
+            "docs[\"Documentation\"]\n    click docs \"https://docs.example.com\" \"Open the docs\" _blank"
+        );
+    }
+
+    #[test]
+    fn node_with_href_without_tooltip_unchanged() {
+        let node = Node::new("docs", "Documentation", NodeShape::Rectangle)
+            .with_href("https://docs.example.com");
+        assert_eq!(
+            node.to_mermaid(),
+            "docs[\"Documentation\"]\n    click docs \"https://docs.example.com\" _blank"
+        );
+    }
+
+    #[test]
+    fn node_tooltip_ignored_without_href() {
+        let node = Node::new("A", "Start", NodeShape::Rectangle).with_tooltip("Hover");
+        assert_eq!(node.to_mermaid(), "a[\"Start\"]");
+    }
+
+    #[test]
+    fn node_with_icon() {
+        let node = Node::new("A", "Start", NodeShape::Rectangle).with_icon("fa:fa-book");
+        assert_eq!(node.to_mermaid(), "a[\"fa:fa-book Start\"]");
+
+        let node = Node::new("gh", "GitHub", NodeShape::Rounded).with_icon("fab:fa-github");
+        assert_eq!(node.to_mermaid(), "gh(\"fab:fa-github GitHub\")");
+    }
+
+    #[test]
+    fn node_with_bare_icon_defaults_to_fa() {
+        let node = Node::new("A", "Start", NodeShape::Stadium).with_icon("fa-book");
+        assert_eq!(node.to_mermaid(), "a([\"fa:fa-book Start\"])");
+    }
+
     #[test]
     fn href_type_parse() {
         assert_eq!(HrefType::parse("blank"), Some(HrefType::Blank));
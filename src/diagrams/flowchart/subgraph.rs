@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::core::Direction;
+use crate::core::{render_id, Direction};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Subgraph {
     pub id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -55,8 +56,17 @@ impl Subgraph {
 
     /// Renders the subgraph start in mermaid syntax
     pub fn to_mermaid_start(&self) -> String {
+        self.to_mermaid_start_with_ids(false)
+    }
+
+    /// Renders the subgraph start, keeping id case when `preserve_ids` is set
+    pub fn to_mermaid_start_with_ids(&self, preserve_ids: bool) -> String {
         let title = self.title.as_deref().unwrap_or(&self.id);
-        let mut output = format!("subgraph {} [\"{}\"]\n", self.id, title);
+        let mut output = format!(
+            "subgraph {} [\"{}\"]\n",
+            render_id(&self.id, preserve_ids),
+            title
+        );
         if let Some(dir) = &self.direction {
             output.push_str(&format!("    direction {}\n", dir));
         }
@@ -70,8 +80,18 @@ impl Subgraph {
 
     /// Renders the complete subgraph with nested subgraphs (with indentation)
     pub fn to_mermaid_with_indent(&self, base_indent: &str) -> String {
+        self.to_mermaid_with_indent_and_ids(base_indent, false)
+    }
+
+    /// Like [`Self::to_mermaid_with_indent`], keeping id case when `preserve_ids` is set
+    pub fn to_mermaid_with_indent_and_ids(&self, base_indent: &str, preserve_ids: bool) -> String {
         let title = self.title.as_deref().unwrap_or(&self.id);
-        let mut output = format!("{}subgraph {} [\"{}\"]\n", base_indent, self.id, title);
+        let mut output = format!(
+            "{}subgraph {} [\"{}\"]\n",
+            base_indent,
+            render_id(&self.id, preserve_ids),
+            title
+        );
 
         let inner_indent = format!("{}    ", base_indent);
 
@@ -81,7 +101,7 @@ impl Subgraph {
 
         // Render nested subgraphs recursively
         for subgraph in &self.subgraphs {
-            output.push_str(&subgraph.to_mermaid_with_indent(&inner_indent));
+            output.push_str(&subgraph.to_mermaid_with_indent_and_ids(&inner_indent, preserve_ids));
         }
 
         output.push_str(&format!("{}end\n", base_indent));
@@ -109,6 +129,24 @@ mod tests {
         assert!(output.contains("direction LR"));
     }
 
+    #[test]
+    fn subgraph_id_normalized() {
+        let sg = Subgraph::new("Backend Services");
+        assert!(sg
+            .to_mermaid_start()
+            .starts_with("subgraph backend_services [\"Backend Services\"]"));
+    }
+
+    #[test]
+    fn subgraph_preserve_ids() {
+        let sg = Subgraph::new("Backend").with_subgraph(Subgraph::new("DataTier"));
+        assert!(sg
+            .to_mermaid_start_with_ids(true)
+            .starts_with("subgraph Backend [\"Backend\"]"));
+        let output = sg.to_mermaid_with_indent_and_ids("", true);
+        assert!(output.contains("    subgraph DataTier [\"DataTier\"]\n"));
+    }
+
     #[test]
     fn nested_subgraph() {
         let inner = Subgraph::new("inner").with_title("Inner Group");
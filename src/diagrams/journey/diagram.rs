@@ -1,13 +1,22 @@
 use serde::{Deserialize, Serialize};
 
-use crate::core::{Config, Diagram, MermaidError};
+use crate::core::{
+    accessibility_lines, statement_count, Config, Diagram, FromConfig, MermaidError, Theme,
+};
 
 use super::{Section, Task};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Journey {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
+    /// Accessible title (`accTitle`) for screen readers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acc_title: Option<String>,
+    /// Accessible description (`accDescr`) for screen readers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acc_descr: Option<String>,
     #[serde(default)]
     pub sections: Vec<Section>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -24,6 +33,8 @@ impl Journey {
     pub fn from_raw_mermaid(script: String) -> Self {
         Self {
             title: None,
+            acc_title: None,
+            acc_descr: None,
             sections: Vec::new(),
             config: None,
             raw_mermaid: Some(script),
@@ -41,6 +52,28 @@ impl Journey {
     pub fn from_toml(toml: &str) -> Result<Self, MermaidError> {
         toml::from_str(toml).map_err(|e| MermaidError::ParseError(e.to_string()))
     }
+
+    /// Check for task scores outside [`Task::SCORE_RANGE`].
+    ///
+    /// Catches scores set directly on the public `score` field, which
+    /// bypass [`Task::new`]'s clamp.
+    pub fn validate(&self) -> Result<(), MermaidError> {
+        for section in &self.sections {
+            for task in &section.tasks {
+                if !Task::SCORE_RANGE.contains(&task.score) {
+                    return Err(MermaidError::InvalidInput(format!(
+                        "Task '{}' in section '{}' has score {}; expected {}..={}",
+                        task.name,
+                        section.name,
+                        task.score,
+                        Task::SCORE_RANGE.start(),
+                        Task::SCORE_RANGE.end()
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Diagram for Journey {
@@ -56,6 +89,13 @@ impl Diagram for Journey {
         self.config.as_ref()
     }
 
+    fn is_empty(&self) -> bool {
+        if let Some(raw) = &self.raw_mermaid {
+            return statement_count(raw) == 0;
+        }
+        self.sections.iter().all(|s| s.tasks.is_empty())
+    }
+
     fn to_mermaid(&self) -> String {
         if let Some(raw) = &self.raw_mermaid {
             return raw.clone();
@@ -66,6 +106,11 @@ impl Diagram for Journey {
         if let Some(title) = &self.title {
             output.push_str(&format!("\ttitle {}\n", title));
         }
+        output.push_str(&accessibility_lines(
+            self.acc_title.as_deref(),
+            self.acc_descr.as_deref(),
+            "\t",
+        ));
 
         for section in &self.sections {
             output.push_str(&section.to_mermaid());
@@ -75,11 +120,28 @@ impl Diagram for Journey {
     }
 }
 
+impl FromConfig for Journey {
+    fn from_json(json: &str) -> Result<Self, MermaidError> {
+        Self::from_json(json)
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Self, MermaidError> {
+        Self::from_yaml(yaml)
+    }
+
+    fn from_toml(toml: &str) -> Result<Self, MermaidError> {
+        Self::from_toml(toml)
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct JourneyBuilder {
     title: Option<String>,
+    acc_title: Option<String>,
+    acc_descr: Option<String>,
     sections: Vec<Section>,
     current_section: Option<Section>,
+    config: Option<Config>,
 }
 
 impl JourneyBuilder {
@@ -92,6 +154,18 @@ impl JourneyBuilder {
         self
     }
 
+    /// Set the accessible title (`accTitle`)
+    pub fn acc_title(mut self, title: impl Into<String>) -> Self {
+        self.acc_title = Some(title.into());
+        self
+    }
+
+    /// Set the accessible description (`accDescr`)
+    pub fn acc_descr(mut self, descr: impl Into<String>) -> Self {
+        self.acc_descr = Some(descr.into());
+        self
+    }
+
     /// Start a new section
     pub fn section(mut self, name: impl Into<String>) -> Self {
         // Save current section if any
@@ -140,6 +214,17 @@ impl JourneyBuilder {
         self
     }
 
+    pub fn theme(mut self, theme: Theme) -> Self {
+        let config = self.config.get_or_insert_with(Config::default);
+        config.theme = theme;
+        self
+    }
+
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = Some(config);
+        self
+    }
+
     pub fn build(mut self) -> Journey {
         // Don't forget the last section
         if let Some(section) = self.current_section.take() {
@@ -148,8 +233,10 @@ impl JourneyBuilder {
 
         Journey {
             title: self.title,
+            acc_title: self.acc_title,
+            acc_descr: self.acc_descr,
             sections: self.sections,
-            config: None,
+            config: self.config,
             raw_mermaid: None,
         }
     }
@@ -158,6 +245,7 @@ impl JourneyBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::ScriptStyle;
 
     #[test]
     fn journey_basic() {
@@ -212,10 +300,95 @@ sections:
         assert_eq!(journey.title, Some("Test Journey".to_string()));
     }
 
+    #[test]
+    fn journey_from_yaml_multiple_actors() {
+        let yaml = r#"
+sections:
+  - name: Together
+    tasks:
+      - name: Plan trip
+        score: 4
+        actors: [Me, You]
+"#;
+
+        let journey = Journey::from_yaml(yaml).unwrap();
+        assert_eq!(journey.sections[0].tasks[0].actors, vec!["Me", "You"]);
+        assert!(journey
+            .to_mermaid()
+            .contains("\t\tPlan trip: 4 : Me, You\n"));
+    }
+
+    #[test]
+    fn journey_from_yaml_invalid_score() {
+        let yaml = "sections:\n  - name: S\n    tasks:\n      - name: T\n        score: 7\n";
+        let err = Journey::from_yaml(yaml).unwrap_err();
+        assert!(err.to_string().contains("between 0 and 5"));
+    }
+
+    #[test]
+    fn journey_validate_scores() {
+        let mut journey = Journey::builder()
+            .section("S")
+            .task("Fine", 0)
+            .task("Great", 5)
+            .build();
+        assert!(journey.validate().is_ok());
+
+        journey.sections[0].tasks[1].score = 200;
+        let err = journey.validate().unwrap_err();
+        assert!(err.to_string().contains("Task 'Great' in section 'S'"));
+    }
+
+    #[test]
+    fn journey_theme_in_build_script() {
+        let journey = Journey::builder()
+            .theme(Theme::Forest)
+            .section("S")
+            .task("T", 3)
+            .build();
+
+        let script = journey.build_script();
+        assert!(script.starts_with("%%{init:"));
+        assert!(script.contains("'theme': 'forest'"));
+        assert!(script.contains("journey\n"));
+    }
+
     #[test]
     fn journey_raw_mermaid() {
         let raw = "journey\n    title Test\n    section S1\n        Task: 5";
         let journey = Journey::from_raw_mermaid(raw.to_string());
         assert_eq!(journey.to_mermaid(), raw);
     }
+
+    #[test]
+    fn journey_accessibility() {
+        let journey = Journey::builder()
+            .title("Checkout")
+            .acc_title("Checkout journey")
+            .acc_descr("How a shopper pays")
+            .section("Pay")
+            .task("Enter card", 3)
+            .build();
+
+        let mermaid = journey.to_mermaid();
+        assert!(mermaid.starts_with(
+            "journey\n\ttitle Checkout\n\taccTitle: Checkout journey\n\taccDescr: How a shopper pays\n"
+        ));
+    }
+
+    #[test]
+    fn journey_build_script_frontmatter() {
+        // journey keeps its title in the body; accessibility lines follow the header
+        let journey = Journey::builder()
+            .title("Orders")
+            .acc_title("Order flow")
+            .theme(Theme::Dark)
+            .section("Checkout")
+            .task("Pay", 5)
+            .build();
+        let script = journey.build_script_with(ScriptStyle::Frontmatter);
+        assert!(script.starts_with(
+            "---\nconfig:\n  theme: dark\n---\njourney\n\ttitle Orders\n\taccTitle: Order flow\n"
+        ));
+    }
 }
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use super::Task;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Section {
     pub name: String,
     #[serde(default)]
@@ -1,14 +1,45 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::ops::RangeInclusive;
+
+use crate::core::MermaidError;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Task {
     pub name: String,
-    pub score: u8, // 0-5 satisfaction score
+    /// Satisfaction score; mermaid's scale is [`Task::SCORE_RANGE`]
+    #[serde(deserialize_with = "deserialize_score")]
+    pub score: u8,
     #[serde(default)]
     pub actors: Vec<String>,
 }
 
+fn deserialize_score<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u8, D::Error> {
+    let score = u8::deserialize(deserializer)?;
+    Task::check_score(score).map_err(serde::de::Error::custom)
+}
+
 impl Task {
+    /// Accepted scores, matching [`Task::new`]'s clamp.
+    ///
+    /// Mermaid's scale is 1..=5, but 0 is accepted too: mermaid draws it with the
+    /// same sad face as 1 and 2, and `Task::new(_, 0)` journeys predate validation.
+    pub const SCORE_RANGE: RangeInclusive<u8> = 0..=5;
+
+    /// Return `score` if it is within [`Self::SCORE_RANGE`]
+    pub fn check_score(score: u8) -> Result<u8, MermaidError> {
+        if Self::SCORE_RANGE.contains(&score) {
+            Ok(score)
+        } else {
+            Err(MermaidError::InvalidInput(format!(
+                "Journey task score must be between {} and {}, got {}",
+                Self::SCORE_RANGE.start(),
+                Self::SCORE_RANGE.end(),
+                score
+            )))
+        }
+    }
+
     pub fn new(name: impl Into<String>, score: u8) -> Self {
         Self {
             name: name.into(),
@@ -17,6 +48,16 @@ impl Task {
         }
     }
 
+    /// Checked constructor: rejects scores outside [`Self::SCORE_RANGE`] instead of clamping
+    pub fn try_new(name: impl Into<String>, score: u8) -> Result<Self, MermaidError> {
+        let score = Self::check_score(score)?;
+        Ok(Self {
+            name: name.into(),
+            score,
+            actors: Vec::new(),
+        })
+    }
+
     pub fn with_actor(mut self, actor: impl Into<String>) -> Self {
         self.actors.push(actor.into());
         self
@@ -57,6 +98,36 @@ mod tests {
         assert!(mermaid.contains("Login: 4 : User"));
     }
 
+    #[test]
+    fn task_serde_round_trips_actors() {
+        let task = Task::new("Login", 4).with_actor("Me").with_actor("You");
+        let json = serde_json::to_string(&task).unwrap();
+        assert!(json.contains(r#""actors":["Me","You"]"#));
+
+        let back: Task = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.actors, vec!["Me", "You"]);
+    }
+
+    #[test]
+    fn task_score_out_of_range_rejected() {
+        let err = serde_json::from_str::<Task>(r#"{"name": "Bad", "score": 9}"#).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("score must be between 0 and 5, got 9"));
+        assert!(Task::check_score(3).is_ok());
+        assert!(Task::check_score(6).is_err());
+    }
+
+    #[test]
+    fn task_try_new() {
+        assert_eq!(Task::try_new("Ok", 0).unwrap().score, 0);
+        assert_eq!(Task::try_new("Ok", 5).unwrap().score, 5);
+        assert!(matches!(
+            Task::try_new("Bad", 200),
+            Err(MermaidError::InvalidInput(_))
+        ));
+    }
+
     #[test]
     fn task_score_clamped() {
         let task = Task::new("Test", 10); // Should clamp to 5
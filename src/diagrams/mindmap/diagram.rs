@@ -1,14 +1,24 @@
 use serde::{Deserialize, Serialize};
 
-use crate::core::{Config, Diagram, MermaidError, Theme};
+use crate::core::{
+    accessibility_lines, statement_count, Config, Diagram, FromConfig, MermaidError, Theme,
+};
 
 use super::{MindmapNode, MindmapNodeShape};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Mindmap {
     pub root: MindmapNode,
+    /// Rendered in frontmatter; mindmaps have no inline title syntax
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
+    /// Accessible title (`accTitle`) for screen readers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acc_title: Option<String>,
+    /// Accessible description (`accDescr`) for screen readers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acc_descr: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config: Option<Config>,
     #[serde(skip)]
@@ -24,6 +34,8 @@ impl Mindmap {
         Self {
             root: MindmapNode::new(""),
             title: None,
+            acc_title: None,
+            acc_descr: None,
             config: None,
             raw_mermaid: Some(script),
         }
@@ -55,21 +67,53 @@ impl Diagram for Mindmap {
         self.config.as_ref()
     }
 
+    fn is_empty(&self) -> bool {
+        if let Some(raw) = &self.raw_mermaid {
+            return statement_count(raw) == 0;
+        }
+        self.root.text.is_empty() && self.root.children.is_empty()
+    }
+
+    fn frontmatter_title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
     fn to_mermaid(&self) -> String {
         if let Some(raw) = &self.raw_mermaid {
             return raw.clone();
         }
 
         let mut output = String::from("mindmap\n");
+        output.push_str(&accessibility_lines(
+            self.acc_title.as_deref(),
+            self.acc_descr.as_deref(),
+            "    ",
+        ));
         output.push_str(&self.root.to_mermaid(1));
         output
     }
 }
 
+impl FromConfig for Mindmap {
+    fn from_json(json: &str) -> Result<Self, MermaidError> {
+        Self::from_json(json)
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Self, MermaidError> {
+        Self::from_yaml(yaml)
+    }
+
+    fn from_toml(toml: &str) -> Result<Self, MermaidError> {
+        Self::from_toml(toml)
+    }
+}
+
 #[derive(Debug)]
 pub struct MindmapBuilder {
     root: MindmapNode,
     title: Option<String>,
+    acc_title: Option<String>,
+    acc_descr: Option<String>,
     config: Option<Config>,
 }
 
@@ -78,6 +122,8 @@ impl MindmapBuilder {
         Self {
             root: MindmapNode::new(root_text),
             title: None,
+            acc_title: None,
+            acc_descr: None,
             config: None,
         }
     }
@@ -87,6 +133,18 @@ impl MindmapBuilder {
         self
     }
 
+    /// Set the accessible title (`accTitle`)
+    pub fn acc_title(mut self, title: impl Into<String>) -> Self {
+        self.acc_title = Some(title.into());
+        self
+    }
+
+    /// Set the accessible description (`accDescr`)
+    pub fn acc_descr(mut self, descr: impl Into<String>) -> Self {
+        self.acc_descr = Some(descr.into());
+        self
+    }
+
     pub fn root_shape(mut self, shape: MindmapNodeShape) -> Self {
         self.root = self.root.with_shape(shape);
         self
@@ -127,6 +185,8 @@ impl MindmapBuilder {
         Mindmap {
             root: self.root,
             title: self.title,
+            acc_title: self.acc_title,
+            acc_descr: self.acc_descr,
             config: self.config,
             raw_mermaid: None,
         }
@@ -136,6 +196,7 @@ impl MindmapBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::ScriptStyle;
 
     #[test]
     fn mindmap_basic() {
@@ -196,4 +257,52 @@ root:
         let mindmap = Mindmap::from_raw_mermaid(raw.to_string());
         assert_eq!(mindmap.to_mermaid(), raw);
     }
+
+    #[test]
+    fn mindmap_title_in_frontmatter() {
+        let mindmap = Mindmap::builder("Root")
+            .title("My Mindmap")
+            .child("Child")
+            .build();
+
+        assert!(!mindmap.to_mermaid().contains("My Mindmap"));
+        assert!(mindmap
+            .build_script()
+            .starts_with("---\ntitle: My Mindmap\n---\nmindmap\n"));
+    }
+
+    #[test]
+    fn mindmap_accessibility() {
+        let mindmap = Mindmap::builder("Root")
+            .acc_title("Project map")
+            .acc_descr("Areas of work")
+            .build();
+
+        assert!(mindmap
+            .to_mermaid()
+            .starts_with("mindmap\n    accTitle: Project map\n    accDescr: Areas of work\n"));
+    }
+
+    #[test]
+    fn mindmap_build_script_frontmatter() {
+        let diagram = Mindmap::builder("Orders")
+            .title("Orders")
+            .theme(Theme::Dark)
+            .build();
+        let script = diagram.build_script_with(ScriptStyle::Frontmatter);
+        assert!(script.starts_with("---\ntitle: Orders\nconfig:\n  theme: dark\n---\nmindmap\n"));
+    }
+
+    #[test]
+    fn mindmap_build_script_accessibility_after_header() {
+        let mindmap = Mindmap::builder("Orders")
+            .title("Orders")
+            .acc_title("Order map")
+            .theme(Theme::Dark)
+            .build();
+        let script = mindmap.build_script_with(ScriptStyle::Frontmatter);
+        assert!(script.starts_with(
+            "---\ntitle: Orders\nconfig:\n  theme: dark\n---\nmindmap\n    accTitle: Order map\n"
+        ));
+    }
 }
@@ -1,7 +1,12 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct MindmapNode {
+    /// Node id, rendered as `id1[Text]`; plain-shaped nodes have no brackets to
+    /// separate an id from the text, so it is only emitted for other shapes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     pub text: String,
     #[serde(default)]
     pub shape: NodeShape,
@@ -16,6 +21,7 @@ pub struct MindmapNode {
 impl MindmapNode {
     pub fn new(text: impl Into<String>) -> Self {
         Self {
+            id: None,
             text: text.into(),
             shape: NodeShape::default(),
             children: Vec::new(),
@@ -24,6 +30,11 @@ impl MindmapNode {
         }
     }
 
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
     pub fn with_shape(mut self, shape: NodeShape) -> Self {
         self.shape = shape;
         self
@@ -54,7 +65,12 @@ impl MindmapNode {
         let spaces = "    ".repeat(indent);
 
         // Node with shape
-        let node_text = self.shape.wrap(&self.text);
+        let node_text = match &self.id {
+            Some(id) if self.shape != NodeShape::Default => {
+                format!("{}{}", id, self.shape.wrap(&self.text))
+            }
+            _ => self.shape.wrap(&self.text),
+        };
         output.push_str(&format!("{}{}\n", spaces, node_text));
 
         // Icon if present
@@ -77,6 +93,7 @@ impl MindmapNode {
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum NodeShape {
     #[default]
@@ -134,6 +151,21 @@ mod tests {
         assert!(mermaid.contains("[Test]"));
     }
 
+    #[test]
+    fn node_with_id() {
+        let node = MindmapNode::new("Urgent task")
+            .with_id("id1")
+            .with_shape(NodeShape::Square);
+        assert_eq!(node.to_mermaid(1), "    id1[Urgent task]\n");
+
+        let node = MindmapNode::new("Urgent task").with_shape(NodeShape::Square);
+        assert_eq!(node.to_mermaid(1), "    [Urgent task]\n");
+
+        // Plain nodes are their own text; the id is not emitted
+        let node = MindmapNode::new("Plain").with_id("id2");
+        assert_eq!(node.to_mermaid(1), "    Plain\n");
+    }
+
     #[test]
     fn node_with_children() {
         let node = MindmapNode::new("Root")
@@ -1,16 +1,24 @@
+pub mod any;
+pub mod detect;
 pub mod er;
 pub mod flowchart;
 pub mod journey;
 pub mod mindmap;
 pub mod pie;
 pub mod requirement;
+#[cfg(feature = "schema")]
+pub mod schema;
 pub mod sequence;
 pub mod state;
 
+pub use any::AnyDiagram;
+pub use detect::{detect_diagram_type, extract_mermaid_blocks, load_diagram, parse};
 pub use er::{
     Attribute, AttributeKey, AttributeType, Cardinality, ERDiagram, Entity, Relationship,
 };
-pub use flowchart::{FlowChart, HrefType, Link, LinkHead, LinkStyle, Node, NodeShape, Subgraph};
+pub use flowchart::{
+    FlowChart, FlowchartKeyword, HrefType, Link, LinkHead, LinkStyle, Node, NodeShape, Subgraph,
+};
 pub use journey::{Journey, Section, Task};
 pub use mindmap::{Mindmap, MindmapNode, MindmapNodeShape};
 pub use pie::PieChart;
@@ -19,7 +27,7 @@ pub use requirement::{
 };
 pub use sequence::{
     Logic, LogicType, Message, MessageType, Note, NotePosition, Participant, ParticipantBox,
-    ParticipantType, SequenceDiagram,
+    ParticipantLink, ParticipantType, SequenceDiagram, SequenceEvent,
 };
 pub use state::{
     Choice, CompositeState, ConcurrentState, Fork, Join, State, StateDiagram, StateType, Transition,
@@ -1,3 +1,3 @@
 mod piechart;
 
-pub use piechart::{PieChart, PieChartBuilder, PieData};
+pub use piechart::{format_pie_value, PieChart, PieChartBuilder, PieData, TitlePlacement};
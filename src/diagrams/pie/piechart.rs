@@ -1,8 +1,10 @@
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
-use crate::core::{Config, Diagram, FromConfig, MermaidError, Theme};
+use crate::core::{statement_count, Config, Diagram, FromConfig, MermaidError, Theme};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PieData {
     pub label: String,
     pub value: f64,
@@ -17,14 +19,64 @@ impl PieData {
     }
 }
 
+/// Format a slice value for mermaid: whole numbers print without a fraction,
+/// others are rounded to at most 4 decimals with trailing zeros trimmed.
+/// Non-zero values too small to survive the rounding print in full.
+pub fn format_pie_value(value: f64) -> String {
+    let formatted = format!("{:.4}", value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    match trimmed {
+        "0" | "-0" if value != 0.0 => value.to_string(),
+        "-0" => "0".to_string(),
+        _ => trimmed.to_string(),
+    }
+}
+
+/// Where a pie chart's title is rendered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum TitlePlacement {
+    /// On the pie line: `pie title Pets`
+    #[default]
+    Inline,
+    /// In a `---` frontmatter block ahead of the script, as mermaid-py does
+    Frontmatter,
+}
+
+/// Parse a `"label" : value` row
+fn parse_pie_row(line: &str) -> Result<PieData, MermaidError> {
+    let malformed = || MermaidError::ParseError(format!("Malformed pie row: {}", line));
+
+    let (label, value) = line.rsplit_once(':').ok_or_else(malformed)?;
+    let label = label
+        .trim()
+        .strip_prefix('"')
+        .and_then(|l| l.strip_suffix('"'))
+        .ok_or_else(malformed)?;
+    let value: f64 = value.trim().parse().map_err(|_| malformed())?;
+
+    Ok(PieData::new(label, value))
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PieChart {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
+    /// Render the title on the pie line (default) or in frontmatter
+    #[serde(default)]
+    pub title_placement: TitlePlacement,
     #[serde(default)]
     pub show_data: bool,
+    /// Append each slice's percentage as `%%` comments (mermaid has no legend primitive)
+    #[serde(default)]
+    pub legend: bool,
     #[serde(default, alias = "slices")]
     pub data: Vec<PieData>,
+    /// Slice colors in slice order, emitted as `pie1..pieN` theme variables
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub colors: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config: Option<Config>,
     /// Raw mermaid passthrough (if set, ignores other fields)
@@ -62,6 +114,108 @@ impl PieChart {
         let chart: Self = toml::from_str(toml)?;
         Ok(chart)
     }
+
+    /// Parse a mermaid pie script into a structured chart.
+    ///
+    /// Reads the `pie [showData] [title ...]` header, an optional `title ...`
+    /// line and `"label" : value` rows. Blank lines and `%%` comments are skipped.
+    pub fn parse_mermaid(script: &str) -> Result<Self, MermaidError> {
+        let mut lines = script
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with("%%"));
+
+        let header = lines
+            .next()
+            .and_then(|l| l.strip_prefix("pie"))
+            .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+            .ok_or_else(|| MermaidError::ParseError("Expected 'pie' header".to_string()))?;
+
+        let mut chart = Self::new();
+        let mut rest = header.trim();
+        if let Some(after) = rest.strip_prefix("showData") {
+            chart.show_data = true;
+            rest = after.trim();
+        }
+        if let Some(title) = rest.strip_prefix("title") {
+            chart.title = Some(title.trim().to_string());
+        } else if !rest.is_empty() {
+            return Err(MermaidError::ParseError(format!(
+                "Unexpected pie header: {}",
+                header.trim()
+            )));
+        }
+
+        for line in lines {
+            if let Some(title) = line.strip_prefix("title ") {
+                chart.title = Some(title.trim().to_string());
+                continue;
+            }
+            chart.data.push(parse_pie_row(line)?);
+        }
+
+        Ok(chart)
+    }
+
+    /// Check for slice values mermaid can't draw.
+    ///
+    /// Rejects negative or non-finite values and charts whose values sum to zero.
+    /// A chart without slices passes; [`Diagram::is_empty`] reports that case.
+    pub fn validate(&self) -> Result<(), MermaidError> {
+        for entry in &self.data {
+            if !entry.value.is_finite() {
+                return Err(MermaidError::InvalidInput(format!(
+                    "pie slice '{}' has non-finite value {}",
+                    entry.label, entry.value
+                )));
+            }
+            if entry.value < 0.0 {
+                return Err(MermaidError::InvalidInput(format!(
+                    "pie slice '{}' has negative value {}",
+                    entry.label,
+                    format_pie_value(entry.value)
+                )));
+            }
+        }
+        if !self.data.is_empty() && self.data.iter().map(|entry| entry.value).sum::<f64>() == 0.0 {
+            return Err(MermaidError::InvalidInput(
+                "pie chart values sum to zero".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Each slice's share of the total, in percent, in slice order.
+    ///
+    /// Every share is `0.0` when the values sum to zero.
+    pub fn percentages(&self) -> Vec<(String, f64)> {
+        let total: f64 = self.data.iter().map(|entry| entry.value).sum();
+        self.data
+            .iter()
+            .map(|entry| {
+                let share = if total == 0.0 {
+                    0.0
+                } else {
+                    entry.value / total * 100.0
+                };
+                (entry.label.clone(), share)
+            })
+            .collect()
+    }
+
+    /// The config with `colors` merged into its pie theme variables
+    fn effective_config(&self) -> Option<Cow<'_, Config>> {
+        if self.colors.is_empty() {
+            return self.config.as_ref().map(Cow::Borrowed);
+        }
+
+        let mut config = self.config.clone().unwrap_or_default();
+        config
+            .theme_variables
+            .get_or_insert_with(Default::default)
+            .pie_colors = self.colors.clone();
+        Some(Cow::Owned(config))
+    }
 }
 
 impl Diagram for PieChart {
@@ -83,14 +237,26 @@ impl Diagram for PieChart {
 
         // Add title inline (mermaid syntax: pie title "Title")
         if let Some(title) = &self.title {
-            output.push_str(&format!(" title {}", title));
+            if self.title_placement == TitlePlacement::Inline {
+                output.push_str(&format!(" title {}", title));
+            }
         }
 
         output.push('\n');
 
         // Add data entries
         for entry in &self.data {
-            output.push_str(&format!("\t\"{}\" : {}\n", entry.label, entry.value));
+            output.push_str(&format!(
+                "\t\"{}\" : {}\n",
+                entry.label,
+                format_pie_value(entry.value)
+            ));
+        }
+
+        if self.legend {
+            for (label, share) in self.percentages() {
+                output.push_str(&format!("\t%% {}: {:.1}%\n", label, share));
+            }
         }
 
         output
@@ -107,6 +273,25 @@ impl Diagram for PieChart {
     fn config(&self) -> Option<&Config> {
         self.config.as_ref()
     }
+
+    fn is_empty(&self) -> bool {
+        if let Some(raw) = &self.raw_mermaid {
+            return statement_count(raw) == 0;
+        }
+        self.data.is_empty()
+    }
+
+    fn frontmatter_title(&self) -> Option<&str> {
+        match self.title_placement {
+            TitlePlacement::Frontmatter => self.title.as_deref(),
+            TitlePlacement::Inline => None,
+        }
+    }
+
+    fn script_config(&self) -> Option<Cow<'_, Config>> {
+        // Slice colors live outside `config`, so merge them into the embedded config
+        self.effective_config()
+    }
 }
 
 impl FromConfig for PieChart {
@@ -126,8 +311,11 @@ impl FromConfig for PieChart {
 #[derive(Debug, Default)]
 pub struct PieChartBuilder {
     title: Option<String>,
+    title_placement: TitlePlacement,
     show_data: bool,
+    legend: bool,
     data: Vec<PieData>,
+    colors: Vec<String>,
     config: Option<Config>,
 }
 
@@ -141,11 +329,22 @@ impl PieChartBuilder {
         self
     }
 
+    pub fn title_placement(mut self, placement: TitlePlacement) -> Self {
+        self.title_placement = placement;
+        self
+    }
+
     pub fn show_data(mut self, show: bool) -> Self {
         self.show_data = show;
         self
     }
 
+    /// Append slice percentages as `%%` comments
+    pub fn legend(mut self, legend: bool) -> Self {
+        self.legend = legend;
+        self
+    }
+
     pub fn data(mut self, label: impl Into<String>, value: f64) -> Self {
         self.data.push(PieData::new(label, value));
         self
@@ -156,6 +355,12 @@ impl PieChartBuilder {
         self
     }
 
+    /// Add a slice color; colors apply to slices in order
+    pub fn color(mut self, color: impl Into<String>) -> Self {
+        self.colors.push(color.into());
+        self
+    }
+
     pub fn theme(mut self, theme: Theme) -> Self {
         let config = self.config.get_or_insert_with(Config::default);
         config.theme = theme;
@@ -170,8 +375,11 @@ impl PieChartBuilder {
     pub fn build(self) -> PieChart {
         PieChart {
             title: self.title,
+            title_placement: self.title_placement,
             show_data: self.show_data,
+            legend: self.legend,
             data: self.data,
+            colors: self.colors,
             config: self.config,
             raw_mermaid: None,
         }
@@ -181,6 +389,7 @@ impl PieChartBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::ScriptStyle;
 
     #[test]
     fn pie_chart_basic() {
@@ -198,6 +407,74 @@ mod tests {
         assert!(mermaid.contains("\"Firefox\" : 20"));
     }
 
+    #[test]
+    fn pie_validate() {
+        let chart = PieChart::builder()
+            .data("Dogs", 3.0)
+            .data("Cats", 0.0)
+            .build();
+        assert!(chart.validate().is_ok());
+
+        let chart = PieChart::builder()
+            .data("Dogs", 3.0)
+            .data("Debt", -2.0)
+            .build();
+        match chart.validate() {
+            Err(MermaidError::InvalidInput(msg)) => {
+                assert_eq!(msg, "pie slice 'Debt' has negative value -2")
+            }
+            other => panic!("expected InvalidInput, got {:?}", other),
+        }
+
+        let chart = PieChart::builder().data("A", 0.0).data("B", 0.0).build();
+        assert!(matches!(
+            chart.validate(),
+            Err(MermaidError::InvalidInput(_))
+        ));
+
+        let chart = PieChart::builder().data("A", f64::NAN).build();
+        assert!(chart.validate().is_err());
+
+        assert!(PieChart::builder().build().validate().is_ok());
+    }
+
+    #[test]
+    fn pie_percentages() {
+        let chart = PieChart::builder()
+            .data("Dogs", 3.0)
+            .data("Cats", 1.0)
+            .data("Fish", 0.5)
+            .build();
+
+        let shares = chart.percentages();
+        assert_eq!(shares[0].0, "Dogs");
+        assert!((shares[0].1 - 66.666).abs() < 0.01);
+        let sum: f64 = shares.iter().map(|(_, share)| share).sum();
+        assert!((sum - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn pie_percentages_zero_total() {
+        let chart = PieChart::builder().data("A", 0.0).data("B", 0.0).build();
+        assert_eq!(
+            chart.percentages(),
+            vec![("A".to_string(), 0.0), ("B".to_string(), 0.0)]
+        );
+        assert!(PieChart::new().percentages().is_empty());
+    }
+
+    #[test]
+    fn pie_legend_comments() {
+        let chart = PieChart::builder()
+            .legend(true)
+            .data("Dogs", 3.0)
+            .data("Cats", 1.0)
+            .build();
+
+        let mermaid = chart.to_mermaid();
+        assert!(mermaid.ends_with("\t%% Dogs: 75.0%\n\t%% Cats: 25.0%\n"));
+    }
+
     #[test]
     fn pie_chart_show_data() {
         let chart = PieChart::builder()
@@ -286,4 +563,143 @@ slices:
         assert!(script.contains("%%{init:"));
         assert!(script.contains("'theme': 'dark'"));
     }
+
+    #[test]
+    fn pie_chart_colors_in_init_directive() {
+        let chart = PieChart::builder()
+            .title("Pets")
+            .data("Dogs", 60.0)
+            .data("Cats", 40.0)
+            .color("#ff6384")
+            .color("#36a2eb")
+            .build();
+
+        let script = chart.build_script();
+        assert!(script.contains("'pie1': '#ff6384'"));
+        assert!(script.contains("'pie2': '#36a2eb'"));
+        assert!(script.ends_with(&chart.to_mermaid()));
+    }
+
+    #[test]
+    fn pie_chart_colors_keep_theme() {
+        let chart = PieChart::builder()
+            .theme(Theme::Dark)
+            .color("#000000")
+            .data("A", 1.0)
+            .build();
+
+        let script = chart.build_script();
+        assert!(script
+            .starts_with("%%{init: {'theme': 'dark', 'themeVariables': {'pie1': '#000000'}}}%%\n"));
+        // The chart's own config is left untouched
+        assert!(chart.config.unwrap().theme_variables.is_none());
+    }
+
+    #[test]
+    fn pie_value_formatting() {
+        assert_eq!(format_pie_value(65.0), "65");
+        assert_eq!(format_pie_value(65.5), "65.5");
+        assert_eq!(format_pie_value(65.25000001), "65.25");
+        assert_eq!(format_pie_value(1.0 / 3.0), "0.3333");
+        assert_eq!(format_pie_value(0.00001), "0.00001");
+        assert_eq!(format_pie_value(-0.00001), "-0.00001");
+        assert_eq!(format_pie_value(-0.0), "0");
+    }
+
+    #[test]
+    fn pie_title_inline() {
+        let chart = PieChart::builder()
+            .title("Pets")
+            .title_placement(TitlePlacement::Inline)
+            .data("Dogs", 10.0)
+            .build();
+
+        assert_eq!(chart.build_script(), "pie title Pets\n\t\"Dogs\" : 10\n");
+    }
+
+    #[test]
+    fn pie_title_frontmatter() {
+        let chart = PieChart::builder()
+            .title("Pets")
+            .title_placement(TitlePlacement::Frontmatter)
+            .show_data(true)
+            .data("Dogs", 10.0)
+            .theme(Theme::Dark)
+            .build();
+
+        assert_eq!(chart.to_mermaid(), "pie showData\n\t\"Dogs\" : 10\n");
+        let script = chart.build_script();
+        assert!(script.starts_with("---\ntitle: Pets\n---\n%%{init:"));
+        assert!(script.ends_with("pie showData\n\t\"Dogs\" : 10\n"));
+    }
+
+    #[test]
+    fn pie_title_placement_from_yaml() {
+        let yaml =
+            "title: Pets\ntitle_placement: frontmatter\ndata:\n  - label: Dogs\n    value: 10\n";
+        let chart = PieChart::from_yaml(yaml).unwrap();
+        assert_eq!(chart.title_placement, TitlePlacement::Frontmatter);
+        assert!(chart
+            .build_script()
+            .starts_with("---\ntitle: Pets\n---\npie\n"));
+    }
+
+    #[test]
+    fn pie_parse_mermaid() {
+        let script =
+            "pie showData\n    title Browser Share\n\t\"Chrome\" : 65.5\n    \"Firefox\" : 20\n";
+        let chart = PieChart::parse_mermaid(script).unwrap();
+
+        assert!(chart.show_data);
+        assert_eq!(chart.title.as_deref(), Some("Browser Share"));
+        assert_eq!(chart.data.len(), 2);
+        assert_eq!(chart.data[0].label, "Chrome");
+        assert_eq!(chart.data[0].value, 65.5);
+        assert_eq!(chart.data[1].label, "Firefox");
+        assert_eq!(chart.data[1].value, 20.0);
+    }
+
+    #[test]
+    fn pie_parse_mermaid_round_trip() {
+        let chart = PieChart::builder()
+            .title("Pets")
+            .show_data(true)
+            .data("Dogs", 10.0)
+            .data("Cats: indoor", 2.25)
+            .build();
+
+        let parsed = PieChart::parse_mermaid(&chart.to_mermaid()).unwrap();
+        assert_eq!(parsed.to_mermaid(), chart.to_mermaid());
+    }
+
+    #[test]
+    fn pie_parse_mermaid_malformed() {
+        assert!(matches!(
+            PieChart::parse_mermaid("pie\n    Dogs : 10"),
+            Err(MermaidError::ParseError(_))
+        ));
+        assert!(matches!(
+            PieChart::parse_mermaid("pie\n    \"Dogs\" : many"),
+            Err(MermaidError::ParseError(_))
+        ));
+        assert!(PieChart::parse_mermaid("graph TD").is_err());
+    }
+
+    #[test]
+    fn pie_build_script_frontmatter() {
+        let diagram = PieChart::builder()
+            .title("Orders")
+            .title_placement(TitlePlacement::Frontmatter)
+            .theme(Theme::Dark)
+            .data("A", 1.0)
+            .build();
+        let script = diagram.build_script_with(ScriptStyle::Frontmatter);
+        assert!(script.starts_with("---\ntitle: Orders\nconfig:\n  theme: dark\n---\npie\n"));
+    }
+
+    #[test]
+    fn pie_is_empty() {
+        assert!(PieChart::builder().title("Nothing").build().is_empty());
+        assert!(!PieChart::builder().data("A", 1.0).build().is_empty());
+    }
 }
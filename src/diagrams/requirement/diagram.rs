@@ -1,19 +1,29 @@
 use serde::{Deserialize, Serialize};
 
-use crate::core::{Config, Diagram, MermaidError};
+use crate::core::{
+    statement_count, Config, Diagram, Direction, FormatOptions, FromConfig, MermaidError, Theme,
+};
 
 use super::{Element, ReqRelationship, Requirement, Risk, VerifyMethod};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct RequirementDiagram {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
+    /// Layout direction, emitted as `direction LR` after the header
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direction: Option<Direction>,
     #[serde(default)]
     pub requirements: Vec<Requirement>,
     #[serde(default)]
     pub elements: Vec<Element>,
     #[serde(default)]
     pub relationships: Vec<ReqRelationship>,
+    /// Emit requirements by id, elements by name and relationships by endpoints,
+    /// instead of insertion order, for diff-stable output
+    #[serde(default)]
+    pub sort: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config: Option<Config>,
     #[serde(skip)]
@@ -28,9 +38,11 @@ impl RequirementDiagram {
     pub fn from_raw_mermaid(script: String) -> Self {
         Self {
             title: None,
+            direction: None,
             requirements: Vec::new(),
             elements: Vec::new(),
             relationships: Vec::new(),
+            sort: false,
             config: None,
             raw_mermaid: Some(script),
         }
@@ -54,6 +66,17 @@ impl Diagram for RequirementDiagram {
         "requirementDiagram"
     }
 
+    fn to_mermaid_with(&self, options: &FormatOptions) -> String {
+        if options.sort_stable && !self.sort {
+            let sorted = Self {
+                sort: true,
+                ..self.clone()
+            };
+            return options.apply(&sorted.to_mermaid());
+        }
+        options.apply(&self.to_mermaid())
+    }
+
     fn title(&self) -> Option<&str> {
         self.title.as_deref()
     }
@@ -62,25 +85,48 @@ impl Diagram for RequirementDiagram {
         self.config.as_ref()
     }
 
+    fn is_empty(&self) -> bool {
+        if let Some(raw) = &self.raw_mermaid {
+            return statement_count(raw) == 0;
+        }
+        self.requirements.is_empty() && self.elements.is_empty() && self.relationships.is_empty()
+    }
+
+    fn frontmatter_title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
     fn to_mermaid(&self) -> String {
         if let Some(raw) = &self.raw_mermaid {
             return raw.clone();
         }
 
         let mut output = String::from("requirementDiagram\n");
+        if let Some(direction) = &self.direction {
+            output.push_str(&format!("    direction {}\n", direction));
+        }
+
+        let mut requirements: Vec<&Requirement> = self.requirements.iter().collect();
+        let mut elements: Vec<&Element> = self.elements.iter().collect();
+        let mut relationships: Vec<&ReqRelationship> = self.relationships.iter().collect();
+        if self.sort {
+            requirements.sort_by(|a, b| (&a.id, &a.name).cmp(&(&b.id, &b.name)));
+            elements.sort_by(|a, b| (&a.name, &a.id).cmp(&(&b.name, &b.id)));
+            relationships.sort_by(|a, b| (&a.from, &a.to).cmp(&(&b.from, &b.to)));
+        }
 
         // Requirements
-        for req in &self.requirements {
+        for req in requirements {
             output.push_str(&req.to_mermaid());
         }
 
         // Elements
-        for elem in &self.elements {
+        for elem in elements {
             output.push_str(&elem.to_mermaid());
         }
 
         // Relationships
-        for rel in &self.relationships {
+        for rel in relationships {
             output.push_str(&rel.to_mermaid());
         }
 
@@ -88,12 +134,29 @@ impl Diagram for RequirementDiagram {
     }
 }
 
+impl FromConfig for RequirementDiagram {
+    fn from_json(json: &str) -> Result<Self, MermaidError> {
+        Self::from_json(json)
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Self, MermaidError> {
+        Self::from_yaml(yaml)
+    }
+
+    fn from_toml(toml: &str) -> Result<Self, MermaidError> {
+        Self::from_toml(toml)
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct RequirementDiagramBuilder {
     title: Option<String>,
+    direction: Option<Direction>,
     requirements: Vec<Requirement>,
     elements: Vec<Element>,
     relationships: Vec<ReqRelationship>,
+    sort: bool,
+    config: Option<Config>,
 }
 
 impl RequirementDiagramBuilder {
@@ -106,6 +169,11 @@ impl RequirementDiagramBuilder {
         self
     }
 
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
     pub fn requirement(mut self, req: Requirement) -> Self {
         self.requirements.push(req);
         self
@@ -176,13 +244,32 @@ impl RequirementDiagramBuilder {
         self
     }
 
+    /// Order output by id instead of insertion order
+    pub fn sort(mut self, sort: bool) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        let config = self.config.get_or_insert_with(Config::default);
+        config.theme = theme;
+        self
+    }
+
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = Some(config);
+        self
+    }
+
     pub fn build(self) -> RequirementDiagram {
         RequirementDiagram {
             title: self.title,
+            direction: self.direction,
             requirements: self.requirements,
             elements: self.elements,
             relationships: self.relationships,
-            config: None,
+            sort: self.sort,
+            config: self.config,
             raw_mermaid: None,
         }
     }
@@ -191,6 +278,48 @@ impl RequirementDiagramBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::ScriptStyle;
+
+    #[test]
+    fn requirement_diagram_direction_and_theme() {
+        let diagram = RequirementDiagram::builder()
+            .direction(Direction::LeftRight)
+            .theme(Theme::Dark)
+            .requirement_simple("R1", "req", None)
+            .build();
+
+        let mermaid = diagram.to_mermaid();
+        assert!(mermaid.starts_with("requirementDiagram\n    direction LR\n"));
+
+        let script = diagram.build_script();
+        assert!(script.starts_with("%%{init:"));
+        assert!(script.contains("'theme': 'dark'"));
+    }
+
+    #[test]
+    fn requirement_diagram_sorted_output() {
+        let build = |sort: bool| {
+            RequirementDiagram::builder()
+                .sort(sort)
+                .requirement_simple("REQ-002", "Logout", None)
+                .requirement_simple("REQ-001", "Login", None)
+                .element_simple("Web", "Web UI")
+                .element_simple("Api", "API")
+                .satisfies("Web", "Logout")
+                .satisfies("Api", "Login")
+                .build()
+                .to_mermaid()
+        };
+
+        let sorted = build(true);
+        let pos = |needle: &str| sorted.find(needle).unwrap();
+        assert!(pos("REQ-001") < pos("REQ-002"));
+        assert!(pos("element API") < pos("element Web UI"));
+        assert!(pos("Api - satisfies -> Login") < pos("Web - satisfies -> Logout"));
+
+        let unsorted = build(false);
+        assert!(unsorted.find("REQ-002").unwrap() < unsorted.find("REQ-001").unwrap());
+    }
 
     #[test]
     fn requirement_diagram_basic() {
@@ -245,4 +374,16 @@ elements:
         let diagram = RequirementDiagram::from_raw_mermaid(raw.to_string());
         assert_eq!(diagram.to_mermaid(), raw);
     }
+
+    #[test]
+    fn requirement_build_script_frontmatter() {
+        let diagram = RequirementDiagram::builder()
+            .title("Orders")
+            .theme(Theme::Dark)
+            .requirement_simple("R1", "req", None)
+            .build();
+        let script = diagram.build_script_with(ScriptStyle::Frontmatter);
+        assert!(script
+            .starts_with("---\ntitle: Orders\nconfig:\n  theme: dark\n---\nrequirementDiagram\n"));
+    }
 }
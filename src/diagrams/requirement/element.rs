@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use super::{Risk, VerifyMethod};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Requirement {
     pub id: String,
     pub name: String,
@@ -63,6 +64,7 @@ impl Requirement {
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum RequirementType {
     #[default]
@@ -88,6 +90,7 @@ impl std::fmt::Display for RequirementType {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Element {
     pub id: String,
     pub name: String,
@@ -130,6 +133,7 @@ impl Element {
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum ElementType {
     #[default]
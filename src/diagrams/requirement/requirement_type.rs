@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum Risk {
     #[default]
@@ -20,17 +21,25 @@ impl std::fmt::Display for Risk {
 }
 
 impl Risk {
+    /// Accepted spec tokens, canonical name first for each risk
+    pub const ALIASES: &'static [(&'static str, Self)] = &[
+        ("low", Self::Low),
+        ("medium", Self::Medium),
+        ("med", Self::Medium),
+        ("high", Self::High),
+    ];
+
     pub fn parse(s: &str) -> Option<Self> {
-        match s.to_lowercase().as_str() {
-            "low" => Some(Self::Low),
-            "medium" | "med" => Some(Self::Medium),
-            "high" => Some(Self::High),
-            _ => None,
-        }
+        let s = s.to_lowercase();
+        Self::ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == s)
+            .map(|(_, value)| *value)
     }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum VerifyMethod {
     #[default]
@@ -64,11 +73,15 @@ impl VerifyMethod {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Relationship {
     pub from: String,
     pub to: String,
     #[serde(rename = "type")]
     pub rel_type: RelationshipType,
+    /// Point the arrow back at `from` (`from <- derives - to`)
+    #[serde(default)]
+    pub reversed: bool,
 }
 
 impl Relationship {
@@ -77,9 +90,15 @@ impl Relationship {
             from: from.into(),
             to: to.into(),
             rel_type,
+            reversed: false,
         }
     }
 
+    pub fn with_reversed(mut self) -> Self {
+        self.reversed = true;
+        self
+    }
+
     pub fn contains(source: impl Into<String>, target: impl Into<String>) -> Self {
         Self::new(source, target, RelationshipType::Contains)
     }
@@ -109,12 +128,17 @@ impl Relationship {
     }
 
     pub fn to_mermaid(&self) -> String {
-        let arrow = self.rel_type.arrow();
+        let arrow = if self.reversed {
+            self.rel_type.reversed_arrow()
+        } else {
+            self.rel_type.arrow().to_string()
+        };
         format!("    {} {} {}\n", self.from, arrow, self.to)
     }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum RelationshipType {
     #[default]
@@ -140,6 +164,15 @@ impl RelationshipType {
         }
     }
 
+    /// The arrow pointing right-to-left, e.g. `<- derives -`
+    pub fn reversed_arrow(&self) -> String {
+        let name = self
+            .arrow()
+            .trim_start_matches("- ")
+            .trim_end_matches(" ->");
+        format!("<- {} -", name)
+    }
+
     pub fn parse(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "contains" => Some(Self::Contains),
@@ -187,4 +220,19 @@ mod tests {
         );
         assert_eq!(RelationshipType::parse("invalid"), None);
     }
+
+    #[test]
+    fn relationship_reversed_arrow() {
+        assert_eq!(RelationshipType::Derives.reversed_arrow(), "<- derives -");
+        assert_eq!(
+            Relationship::traces("spec", "impl").to_mermaid(),
+            "    spec - traces -> impl\n"
+        );
+        assert_eq!(
+            Relationship::derives("child", "parent")
+                .with_reversed()
+                .to_mermaid(),
+            "    child <- derives - parent\n"
+        );
+    }
 }
@@ -0,0 +1,60 @@
+use schemars::{schema_for, Schema};
+
+use crate::core::DiagramKind;
+
+use super::{
+    ERDiagram, FlowChart, Journey, Mindmap, PieChart, RequirementDiagram, SequenceDiagram,
+    StateDiagram,
+};
+
+/// JSON Schema for the JSON/YAML/TOML spec of a diagram kind
+pub fn diagram_schema(kind: DiagramKind) -> Schema {
+    match kind {
+        DiagramKind::Flowchart => schema_for!(FlowChart),
+        DiagramKind::Sequence => schema_for!(SequenceDiagram),
+        DiagramKind::State => schema_for!(StateDiagram),
+        DiagramKind::ER => schema_for!(ERDiagram),
+        DiagramKind::Pie => schema_for!(PieChart),
+        DiagramKind::Journey => schema_for!(Journey),
+        DiagramKind::Mindmap => schema_for!(Mindmap),
+        DiagramKind::Requirement => schema_for!(RequirementDiagram),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pie_schema_properties() {
+        let schema = diagram_schema(DiagramKind::Pie);
+        let properties = schema
+            .get("properties")
+            .and_then(|p| p.as_object())
+            .unwrap();
+        assert!(properties.contains_key("data"));
+        assert!(properties.contains_key("show_data"));
+        assert!(!properties.contains_key("raw_mermaid"));
+    }
+
+    #[test]
+    fn sequence_schema_accepts_grouped_keys() {
+        let schema = diagram_schema(DiagramKind::Sequence);
+        let properties = schema
+            .get("properties")
+            .and_then(|p| p.as_object())
+            .unwrap();
+        for key in ["messages", "notes", "logic", "events"] {
+            assert!(properties.contains_key(key), "missing {}", key);
+        }
+        let required = schema.get("required").and_then(|r| r.as_array());
+        assert!(required.is_none_or(|r| r.is_empty()));
+    }
+
+    #[test]
+    fn every_kind_has_a_schema() {
+        for kind in DiagramKind::ALL {
+            assert!(diagram_schema(kind).get("properties").is_some());
+        }
+    }
+}
@@ -1,10 +1,22 @@
-use serde::{Deserialize, Serialize};
-
-use crate::core::{Config, Diagram, FromConfig, MermaidError, Theme};
-
-use super::{Logic, Message, MessageType, Note, NotePosition, Participant, ParticipantBox};
-
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+use serde::de::{IgnoredAny, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+
+use crate::core::{
+    statement_count, Config, Diagram, FormatOptions, FromConfig, MermaidError, Theme,
+};
+
+use super::{
+    Logic, Message, MessageType, Note, NotePosition, Participant, ParticipantBox, SequenceEvent,
+};
+
+/// A sequence diagram.
+///
+/// Messages, notes and logic blocks live in one `events` stream and render in
+/// authored order, so a note can sit between two messages. Config files may
+/// still use the grouped `messages`, `notes` and `logic` keys; they are merged
+/// into `events` in the order the keys are declared.
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct SequenceDiagram {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
@@ -12,14 +24,15 @@ pub struct SequenceDiagram {
     pub autonumber: bool,
     #[serde(default)]
     pub participants: Vec<Participant>,
+    /// Participant groups; each renders where its first member is declared
     #[serde(default)]
     pub boxes: Vec<ParticipantBox>,
-    #[serde(default)]
-    pub messages: Vec<Message>,
-    #[serde(default)]
-    pub notes: Vec<Note>,
-    #[serde(default)]
-    pub logic: Vec<Logic>,
+    /// Messages, notes and logic blocks in authored order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<SequenceEvent>,
+    /// Comments emitted as `%%` lines after the diagram-type line
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config: Option<Config>,
     /// Raw mermaid passthrough (if set, ignores other fields)
@@ -43,6 +56,33 @@ impl SequenceDiagram {
         }
     }
 
+    /// Build a diagram from `(from, to, method, path)` service calls.
+    ///
+    /// Each service is declared once, in order of first appearance. Every call
+    /// becomes a `from->>+to: METHOD path` request answered by
+    /// `to-->>-from: response` (mermaid requires text on every message).
+    pub fn from_calls(calls: &[(&str, &str, &str, &str)]) -> Self {
+        let mut builder = Self::builder();
+
+        let mut seen: Vec<&str> = Vec::new();
+        for &(from, to, _, _) in calls {
+            for id in [from, to] {
+                if !seen.contains(&id) {
+                    seen.push(id);
+                    builder = builder.participant_simple(id);
+                }
+            }
+        }
+
+        for &(from, to, method, path) in calls {
+            builder = builder
+                .call(from, to, format!("{} {}", method, path))
+                .return_msg(to, from, "response");
+        }
+
+        builder.build()
+    }
+
     pub fn from_json(json: &str) -> Result<Self, MermaidError> {
         let diagram: Self = serde_json::from_str(json)?;
         Ok(diagram)
@@ -58,6 +98,60 @@ impl SequenceDiagram {
         Ok(diagram)
     }
 
+    /// Top-level messages, in authored order (excludes messages inside logic blocks)
+    pub fn messages(&self) -> impl Iterator<Item = &Message> {
+        self.events.iter().filter_map(|event| match event {
+            SequenceEvent::Message(message) => Some(message),
+            _ => None,
+        })
+    }
+
+    /// Notes, in authored order
+    pub fn notes(&self) -> impl Iterator<Item = &Note> {
+        self.events.iter().filter_map(|event| match event {
+            SequenceEvent::Note(note) => Some(note),
+            _ => None,
+        })
+    }
+
+    /// Logic blocks (loop, alt, opt, ...), in authored order
+    pub fn logic(&self) -> impl Iterator<Item = &Logic> {
+        self.events.iter().filter_map(|event| match event {
+            SequenceEvent::Logic(block) => Some(block),
+            _ => None,
+        })
+    }
+
+    /// Messages paired with the number `autonumber` assigns them, in render order.
+    ///
+    /// Messages inside logic blocks are numbered too, as mermaid does.
+    /// Returns an empty list when autonumber is off.
+    pub fn numbered_messages(&self) -> Vec<(u32, &Message)> {
+        if !self.autonumber {
+            return Vec::new();
+        }
+
+        fn logic_messages(block: &Logic) -> impl Iterator<Item = &Message> {
+            block
+                .messages
+                .iter()
+                .chain(block.else_blocks.iter().flat_map(|e| e.messages.iter()))
+        }
+
+        self.events
+            .iter()
+            .flat_map(|event| -> Vec<&Message> {
+                match event {
+                    SequenceEvent::Message(message) => vec![message],
+                    SequenceEvent::Logic(block) => logic_messages(block).collect(),
+                    SequenceEvent::Note(_) => Vec::new(),
+                }
+            })
+            .enumerate()
+            .map(|(i, msg)| (i as u32 + 1, msg))
+            .collect()
+    }
+
     /// Find participants that belong to a specific box
     fn participants_in_box(&self, box_def: &ParticipantBox) -> Vec<&Participant> {
         self.participants
@@ -65,9 +159,28 @@ impl SequenceDiagram {
             .filter(|p| box_def.members.contains(&p.id))
             .collect()
     }
+
+    fn render_box(&self, box_def: &ParticipantBox, output: &mut String) {
+        output.push_str(&format!("    {}", box_def.to_mermaid_start()));
+        for participant in self.participants_in_box(box_def) {
+            output.push_str(&format!("        {}\n", participant.to_mermaid()));
+        }
+        output.push_str(&format!("    {}\n", box_def.to_mermaid_end()));
+    }
 }
 
 impl Diagram for SequenceDiagram {
+    /// With `sort_stable`, participants (and so lifelines and boxes) are ordered
+    /// by id; events keep their authored order since it carries meaning.
+    fn to_mermaid_with(&self, options: &FormatOptions) -> String {
+        if !options.sort_stable || self.raw_mermaid.is_some() {
+            return options.apply(&self.to_mermaid());
+        }
+        let mut sorted = self.clone();
+        sorted.participants.sort_by(|a, b| a.id.cmp(&b.id));
+        options.apply(&sorted.to_mermaid())
+    }
+
     fn to_mermaid(&self) -> String {
         // If raw mermaid was provided, return it directly
         if let Some(raw) = &self.raw_mermaid {
@@ -84,44 +197,41 @@ impl Diagram for SequenceDiagram {
             output.push_str("    autonumber\n");
         }
 
-        // Collect participants that are in boxes
-        let mut participants_in_boxes: Vec<String> = Vec::new();
-        for box_def in &self.boxes {
-            participants_in_boxes.extend(box_def.members.clone());
-        }
-
-        // Render participants not in any box
+        // Declare participants in authored order; a box is emitted, with all
+        // its members, where its first member was declared
+        let mut rendered_boxes = vec![false; self.boxes.len()];
         for participant in &self.participants {
-            if !participants_in_boxes.contains(&participant.id) {
-                output.push_str(&format!("    {}\n", participant.to_mermaid()));
+            match self
+                .boxes
+                .iter()
+                .position(|b| b.members.contains(&participant.id))
+            {
+                Some(i) if rendered_boxes[i] => {}
+                Some(i) => {
+                    rendered_boxes[i] = true;
+                    self.render_box(&self.boxes[i], &mut output);
+                }
+                None => output.push_str(&format!("    {}\n", participant.to_mermaid())),
             }
         }
 
-        // Render boxes with their participants
-        for box_def in &self.boxes {
-            output.push_str(&format!("    {}", box_def.to_mermaid_start()));
-            for participant in self.participants_in_box(box_def) {
-                output.push_str(&format!("        {}\n", participant.to_mermaid()));
+        // Boxes with no declared members go last
+        for (box_def, rendered) in self.boxes.iter().zip(rendered_boxes) {
+            if !rendered {
+                self.render_box(box_def, &mut output);
             }
-            output.push_str(&format!("    {}\n", box_def.to_mermaid_end()));
-        }
-
-        // Render messages
-        for message in &self.messages {
-            output.push_str(&format!("    {}\n", message.to_mermaid()));
         }
 
-        // Render notes
-        for note in &self.notes {
-            output.push_str(&format!("    {}\n", note.to_mermaid()));
+        // Render participant menu links
+        for participant in &self.participants {
+            if let Some(links) = participant.links_to_mermaid() {
+                output.push_str(&format!("    {}\n", links));
+            }
         }
 
-        // Render logic blocks
-        for logic_block in &self.logic {
-            // Indent logic block output
-            for line in logic_block.to_mermaid().lines() {
-                output.push_str(&format!("    {}\n", line));
-            }
+        // Render messages, notes and logic blocks in authored order
+        for event in &self.events {
+            output.push_str(&event.to_mermaid());
         }
 
         output
@@ -138,6 +248,140 @@ impl Diagram for SequenceDiagram {
     fn config(&self) -> Option<&Config> {
         self.config.as_ref()
     }
+
+    fn is_empty(&self) -> bool {
+        if let Some(raw) = &self.raw_mermaid {
+            return statement_count(raw) == 0;
+        }
+        self.participants.is_empty() && self.events.is_empty()
+    }
+
+    fn frontmatter_title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    fn comments(&self) -> &[String] {
+        &self.comments
+    }
+
+    fn estimated_complexity(&self) -> usize {
+        if let Some(raw) = &self.raw_mermaid {
+            return statement_count(raw);
+        }
+        let events: usize = self
+            .events
+            .iter()
+            .map(|event| match event {
+                SequenceEvent::Logic(logic) => {
+                    1 + logic.messages.len()
+                        + logic
+                            .else_blocks
+                            .iter()
+                            .map(|block| 1 + block.messages.len())
+                            .sum::<usize>()
+                }
+                _ => 1,
+            })
+            .sum();
+        self.participants.len() + events
+    }
+}
+
+impl<'de> Deserialize<'de> for SequenceDiagram {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(SequenceDiagramVisitor)
+    }
+}
+
+/// Reads the diagram key by key so the grouped `messages`, `notes` and `logic`
+/// lists (and `events`) are appended to the timeline in declared order.
+struct SequenceDiagramVisitor;
+
+impl<'de> Visitor<'de> for SequenceDiagramVisitor {
+    type Value = SequenceDiagram;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a sequence diagram")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut diagram = SequenceDiagram::default();
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "title" => diagram.title = map.next_value()?,
+                "autonumber" => diagram.autonumber = map.next_value()?,
+                "participants" => diagram.participants = map.next_value()?,
+                "boxes" => diagram.boxes = map.next_value()?,
+                "messages" => diagram.events.extend(
+                    map.next_value::<Vec<Message>>()?
+                        .into_iter()
+                        .map(SequenceEvent::from),
+                ),
+                "notes" => diagram.events.extend(
+                    map.next_value::<Vec<Note>>()?
+                        .into_iter()
+                        .map(SequenceEvent::from),
+                ),
+                "logic" => diagram.events.extend(
+                    map.next_value::<Vec<Logic>>()?
+                        .into_iter()
+                        .map(SequenceEvent::from),
+                ),
+                "events" => diagram
+                    .events
+                    .extend(map.next_value::<Vec<SequenceEvent>>()?),
+                "comments" => diagram.comments = map.next_value()?,
+                "config" => diagram.config = map.next_value()?,
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(diagram)
+    }
+}
+
+/// The keys [`SequenceDiagramVisitor`] accepts, described for the JSON schema
+#[cfg(feature = "schema")]
+#[derive(schemars::JsonSchema)]
+#[schemars(rename = "SequenceDiagram")]
+#[allow(dead_code)]
+struct SequenceDiagramSpec {
+    title: Option<String>,
+    #[serde(default)]
+    autonumber: bool,
+    #[serde(default)]
+    participants: Vec<Participant>,
+    /// Participant groups; each renders where its first member is declared
+    #[serde(default)]
+    boxes: Vec<ParticipantBox>,
+    /// Messages, appended to the timeline in key order
+    #[serde(default)]
+    messages: Vec<Message>,
+    /// Notes, appended to the timeline in key order
+    #[serde(default)]
+    notes: Vec<Note>,
+    /// Logic blocks (loop, alt, opt, ...), appended to the timeline in key order
+    #[serde(default)]
+    logic: Vec<Logic>,
+    /// Messages, notes and logic blocks in authored order
+    #[serde(default)]
+    events: Vec<SequenceEvent>,
+    /// Comments emitted as `%%` lines after the diagram-type line
+    #[serde(default)]
+    comments: Vec<String>,
+    config: Option<Config>,
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for SequenceDiagram {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        SequenceDiagramSpec::schema_name()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        SequenceDiagramSpec::json_schema(generator)
+    }
 }
 
 impl FromConfig for SequenceDiagram {
@@ -160,9 +404,8 @@ pub struct SequenceDiagramBuilder {
     autonumber: bool,
     participants: Vec<Participant>,
     boxes: Vec<ParticipantBox>,
-    messages: Vec<Message>,
-    notes: Vec<Note>,
-    logic: Vec<Logic>,
+    events: Vec<SequenceEvent>,
+    comments: Vec<String>,
     config: Option<Config>,
 }
 
@@ -212,75 +455,144 @@ impl SequenceDiagramBuilder {
         self
     }
 
+    /// Add a participant with a popup menu of (label, url) links
+    pub fn participant_with_links(
+        mut self,
+        id: impl Into<String>,
+        menu: Vec<(String, String)>,
+    ) -> Self {
+        self.participants
+            .push(Participant::non_actor(id).with_links(menu));
+        self
+    }
+
     pub fn participant_box(mut self, box_def: ParticipantBox) -> Self {
         self.boxes.push(box_def);
         self
     }
 
     pub fn message(mut self, message: Message) -> Self {
-        self.messages.push(message);
+        self.events.push(SequenceEvent::Message(message));
         self
     }
 
     pub fn message_simple(
-        mut self,
+        self,
         from: impl Into<String>,
         to: impl Into<String>,
         text: impl Into<String>,
     ) -> Self {
-        self.messages.push(Message::new(from, to).with_text(text));
-        self
+        self.message(Message::new(from, to).with_text(text))
     }
 
     pub fn message_with_type(
-        mut self,
+        self,
         from: impl Into<String>,
         to: impl Into<String>,
         message_type: MessageType,
         text: impl Into<String>,
     ) -> Self {
-        self.messages.push(
+        self.message(
             Message::new(from, to)
                 .with_type(message_type)
                 .with_text(text),
-        );
-        self
+        )
+    }
+
+    /// Synchronous call: `from->>to: text`
+    pub fn sync_call(
+        self,
+        from: impl Into<String>,
+        to: impl Into<String>,
+        text: impl Into<String>,
+    ) -> Self {
+        self.message_with_type(from, to, MessageType::SolidArrow, text)
+    }
+
+    /// Fire-and-forget asynchronous call: `from-)to: text`
+    pub fn async_call(
+        self,
+        from: impl Into<String>,
+        to: impl Into<String>,
+        text: impl Into<String>,
+    ) -> Self {
+        self.message_with_type(from, to, MessageType::SolidOpen, text)
+    }
+
+    /// Reply to a prior call: `from-->>to: text`
+    pub fn reply(
+        self,
+        from: impl Into<String>,
+        to: impl Into<String>,
+        text: impl Into<String>,
+    ) -> Self {
+        self.message_with_type(from, to, MessageType::DottedArrow, text)
+    }
+
+    /// Request that activates the receiver: `from->>+to: text`
+    pub fn call(
+        self,
+        from: impl Into<String>,
+        to: impl Into<String>,
+        text: impl Into<String>,
+    ) -> Self {
+        self.message(
+            Message::new(from, to)
+                .with_text(text)
+                .activate()
+                .with_shorthand_activation(),
+        )
+    }
+
+    /// Response that deactivates the sender: `from-->>-to: text`
+    ///
+    /// Mermaid's `-` suffix deactivates the message's sender, matching a prior [`Self::call`].
+    pub fn return_msg(
+        self,
+        from: impl Into<String>,
+        to: impl Into<String>,
+        text: impl Into<String>,
+    ) -> Self {
+        self.message(
+            Message::new(from, to)
+                .with_type(MessageType::DottedArrow)
+                .with_text(text)
+                .deactivate()
+                .with_shorthand_activation(),
+        )
     }
 
     pub fn note(mut self, note: Note) -> Self {
-        self.notes.push(note);
+        self.events.push(SequenceEvent::Note(note));
         self
     }
 
-    pub fn note_over(mut self, participant: impl Into<String>, text: impl Into<String>) -> Self {
-        self.notes.push(Note::over_participant(
+    pub fn note_over(self, participant: impl Into<String>, text: impl Into<String>) -> Self {
+        self.note(Note::over_participant(
             NotePosition::Over,
             participant,
             text,
-        ));
-        self
+        ))
     }
 
-    pub fn note_left(mut self, participant: impl Into<String>, text: impl Into<String>) -> Self {
-        self.notes.push(Note::over_participant(
+    pub fn note_left(self, participant: impl Into<String>, text: impl Into<String>) -> Self {
+        self.note(Note::over_participant(
             NotePosition::Left,
             participant,
             text,
-        ));
-        self
+        ))
     }
 
-    pub fn note_right(mut self, participant: impl Into<String>, text: impl Into<String>) -> Self {
-        self.notes.push(Note::over_participant(
+    pub fn note_right(self, participant: impl Into<String>, text: impl Into<String>) -> Self {
+        self.note(Note::over_participant(
             NotePosition::Right,
             participant,
             text,
-        ));
-        self
+        ))
     }
 
     pub fn logic(mut self, logic_block: Logic) -> Self {
-        self.logic.push(logic_block);
+        self.events.push(SequenceEvent::Logic(logic_block));
         self
     }
 
@@ -290,6 +602,12 @@ impl SequenceDiagramBuilder {
         self
     }
 
+    /// Add a `%%` comment line (e.g. generator or source provenance)
+    pub fn comment(mut self, comment: impl Into<String>) -> Self {
+        self.comments.push(comment.into());
+        self
+    }
+
     pub fn config(mut self, config: Config) -> Self {
         self.config = Some(config);
         self
@@ -301,9 +619,8 @@ impl SequenceDiagramBuilder {
             autonumber: self.autonumber,
             participants: self.participants,
             boxes: self.boxes,
-            messages: self.messages,
-            notes: self.notes,
-            logic: self.logic,
+            events: self.events,
+            comments: self.comments,
             config: self.config,
             raw_mermaid: None,
         }
@@ -313,6 +630,7 @@ impl SequenceDiagramBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::ScriptStyle;
 
     #[test]
     fn sequence_basic() {
@@ -360,7 +678,60 @@ mod tests {
 
         let mermaid = diagram.to_mermaid();
         assert!(mermaid.contains("box rgb(200,255,200) Backend"));
-        assert!(mermaid.contains("end"));
+        assert!(mermaid.contains(
+            "    box rgb(200,255,200) Backend\n        participant S as Server\n    end\n"
+        ));
+    }
+
+    #[test]
+    fn sequence_box_declared_first_renders_first() {
+        let diagram = SequenceDiagram::builder()
+            .participant_simple("A")
+            .participant_simple("B")
+            .participant_simple("Loose")
+            .participant_box(
+                ParticipantBox::new("Pair").with_members(vec!["A".to_string(), "B".to_string()]),
+            )
+            .build();
+
+        let mermaid = diagram.to_mermaid();
+        assert!(mermaid.starts_with(
+            "sequenceDiagram\n    box Pair\n        participant A\n        participant B\n    end\n    participant Loose\n"
+        ));
+    }
+
+    #[test]
+    fn sequence_box_after_loose_participant() {
+        let diagram = SequenceDiagram::builder()
+            .participant_simple("Loose")
+            .participant_simple("A")
+            .participant_box(ParticipantBox::new("Solo").with_member("A"))
+            .build();
+
+        let mermaid = diagram.to_mermaid();
+        let loose = mermaid.find("participant Loose").unwrap();
+        let boxed = mermaid.find("box Solo").unwrap();
+        assert!(loose < boxed);
+    }
+
+    #[test]
+    fn sequence_with_participant_links() {
+        let diagram = SequenceDiagram::builder()
+            .participant_with_links(
+                "Alice",
+                vec![("Repo".to_string(), "https://github.com/alice".to_string())],
+            )
+            .participant_simple("Bob")
+            .message_simple("Alice", "Bob", "Hello")
+            .build();
+
+        let mermaid = diagram.to_mermaid();
+        let links_pos = mermaid
+            .find("links Alice: {\"Repo\": \"https://github.com/alice\"}")
+            .unwrap();
+        // Links follow participant declarations and precede messages
+        assert!(links_pos > mermaid.find("participant Bob").unwrap());
+        assert!(links_pos < mermaid.find("Alice->>Bob").unwrap());
     }
 
     #[test]
@@ -397,6 +768,37 @@ mod tests {
         assert!(mermaid.contains("end"));
     }
 
+    #[test]
+    fn sequence_numbered_messages() {
+        let diagram = SequenceDiagram::builder()
+            .autonumber(true)
+            .message_simple("A", "B", "Request")
+            .logic(
+                Logic::alt("Success")
+                    .with_message(Message::new("B", "A").with_text("OK"))
+                    .with_else_condition(
+                        "Failure",
+                        vec![Message::new("B", "A").with_text("Error")],
+                    ),
+            )
+            .build();
+
+        let numbered = diagram.numbered_messages();
+        let texts: Vec<(u32, &str)> = numbered
+            .iter()
+            .map(|(n, m)| (*n, m.text.as_deref().unwrap()))
+            .collect();
+        assert_eq!(texts, vec![(1, "Request"), (2, "OK"), (3, "Error")]);
+    }
+
+    #[test]
+    fn sequence_numbered_messages_without_autonumber() {
+        let diagram = SequenceDiagram::builder()
+            .message_simple("A", "B", "Request")
+            .build();
+        assert!(diagram.numbered_messages().is_empty());
+    }
+
     #[test]
     fn sequence_from_json() {
         let json = r#"{
@@ -413,7 +815,7 @@ mod tests {
         let diagram = SequenceDiagram::from_json(json).unwrap();
         assert!(diagram.autonumber);
         assert_eq!(diagram.participants.len(), 2);
-        assert_eq!(diagram.messages.len(), 1);
+        assert_eq!(diagram.messages().count(), 1);
     }
 
     #[test]
@@ -441,7 +843,7 @@ messages:
         assert_eq!(diagram.title, Some("API Flow".to_string()));
         assert!(diagram.autonumber);
         assert_eq!(diagram.participants.len(), 2);
-        assert_eq!(diagram.messages.len(), 2);
+        assert_eq!(diagram.messages().count(), 2);
     }
 
     #[test]
@@ -450,4 +852,146 @@ messages:
         let diagram = SequenceDiagram::from_raw_mermaid(raw.to_string());
         assert_eq!(diagram.to_mermaid(), raw);
     }
+
+    #[test]
+    fn sequence_with_comments() {
+        let diagram = SequenceDiagram::builder()
+            .comment("generated by mermaid-rs")
+            .message_simple("Alice", "Bob", "Hi")
+            .build();
+        let script = diagram.build_script();
+        let lines: Vec<&str> = script.lines().collect();
+        assert_eq!(lines[0], "sequenceDiagram");
+        assert_eq!(lines[1], "%% generated by mermaid-rs");
+    }
+
+    #[test]
+    fn sequence_note_between_messages_keeps_order() {
+        let diagram = SequenceDiagram::builder()
+            .message_simple("Alice", "Bob", "Hello")
+            .note_over("Bob", "Thinking")
+            .message_simple("Bob", "Alice", "Hi")
+            .build();
+
+        assert_eq!(
+            diagram.to_mermaid(),
+            "sequenceDiagram\n    Alice->>Bob: Hello\n    Note over Bob: Thinking\n    Bob->>Alice: Hi\n"
+        );
+    }
+
+    #[test]
+    fn sequence_grouped_fields_merge_in_declared_order() {
+        let yaml = r#"
+notes:
+  - position: over
+    over: [A]
+    text: Starting
+messages:
+  - from: A
+    to: B
+    text: Hello
+events:
+  - kind: note
+    position: right
+    over: [B]
+    text: Done
+"#;
+
+        let diagram = SequenceDiagram::from_yaml(yaml).unwrap();
+        assert_eq!(diagram.events.len(), 3);
+        assert_eq!(diagram.notes().count(), 2);
+        assert_eq!(
+            diagram.to_mermaid(),
+            "sequenceDiagram\n    Note over A: Starting\n    A->>B: Hello\n    Note right of B: Done\n"
+        );
+    }
+
+    #[test]
+    fn sequence_serde_roundtrip_keeps_order() {
+        let diagram = SequenceDiagram::builder()
+            .message_simple("A", "B", "one")
+            .note_over("A", "mid")
+            .message_simple("B", "A", "two")
+            .build();
+
+        let json = serde_json::to_string(&diagram).unwrap();
+        let parsed = SequenceDiagram::from_json(&json).unwrap();
+        assert_eq!(parsed.to_mermaid(), diagram.to_mermaid());
+    }
+
+    #[test]
+    fn sequence_call_and_return() {
+        let diagram = SequenceDiagram::builder()
+            .call("Client", "Server", "request")
+            .return_msg("Server", "Client", "response")
+            .build();
+
+        let mermaid = diagram.to_mermaid();
+        assert!(mermaid.contains("Client->>+Server: request"));
+        assert!(mermaid.contains("Server-->>-Client: response"));
+        assert!(!mermaid.contains("activate"));
+    }
+
+    #[test]
+    fn sequence_sync_async_reply() {
+        let diagram = SequenceDiagram::builder()
+            .sync_call("Client", "Server", "get")
+            .reply("Server", "Client", "data")
+            .async_call("Client", "Queue", "publish")
+            .build();
+
+        let mermaid = diagram.to_mermaid();
+        assert!(mermaid.contains("Client->>Server: get"));
+        assert!(mermaid.contains("Server-->>Client: data"));
+        assert!(mermaid.contains("Client-)Queue: publish"));
+
+        // The CLI's --type tokens name the same arrows as the builder methods
+        for (token, message) in ["sync", "reply", "async"].iter().zip(diagram.messages()) {
+            assert_eq!(MessageType::parse(token), Some(message.message_type));
+        }
+    }
+
+    #[test]
+    fn sequence_build_script_frontmatter() {
+        let diagram = SequenceDiagram::builder()
+            .title("Orders")
+            .theme(Theme::Dark)
+            .participant_simple("A")
+            .build();
+        let script = diagram.build_script_with(ScriptStyle::Frontmatter);
+        assert!(script
+            .starts_with("---\ntitle: Orders\nconfig:\n  theme: dark\n---\nsequenceDiagram\n"));
+    }
+
+    #[test]
+    fn sequence_is_empty() {
+        assert!(SequenceDiagram::builder().build().is_empty());
+        assert!(!SequenceDiagram::builder()
+            .participant_simple("A")
+            .build()
+            .is_empty());
+        assert!(SequenceDiagram::from_raw_mermaid("sequenceDiagram\n".to_string()).is_empty());
+    }
+
+    #[test]
+    fn sequence_from_calls() {
+        let diagram = SequenceDiagram::from_calls(&[
+            ("Web", "Api", "GET", "/orders"),
+            ("Api", "Db", "SELECT", "orders"),
+            ("Web", "Api", "POST", "/orders"),
+        ]);
+
+        let ids: Vec<&str> = diagram.participants.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, ["Web", "Api", "Db"]);
+        assert_eq!(diagram.messages().count(), 6);
+
+        let mermaid = diagram.to_mermaid();
+        assert!(mermaid.contains("Web->>+Api: GET /orders\n    Api-->>-Web: response\n"));
+        assert!(mermaid.contains("Api->>+Db: SELECT orders\n    Db-->>-Api: response\n"));
+        assert!(mermaid.contains("Web->>+Api: POST /orders\n"));
+        assert!(mermaid
+            .lines()
+            .filter(|line| line.contains(">>"))
+            .all(|line| line.contains(": ")));
+    }
 }
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Logic, Message, Note};
+
+/// A message, note or logic block in a sequence diagram's authored timeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum SequenceEvent {
+    Message(Message),
+    Note(Note),
+    Logic(Logic),
+}
+
+impl SequenceEvent {
+    /// Renders the event in mermaid syntax, indented for the diagram body
+    pub fn to_mermaid(&self) -> String {
+        match self {
+            Self::Message(message) => format!("    {}\n", message.to_mermaid()),
+            Self::Note(note) => format!("    {}\n", note.to_mermaid()),
+            Self::Logic(logic) => logic
+                .to_mermaid()
+                .lines()
+                .map(|line| format!("    {}\n", line))
+                .collect(),
+        }
+    }
+}
+
+impl From<Message> for SequenceEvent {
+    fn from(message: Message) -> Self {
+        Self::Message(message)
+    }
+}
+
+impl From<Note> for SequenceEvent {
+    fn from(note: Note) -> Self {
+        Self::Note(note)
+    }
+}
+
+impl From<Logic> for SequenceEvent {
+    fn from(logic: Logic) -> Self {
+        Self::Logic(logic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagrams::sequence::NotePosition;
+
+    #[test]
+    fn event_to_mermaid() {
+        let event = SequenceEvent::from(Message::new("A", "B").with_text("Hi"));
+        assert_eq!(event.to_mermaid(), "    A->>B: Hi\n");
+
+        let event = SequenceEvent::from(Note::over_participant(NotePosition::Over, "A", "Wait"));
+        assert_eq!(event.to_mermaid(), "    Note over A: Wait\n");
+
+        let event = SequenceEvent::from(
+            Logic::loop_block("Every minute").with_message(Message::new("A", "B")),
+        );
+        assert_eq!(
+            event.to_mermaid(),
+            "    loop Every minute\n        A->>B\n    end\n"
+        );
+    }
+
+    #[test]
+    fn event_from_yaml() {
+        let yaml = "kind: message\nfrom: A\nto: B\ntext: Hi\n";
+        let event: SequenceEvent = serde_yaml::from_str(yaml).unwrap();
+        assert!(matches!(event, SequenceEvent::Message(_)));
+    }
+}
@@ -3,15 +3,19 @@ use serde::{Deserialize, Serialize};
 use super::Message;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Logic {
     pub logic_type: LogicType,
     pub condition: String,
     pub messages: Vec<Message>,
-    #[serde(default)]
+    /// Further branches, separated by the block's [`LogicType::branch_keyword`]
+    #[serde(default, alias = "branches")]
     pub else_blocks: Vec<ElseBlock>,
 }
 
+/// A branch after the first in a logic block (`else`, `and` or `option`)
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ElseBlock {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub condition: Option<String>,
@@ -82,6 +86,16 @@ impl Logic {
         self
     }
 
+    /// Add a parallel branch (`and <label>`); intended for `par` blocks
+    pub fn with_and(self, label: impl Into<String>, messages: Vec<Message>) -> Self {
+        self.with_else_condition(label, messages)
+    }
+
+    /// Add an alternative branch (`option <label>`); intended for `critical` blocks
+    pub fn with_option(self, label: impl Into<String>, messages: Vec<Message>) -> Self {
+        self.with_else_condition(label, messages)
+    }
+
     pub fn to_mermaid(&self) -> String {
         let mut output = String::new();
 
@@ -94,11 +108,12 @@ impl Logic {
             output.push_str(&format!("    {}\n", msg.to_mermaid()));
         }
 
-        // Else blocks
+        // Further branches
+        let separator = self.logic_type.branch_keyword();
         for else_block in &self.else_blocks {
             match &else_block.condition {
-                Some(cond) => output.push_str(&format!("else {}\n", cond)),
-                None => output.push_str("else\n"),
+                Some(cond) => output.push_str(&format!("{} {}\n", separator, cond)),
+                None => output.push_str(&format!("{}\n", separator)),
             }
             for msg in &else_block.messages {
                 output.push_str(&format!("    {}\n", msg.to_mermaid()));
@@ -111,6 +126,7 @@ impl Logic {
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum LogicType {
     #[default]
@@ -134,6 +150,15 @@ impl LogicType {
         }
     }
 
+    /// Keyword separating branches: `and` for par, `option` for critical, else `else`
+    pub fn branch_keyword(&self) -> &'static str {
+        match self {
+            Self::Par => "and",
+            Self::Critical => "option",
+            _ => "else",
+        }
+    }
+
     pub fn parse(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "alt" | "alternative" => Some(Self::Alt),
@@ -197,11 +222,11 @@ mod tests {
     fn logic_critical() {
         let logic = Logic::critical("Mutex lock")
             .with_message(Message::new("A", "B").with_text("critical section"))
-            .with_else_condition("Error", vec![Message::new("A", "B").with_text("rollback")]);
+            .with_option("Error", vec![Message::new("A", "B").with_text("rollback")]);
 
         let mermaid = logic.to_mermaid();
         assert!(mermaid.contains("critical Mutex lock"));
-        assert!(mermaid.contains("else Error"));
+        assert!(mermaid.contains("option Error"));
         assert!(mermaid.contains("end"));
     }
 
@@ -220,11 +245,18 @@ mod tests {
     fn logic_par() {
         let logic = Logic::par("Parallel tasks")
             .with_message(Message::new("A", "B").with_text("task1"))
-            .with_else_condition("and", vec![Message::new("A", "C").with_text("task2")]);
+            .with_and("Task 2", vec![Message::new("A", "C").with_text("task2")])
+            .with_and("Task 3", vec![Message::new("A", "D").with_text("task3")]);
 
-        let mermaid = logic.to_mermaid();
-        assert!(mermaid.contains("par Parallel tasks"));
-        assert!(mermaid.contains("else and"));
-        assert!(mermaid.contains("end"));
+        assert_eq!(
+            logic.to_mermaid(),
+            "par Parallel tasks\n    A->>B: task1\nand Task 2\n    A->>C: task2\nand Task 3\n    A->>D: task3\nend"
+        );
+    }
+
+    #[test]
+    fn logic_alt_keeps_else() {
+        let logic = Logic::alt("ok").with_else(vec![Message::new("A", "B")]);
+        assert!(logic.to_mermaid().contains("\nelse\n"));
     }
 }
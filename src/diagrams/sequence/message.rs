@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+use crate::core::escape_text;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Message {
     pub from: String,
     pub to: String,
@@ -85,7 +88,10 @@ impl Message {
         let msg_line = match &self.text {
             Some(text) => format!(
                 "{}{}{}: {}",
-                self.from, arrow_with_activation, self.to, text
+                self.from,
+                arrow_with_activation,
+                self.to,
+                escape_text(text)
             ),
             None => format!("{}{}{}", self.from, arrow_with_activation, self.to),
         };
@@ -102,6 +108,7 @@ impl Message {
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub enum MessageType {
     Solid,  // ->
@@ -113,6 +120,8 @@ pub enum MessageType {
     DottedCross, // --x
     SolidOpen, // -)
     DottedOpen, // --)
+    SolidBidirectional, // <<->>
+    DottedBidirectional, // <<-->>
 }
 
 impl MessageType {
@@ -126,21 +135,52 @@ impl MessageType {
             Self::DottedCross => "--x",
             Self::SolidOpen => "-)",
             Self::DottedOpen => "--)",
+            Self::SolidBidirectional => "<<->>",
+            Self::DottedBidirectional => "<<-->>",
         }
     }
 
+    /// Accepted spec tokens, canonical name first for each message type
+    pub const ALIASES: &'static [(&'static str, Self)] = &[
+        ("solid", Self::Solid),
+        ("->", Self::Solid),
+        ("dotted", Self::Dotted),
+        ("-->", Self::Dotted),
+        ("solid-arrow", Self::SolidArrow),
+        ("solidarrow", Self::SolidArrow),
+        ("sync", Self::SolidArrow),
+        ("->>", Self::SolidArrow),
+        ("dotted-arrow", Self::DottedArrow),
+        ("dottedarrow", Self::DottedArrow),
+        ("reply", Self::DottedArrow),
+        ("-->>", Self::DottedArrow),
+        ("solid-cross", Self::SolidCross),
+        ("solidcross", Self::SolidCross),
+        ("-x", Self::SolidCross),
+        ("dotted-cross", Self::DottedCross),
+        ("dottedcross", Self::DottedCross),
+        ("--x", Self::DottedCross),
+        ("solid-open", Self::SolidOpen),
+        ("solidopen", Self::SolidOpen),
+        ("async", Self::SolidOpen),
+        ("-)", Self::SolidOpen),
+        ("dotted-open", Self::DottedOpen),
+        ("dottedopen", Self::DottedOpen),
+        ("async-reply", Self::DottedOpen),
+        ("--)", Self::DottedOpen),
+        ("solid-bidirectional", Self::SolidBidirectional),
+        ("bidirectional", Self::SolidBidirectional),
+        ("<<->>", Self::SolidBidirectional),
+        ("dotted-bidirectional", Self::DottedBidirectional),
+        ("<<-->>", Self::DottedBidirectional),
+    ];
+
     pub fn parse(s: &str) -> Option<Self> {
-        match s.to_lowercase().as_str() {
-            "solid" | "sync" => Some(Self::Solid),
-            "dotted" | "reply" => Some(Self::Dotted),
-            "solid-arrow" | "solidarrow" | "async" => Some(Self::SolidArrow),
-            "dotted-arrow" | "dottedarrow" | "async-reply" => Some(Self::DottedArrow),
-            "solid-cross" | "solidcross" => Some(Self::SolidCross),
-            "dotted-cross" | "dottedcross" => Some(Self::DottedCross),
-            "solid-open" | "solidopen" => Some(Self::SolidOpen),
-            "dotted-open" | "dottedopen" => Some(Self::DottedOpen),
-            _ => None,
-        }
+        let s = s.to_lowercase();
+        Self::ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == s)
+            .map(|(_, value)| *value)
     }
 }
 
@@ -164,6 +204,22 @@ mod tests {
         assert_eq!(MessageType::DottedCross.arrow(), "--x");
     }
 
+    #[test]
+    fn message_type_parse_raw_arrows() {
+        assert_eq!(MessageType::parse("->>"), Some(MessageType::SolidArrow));
+        assert_eq!(MessageType::parse("--)"), Some(MessageType::DottedOpen));
+        assert_eq!(
+            MessageType::parse("<<-->>"),
+            Some(MessageType::DottedBidirectional)
+        );
+        for (_, message_type) in MessageType::ALIASES {
+            assert_eq!(
+                MessageType::parse(message_type.arrow()),
+                Some(*message_type)
+            );
+        }
+    }
+
     #[test]
     fn message_with_type() {
         let msg = Message::new("A", "B")
@@ -225,4 +281,16 @@ mod tests {
             .with_text("async");
         assert_eq!(msg.to_mermaid(), "A-)B: async");
     }
+
+    #[test]
+    fn message_text_with_colon_escaped() {
+        let msg = Message::new("Client", "Server").with_text("GET /a:b");
+        assert_eq!(msg.to_mermaid(), "Client->>Server: GET /a#58;b");
+    }
+
+    #[test]
+    fn message_text_with_hash_escaped() {
+        let msg = Message::new("Alice", "Bob").with_text("ticket #42");
+        assert_eq!(msg.to_mermaid(), "Alice->>Bob: ticket #42");
+    }
 }
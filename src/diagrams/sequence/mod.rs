@@ -1,11 +1,13 @@
 mod diagram;
+mod event;
 mod logic;
 mod message;
 mod note;
 mod participant;
 
 pub use diagram::{SequenceDiagram, SequenceDiagramBuilder};
+pub use event::SequenceEvent;
 pub use logic::{Logic, LogicType};
 pub use message::{Message, MessageType};
 pub use note::{Note, NotePosition};
-pub use participant::{Participant, ParticipantBox, ParticipantType};
+pub use participant::{Participant, ParticipantBox, ParticipantLink, ParticipantType};
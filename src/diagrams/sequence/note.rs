@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Note {
     pub position: NotePosition,
     pub over: Vec<String>,
@@ -52,12 +53,14 @@ impl Note {
             NotePosition::Over => "over",
         };
 
-        let participants = self.over.join(",");
+        let participants: Vec<&str> = self.over.iter().map(|id| id.trim()).collect();
+        let participants = participants.join(",");
         format!("Note {} {}: {}", position_str, participants, self.text)
     }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum NotePosition {
     Left,
@@ -69,8 +72,8 @@ pub enum NotePosition {
 impl NotePosition {
     pub fn parse(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
-            "left" => Some(Self::Left),
-            "right" => Some(Self::Right),
+            "left" | "left of" | "left-of" | "left_of" => Some(Self::Left),
+            "right" | "right of" | "right-of" | "right_of" => Some(Self::Right),
             "over" => Some(Self::Over),
             _ => None,
         }
@@ -97,6 +100,27 @@ mod tests {
         assert_eq!(note.to_mermaid(), "Note over Alice,Bob: Shared note");
     }
 
+    #[test]
+    fn note_over_trims_participants() {
+        let note = Note::over_participants(
+            NotePosition::Over,
+            vec!["Alice".to_string(), " Bob".to_string()],
+            "Shared",
+        );
+        assert_eq!(note.to_mermaid(), "Note over Alice,Bob: Shared");
+    }
+
+    #[test]
+    fn note_position_parse() {
+        assert_eq!(NotePosition::parse("over"), Some(NotePosition::Over));
+        assert_eq!(NotePosition::parse("left"), Some(NotePosition::Left));
+        assert_eq!(NotePosition::parse("Left Of"), Some(NotePosition::Left));
+        assert_eq!(NotePosition::parse("right"), Some(NotePosition::Right));
+        assert_eq!(NotePosition::parse("right of"), Some(NotePosition::Right));
+        assert_eq!(NotePosition::parse("right_of"), Some(NotePosition::Right));
+        assert_eq!(NotePosition::parse("above"), None);
+    }
+
     #[test]
     fn note_left() {
         let note = Note::over_participant(NotePosition::Left, "Alice", "Left note");
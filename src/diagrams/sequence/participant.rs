@@ -1,12 +1,35 @@
 use serde::{Deserialize, Serialize};
 
+use crate::core::escape_text;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Participant {
     pub id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
     #[serde(default, rename = "type")]
     pub participant_type: ParticipantType,
+    /// Actor menu links (links Alice: {"Repo": "https://..."})
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<ParticipantLink>,
+}
+
+/// A labeled URL shown in a participant's popup menu
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ParticipantLink {
+    pub label: String,
+    pub url: String,
+}
+
+impl ParticipantLink {
+    pub fn new(label: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            url: url.into(),
+        }
+    }
 }
 
 impl Participant {
@@ -15,6 +38,7 @@ impl Participant {
             id: id.into(),
             label: None,
             participant_type: ParticipantType::default(),
+            links: Vec::new(),
         }
     }
 
@@ -28,6 +52,7 @@ impl Participant {
             id: id.into(),
             label: None,
             participant_type: ParticipantType::Actor,
+            links: Vec::new(),
         }
     }
 
@@ -37,9 +62,49 @@ impl Participant {
             id: id.into(),
             label: None,
             participant_type: ParticipantType::Participant,
+            links: Vec::new(),
         }
     }
 
+    /// Add a single menu link
+    pub fn with_link(mut self, label: impl Into<String>, url: impl Into<String>) -> Self {
+        self.links.push(ParticipantLink::new(label, url));
+        self
+    }
+
+    /// Set the menu links as (label, url) pairs
+    pub fn with_links(mut self, menu: Vec<(String, String)>) -> Self {
+        self.links = menu
+            .into_iter()
+            .map(|(label, url)| ParticipantLink::new(label, url))
+            .collect();
+        self
+    }
+
+    /// Renders the `links` directive, or None if there are no links.
+    ///
+    /// Links attach to a participant by id, so a participant without an id emits nothing.
+    pub fn links_to_mermaid(&self) -> Option<String> {
+        if self.links.is_empty() || self.id.trim().is_empty() {
+            return None;
+        }
+
+        let entries: Vec<String> = self
+            .links
+            .iter()
+            .map(|link| {
+                // serde_json handles quoting and escaping of both keys and values
+                format!(
+                    "{}: {}",
+                    serde_json::Value::String(link.label.clone()),
+                    serde_json::Value::String(link.url.clone())
+                )
+            })
+            .collect();
+
+        Some(format!("links {}: {{{}}}", self.id, entries.join(", ")))
+    }
+
     pub fn to_mermaid(&self) -> String {
         let keyword = match self.participant_type {
             ParticipantType::Actor => "actor",
@@ -47,13 +112,14 @@ impl Participant {
         };
 
         match &self.label {
-            Some(label) => format!("{} {} as {}", keyword, self.id, label),
+            Some(label) => format!("{} {} as {}", keyword, self.id, escape_text(label)),
             None => format!("{} {}", keyword, self.id),
         }
     }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum ParticipantType {
     Participant,
@@ -61,7 +127,18 @@ pub enum ParticipantType {
     Actor,
 }
 
+impl ParticipantType {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "participant" => Some(Self::Participant),
+            "actor" => Some(Self::Actor),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ParticipantBox {
     pub title: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -83,6 +160,12 @@ impl ParticipantBox {
         self
     }
 
+    /// Draw the box without a background (`box transparent Name`)
+    pub fn transparent(mut self) -> Self {
+        self.color = Some("transparent".to_string());
+        self
+    }
+
     pub fn with_member(mut self, member: impl Into<String>) -> Self {
         self.members.push(member.into());
         self
@@ -94,10 +177,13 @@ impl ParticipantBox {
     }
 
     pub fn to_mermaid_start(&self) -> String {
-        match &self.color {
-            Some(color) => format!("box {} {}\n", color, self.title),
-            None => format!("box {}\n", self.title),
-        }
+        let color = self.color.as_deref().map(str::trim);
+        let parts: Vec<&str> = std::iter::once("box")
+            .chain(color)
+            .chain(Some(self.title.trim()))
+            .filter(|part| !part.is_empty())
+            .collect();
+        format!("{}\n", parts.join(" "))
     }
 
     pub fn to_mermaid_end(&self) -> &'static str {
@@ -127,6 +213,34 @@ mod tests {
         assert_eq!(a.to_mermaid(), "actor User");
     }
 
+    #[test]
+    fn participant_links() {
+        let p = Participant::actor("Alice").with_links(vec![
+            ("Repo".to_string(), "https://github.com/alice".to_string()),
+            ("Docs".to_string(), "https://docs.example.com".to_string()),
+        ]);
+        assert_eq!(
+            p.links_to_mermaid().unwrap(),
+            "links Alice: {\"Repo\": \"https://github.com/alice\", \"Docs\": \"https://docs.example.com\"}"
+        );
+    }
+
+    #[test]
+    fn participant_links_escaped() {
+        let p = Participant::actor("Alice").with_link("Say \"hi\"", "https://x.com/?a=1&b=\\");
+        assert_eq!(
+            p.links_to_mermaid().unwrap(),
+            "links Alice: {\"Say \\\"hi\\\"\": \"https://x.com/?a=1&b=\\\\\"}"
+        );
+    }
+
+    #[test]
+    fn participant_links_require_id() {
+        assert!(Participant::actor("Alice").links_to_mermaid().is_none());
+        let p = Participant::actor("").with_link("Repo", "https://example.com");
+        assert!(p.links_to_mermaid().is_none());
+    }
+
     #[test]
     fn box_basic() {
         let b = ParticipantBox::new("Frontend")
@@ -136,4 +250,45 @@ mod tests {
             .to_mermaid_start()
             .contains("box rgb(200,220,255) Frontend"));
     }
+
+    #[test]
+    fn participant_label_escaped() {
+        let p = Participant::non_actor("api").with_label("API: v2 #1");
+        assert_eq!(p.to_mermaid(), "participant api as API#58; v2 #1");
+    }
+
+    #[test]
+    fn participant_type_parse() {
+        assert_eq!(
+            ParticipantType::parse("Actor"),
+            Some(ParticipantType::Actor)
+        );
+        assert_eq!(
+            ParticipantType::parse("participant"),
+            Some(ParticipantType::Participant)
+        );
+        assert_eq!(ParticipantType::parse("robot"), None);
+    }
+
+    #[test]
+    fn box_start_spacing() {
+        assert_eq!(ParticipantBox::new("Team").to_mermaid_start(), "box Team\n");
+        assert_eq!(
+            ParticipantBox::new("Team").transparent().to_mermaid_start(),
+            "box transparent Team\n"
+        );
+        assert_eq!(
+            ParticipantBox::new("Team")
+                .with_color("rgb(33,66,99)")
+                .to_mermaid_start(),
+            "box rgb(33,66,99) Team\n"
+        );
+        assert_eq!(ParticipantBox::new("").to_mermaid_start(), "box\n");
+        assert_eq!(
+            ParticipantBox::new("Team")
+                .with_color("")
+                .to_mermaid_start(),
+            "box Team\n"
+        );
+    }
 }
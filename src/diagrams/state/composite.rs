@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use super::{State, Transition};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CompositeState {
     pub id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -53,6 +54,11 @@ impl CompositeState {
     }
 
     pub fn to_mermaid(&self) -> String {
+        self.to_mermaid_with_ids(false)
+    }
+
+    /// Renders the state, keeping inner state ids' case when `preserve_ids` is set
+    pub fn to_mermaid_with_ids(&self, preserve_ids: bool) -> String {
         let mut output = String::new();
 
         // Start composite state
@@ -61,7 +67,7 @@ impl CompositeState {
 
         // Render inner states
         for state in &self.states {
-            let state_mermaid = state.to_mermaid();
+            let state_mermaid = state.to_mermaid_with_ids(preserve_ids);
             if !state_mermaid.is_empty() {
                 output.push_str(&format!("    {}\n", state_mermaid));
             }
@@ -69,7 +75,10 @@ impl CompositeState {
 
         // Render inner transitions
         for transition in &self.transitions {
-            output.push_str(&format!("    {}\n", transition.to_mermaid()));
+            output.push_str(&format!(
+                "    {}\n",
+                transition.to_mermaid_with_ids(preserve_ids)
+            ));
         }
 
         output.push_str("}\n");
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use super::{State, Transition};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ConcurrentState {
     pub id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -12,6 +13,7 @@ pub struct ConcurrentState {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ConcurrentRegion {
     #[serde(default)]
     pub states: Vec<State>,
@@ -78,6 +80,11 @@ impl ConcurrentState {
     }
 
     pub fn to_mermaid(&self) -> String {
+        self.to_mermaid_with_ids(false)
+    }
+
+    /// Renders the state, keeping inner state ids' case when `preserve_ids` is set
+    pub fn to_mermaid_with_ids(&self, preserve_ids: bool) -> String {
         let mut output = String::new();
 
         // Start concurrent state
@@ -92,7 +99,7 @@ impl ConcurrentState {
 
             // Render states in region
             for state in &region.states {
-                let state_mermaid = state.to_mermaid();
+                let state_mermaid = state.to_mermaid_with_ids(preserve_ids);
                 if !state_mermaid.is_empty() {
                     output.push_str(&format!("    {}\n", state_mermaid));
                 }
@@ -100,7 +107,10 @@ impl ConcurrentState {
 
             // Render transitions in region
             for transition in &region.transitions {
-                output.push_str(&format!("    {}\n", transition.to_mermaid()));
+                output.push_str(&format!(
+                    "    {}\n",
+                    transition.to_mermaid_with_ids(preserve_ids)
+                ));
             }
         }
 
@@ -7,5 +7,5 @@ mod transition;
 pub use composite::CompositeState;
 pub use concurrent::{ConcurrentRegion, ConcurrentState};
 pub use state_diagram::{StateDiagram, StateDiagramBuilder};
-pub use state_types::{State, StateType};
+pub use state_types::{DescriptionStyle, State, StateType};
 pub use transition::{Choice, Fork, Join, Transition};
@@ -1,10 +1,13 @@
 use serde::{Deserialize, Serialize};
 
-use crate::core::{Config, Diagram, Direction, FromConfig, MermaidError, Theme};
+use crate::core::{
+    render_id, statement_count, Config, Diagram, Direction, FromConfig, MermaidError, Theme,
+};
 
 use super::{Choice, CompositeState, ConcurrentState, Fork, Join, State, Transition};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct StateDiagram {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
@@ -24,6 +27,9 @@ pub struct StateDiagram {
     pub composites: Vec<CompositeState>,
     #[serde(default)]
     pub concurrents: Vec<ConcurrentState>,
+    /// Keep state ids as written instead of lowercasing them
+    #[serde(default)]
+    pub preserve_ids: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config: Option<Config>,
     /// Raw mermaid passthrough (if set, ignores other fields)
@@ -61,6 +67,68 @@ impl StateDiagram {
         let diagram: Self = toml::from_str(toml)?;
         Ok(diagram)
     }
+
+    /// Check states and fork/join pseudo-states for problems that would render incorrectly.
+    ///
+    /// Rejects distinct state ids that render the same after normalization,
+    /// fork/join ids that collide with another state and join sources that are
+    /// not declared states.
+    pub fn validate(&self) -> Result<(), MermaidError> {
+        let key = |id: &str| render_id(id, self.preserve_ids);
+        let mut declared: Vec<String> = Vec::new();
+        for (i, state) in self.states.iter().enumerate() {
+            let id = key(&state.id);
+            if let Some(other) = self.states[..i]
+                .iter()
+                .find(|s| s.id != state.id && key(&s.id) == id)
+            {
+                return Err(MermaidError::InvalidInput(format!(
+                    "State ids '{}' and '{}' both render as '{}'; set preserve_ids to keep their case",
+                    other.id, state.id, id
+                )));
+            }
+            declared.push(id);
+        }
+        for composite in &self.composites {
+            declared.push(key(&composite.id));
+            declared.extend(composite.states.iter().map(|s| key(&s.id)));
+        }
+        for concurrent in &self.concurrents {
+            declared.push(key(&concurrent.id));
+            for region in &concurrent.regions {
+                declared.extend(region.states.iter().map(|s| key(&s.id)));
+            }
+        }
+        declared.extend(self.choices.iter().map(|c| key(&c.id)));
+
+        let pseudo_ids = self
+            .forks
+            .iter()
+            .map(|f| &f.id)
+            .chain(self.joins.iter().map(|j| &j.id));
+        for id in pseudo_ids {
+            let normalized = key(id);
+            if declared.contains(&normalized) {
+                return Err(MermaidError::InvalidInput(format!(
+                    "Duplicate state id '{}'",
+                    id
+                )));
+            }
+            declared.push(normalized);
+        }
+
+        for join in &self.joins {
+            for source in &join.sources {
+                if !declared.contains(&key(source)) {
+                    return Err(MermaidError::DanglingReference {
+                        kind: "state".to_string(),
+                        id: source.clone(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Diagram for StateDiagram {
@@ -82,7 +150,7 @@ impl Diagram for StateDiagram {
 
         // Render states
         for state in &self.states {
-            let state_mermaid = state.to_mermaid();
+            let state_mermaid = state.to_mermaid_with_ids(self.preserve_ids);
             if !state_mermaid.is_empty() {
                 output.push_str(&format!("\t{}\n", state_mermaid));
             }
@@ -91,7 +159,7 @@ impl Diagram for StateDiagram {
         // Render composite states
         for composite in &self.composites {
             // Indent composite output
-            for line in composite.to_mermaid().lines() {
+            for line in composite.to_mermaid_with_ids(self.preserve_ids).lines() {
                 output.push_str(&format!("\t{}\n", line));
             }
         }
@@ -99,35 +167,38 @@ impl Diagram for StateDiagram {
         // Render concurrent states
         for concurrent in &self.concurrents {
             // Indent concurrent output
-            for line in concurrent.to_mermaid().lines() {
+            for line in concurrent.to_mermaid_with_ids(self.preserve_ids).lines() {
                 output.push_str(&format!("\t{}\n", line));
             }
         }
 
         // Render choices
         for choice in &self.choices {
-            for line in choice.to_mermaid().lines() {
+            for line in choice.to_mermaid_with_ids(self.preserve_ids).lines() {
                 output.push_str(&format!("\t{}\n", line));
             }
         }
 
         // Render forks
         for fork in &self.forks {
-            for line in fork.to_mermaid().lines() {
+            for line in fork.to_mermaid_with_ids(self.preserve_ids).lines() {
                 output.push_str(&format!("\t{}\n", line));
             }
         }
 
         // Render joins
         for join in &self.joins {
-            for line in join.to_mermaid().lines() {
+            for line in join.to_mermaid_with_ids(self.preserve_ids).lines() {
                 output.push_str(&format!("\t{}\n", line));
             }
         }
 
         // Render transitions
         for transition in &self.transitions {
-            output.push_str(&format!("\t{}\n", transition.to_mermaid()));
+            output.push_str(&format!(
+                "\t{}\n",
+                transition.to_mermaid_with_ids(self.preserve_ids)
+            ));
         }
 
         output
@@ -144,6 +215,48 @@ impl Diagram for StateDiagram {
     fn config(&self) -> Option<&Config> {
         self.config.as_ref()
     }
+
+    fn is_empty(&self) -> bool {
+        if let Some(raw) = &self.raw_mermaid {
+            return statement_count(raw) == 0;
+        }
+        self.states.is_empty()
+            && self.transitions.is_empty()
+            && self.choices.is_empty()
+            && self.forks.is_empty()
+            && self.joins.is_empty()
+            && self.composites.is_empty()
+            && self.concurrents.is_empty()
+    }
+
+    fn frontmatter_title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    fn estimated_complexity(&self) -> usize {
+        if let Some(raw) = &self.raw_mermaid {
+            return statement_count(raw);
+        }
+        let composites: usize = self
+            .composites
+            .iter()
+            .map(|c| 1 + c.states.len() + c.transitions.len())
+            .sum();
+        let concurrents: usize = self
+            .concurrents
+            .iter()
+            .flat_map(|c| &c.regions)
+            .map(|r| r.states.len() + r.transitions.len())
+            .sum::<usize>()
+            + self.concurrents.len();
+        self.states.len()
+            + self.transitions.len()
+            + self.choices.len()
+            + self.forks.len()
+            + self.joins.len()
+            + composites
+            + concurrents
+    }
 }
 
 impl FromConfig for StateDiagram {
@@ -171,6 +284,7 @@ pub struct StateDiagramBuilder {
     joins: Vec<Join>,
     composites: Vec<CompositeState>,
     concurrents: Vec<ConcurrentState>,
+    preserve_ids: bool,
     config: Option<Config>,
 }
 
@@ -265,6 +379,12 @@ impl StateDiagramBuilder {
         self
     }
 
+    /// Keep state ids as written (e.g. `OrderPlaced`) instead of lowercasing them
+    pub fn preserve_ids(mut self, preserve: bool) -> Self {
+        self.preserve_ids = preserve;
+        self
+    }
+
     pub fn theme(mut self, theme: Theme) -> Self {
         let config = self.config.get_or_insert_with(Config::default);
         config.theme = theme;
@@ -287,6 +407,7 @@ impl StateDiagramBuilder {
             joins: self.joins,
             composites: self.composites,
             concurrents: self.concurrents,
+            preserve_ids: self.preserve_ids,
             config: self.config,
             raw_mermaid: None,
         }
@@ -296,6 +417,7 @@ impl StateDiagramBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::ScriptStyle;
 
     #[test]
     fn state_diagram_basic() {
@@ -350,6 +472,104 @@ mod tests {
         assert!(mermaid.contains("<<choice>>"));
     }
 
+    #[test]
+    fn state_diagram_validate_fork_join() {
+        let diagram = StateDiagram::builder()
+            .state_simple("A")
+            .state_simple("B")
+            .state_simple("Done")
+            .fork(Fork::new("split").with_target("A").with_target("B"))
+            .join(Join::new("merge", "Done").with_source("A").with_source("B"))
+            .build();
+
+        assert!(diagram.validate().is_ok());
+        let mermaid = diagram.to_mermaid();
+        assert!(mermaid.contains("\ta : A\n"));
+        assert!(mermaid.contains("\tdone : Done\n"));
+        assert!(mermaid.contains("\t    split --> a\n"));
+        assert!(mermaid.contains("\t    a --> merge\n"));
+        assert!(mermaid.contains("\t    merge --> done\n"));
+    }
+
+    #[test]
+    fn state_diagram_validate_join_undeclared_source() {
+        let diagram = StateDiagram::builder()
+            .state_simple("A")
+            .state_simple("Done")
+            .join(Join::new("merge", "Done").with_source("A").with_source("B"))
+            .build();
+
+        match diagram.validate() {
+            Err(MermaidError::DanglingReference { kind, id }) => {
+                assert_eq!(kind, "state");
+                assert_eq!(id, "B");
+            }
+            other => panic!("expected DanglingReference, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn state_diagram_preserve_ids() {
+        let diagram = StateDiagram::builder()
+            .preserve_ids(true)
+            .state_simple("OrderPlaced")
+            .state_simple("orderPlaced")
+            .transition_simple("OrderPlaced", "orderPlaced")
+            .build();
+
+        assert!(diagram.validate().is_ok());
+        let mermaid = diagram.to_mermaid();
+        assert!(mermaid.contains("\tOrderPlaced : OrderPlaced\n"));
+        assert!(mermaid.contains("\tOrderPlaced --> orderPlaced\n"));
+
+        let normalized = StateDiagram {
+            preserve_ids: false,
+            ..diagram
+        };
+        assert!(matches!(
+            normalized.validate(),
+            Err(MermaidError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn state_diagram_preserve_ids_fork_join() {
+        let diagram = StateDiagram::builder()
+            .preserve_ids(true)
+            .state_simple("Build")
+            .state_simple("Test")
+            .state_simple("Ship")
+            .fork(Fork::new("Split").with_target("Build").with_target("Test"))
+            .join(
+                Join::new("Merge", "Ship")
+                    .with_source("Build")
+                    .with_source("Test"),
+            )
+            .choice(Choice::new("Gate").with_condition("ok", "Ship"))
+            .build();
+
+        assert!(diagram.validate().is_ok());
+        let mermaid = diagram.to_mermaid();
+        assert!(mermaid.contains("\tstate Split <<fork>>\n"));
+        assert!(mermaid.contains("\t    Split --> Build\n"));
+        assert!(mermaid.contains("\t    Test --> Merge\n"));
+        assert!(mermaid.contains("\t    Merge --> Ship\n"));
+        assert!(mermaid.contains("\t    Gate --> Ship: ok\n"));
+    }
+
+    #[test]
+    fn state_diagram_validate_fork_id_collision() {
+        let diagram = StateDiagram::builder()
+            .state_simple("Split")
+            .fork(Fork::new("split").with_target("Split"))
+            .build();
+
+        assert!(matches!(
+            diagram.validate(),
+            Err(MermaidError::InvalidInput(_))
+        ));
+    }
+
     #[test]
     fn state_diagram_with_composite() {
         let diagram = StateDiagram::builder()
@@ -430,4 +650,16 @@ transitions:
         let diagram = StateDiagram::from_raw_mermaid(raw.to_string());
         assert_eq!(diagram.to_mermaid(), raw);
     }
+
+    #[test]
+    fn state_build_script_frontmatter() {
+        let diagram = StateDiagram::builder()
+            .title("Orders")
+            .theme(Theme::Dark)
+            .state_simple("Idle")
+            .build();
+        let script = diagram.build_script_with(ScriptStyle::Frontmatter);
+        assert!(script
+            .starts_with("---\ntitle: Orders\nconfig:\n  theme: dark\n---\nstateDiagram-v2\n"));
+    }
 }
@@ -1,14 +1,18 @@
 use serde::{Deserialize, Serialize};
 
-use crate::core::normalize_id;
+use crate::core::render_id;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct State {
     pub id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(default, rename = "type")]
     pub state_type: StateType,
+    /// How the description is written; inline falls back to alias when needed
+    #[serde(default)]
+    pub description_style: DescriptionStyle,
 }
 
 impl State {
@@ -17,6 +21,7 @@ impl State {
             id: id.into(),
             description: None,
             state_type: StateType::Normal,
+            description_style: DescriptionStyle::default(),
         }
     }
 
@@ -25,11 +30,17 @@ impl State {
         self
     }
 
+    pub fn with_description_style(mut self, style: DescriptionStyle) -> Self {
+        self.description_style = style;
+        self
+    }
+
     pub fn start() -> Self {
         Self {
             id: "[*]".to_string(),
             description: None,
             state_type: StateType::Start,
+            description_style: DescriptionStyle::default(),
         }
     }
 
@@ -38,17 +49,30 @@ impl State {
             id: "[*]".to_string(),
             description: None,
             state_type: StateType::End,
+            description_style: DescriptionStyle::default(),
         }
     }
 
     pub fn to_mermaid(&self) -> String {
+        self.to_mermaid_with_ids(false)
+    }
+
+    /// Renders the state, keeping the id's case when `preserve_ids` is set
+    pub fn to_mermaid_with_ids(&self, preserve_ids: bool) -> String {
         match self.state_type {
             StateType::Start | StateType::End => String::new(), // Start/End are rendered as transitions
             StateType::Normal => {
                 // Normalize ID to match mermaid-py's text_to_snake_case()
-                let id = normalize_id(&self.id);
+                let id = render_id(&self.id, preserve_ids);
                 let content = self.description.as_ref().unwrap_or(&self.id);
-                format!("{} : {}", id, content)
+                // `:` and `;` would be read as syntax in the inline form
+                let alias = self.description_style == DescriptionStyle::Alias
+                    || content.contains([':', ';']);
+                if alias {
+                    format!("state \"{}\" as {}", content.replace('"', "#quot;"), id)
+                } else {
+                    format!("{} : {}", id, content)
+                }
             }
         }
     }
@@ -60,6 +84,7 @@ impl State {
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum StateType {
     #[default]
@@ -68,6 +93,18 @@ pub enum StateType {
     End,
 }
 
+/// How a state's description is written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum DescriptionStyle {
+    /// `id : description`
+    #[default]
+    Inline,
+    /// `state "description" as id`
+    Alias,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -92,6 +129,26 @@ mod tests {
         assert_eq!(state.to_mermaid(), "active : The active state");
     }
 
+    #[test]
+    fn state_alias_description() {
+        let state = State::new("Active")
+            .with_description("The active state")
+            .with_description_style(DescriptionStyle::Alias);
+        assert_eq!(state.to_mermaid(), "state \"The active state\" as active");
+    }
+
+    #[test]
+    fn state_description_with_colon_uses_alias() {
+        let state = State::new("Wait").with_description("Timeout: 30s");
+        assert_eq!(state.to_mermaid(), "state \"Timeout: 30s\" as wait");
+
+        let state = State::new("Quote").with_description("say \"hi\"; wait");
+        assert_eq!(
+            state.to_mermaid(),
+            "state \"say #quot;hi#quot;; wait\" as quote"
+        );
+    }
+
     #[test]
     fn state_start_end() {
         let start = State::start();
@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::core::normalize_id;
+use crate::core::render_id;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Transition {
     pub from: String,
     pub to: String,
@@ -41,17 +42,13 @@ impl Transition {
     }
 
     pub fn to_mermaid(&self) -> String {
-        // Normalize IDs to match mermaid-py's text_to_snake_case() (but not [*])
-        let from = if self.from == "[*]" {
-            self.from.clone()
-        } else {
-            normalize_id(&self.from)
-        };
-        let to = if self.to == "[*]" {
-            self.to.clone()
-        } else {
-            normalize_id(&self.to)
-        };
+        self.to_mermaid_with_ids(false)
+    }
+
+    /// Renders the transition, keeping endpoint case when `preserve_ids` is set
+    pub fn to_mermaid_with_ids(&self, preserve_ids: bool) -> String {
+        let from = state_ref(&self.from, preserve_ids);
+        let to = state_ref(&self.to, preserve_ids);
         match &self.label {
             Some(label) => format!("{} --> {} : {}", from, to, label),
             None => format!("{} --> {}", from, to),
@@ -59,13 +56,25 @@ impl Transition {
     }
 }
 
+/// Render a state reference; ids are normalized to match mermaid-py's
+/// text_to_snake_case() unless `preserve_ids` is set, but `[*]` is kept as is
+fn state_ref(id: &str, preserve_ids: bool) -> String {
+    if id == "[*]" {
+        id.to_string()
+    } else {
+        render_id(id, preserve_ids)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Choice {
     pub id: String,
     pub conditions: Vec<ChoiceCondition>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ChoiceCondition {
     pub condition: String,
     pub target: String,
@@ -92,11 +101,19 @@ impl Choice {
     }
 
     pub fn to_mermaid(&self) -> String {
-        let mut output = format!("state {} <<choice>>\n", self.id);
+        self.to_mermaid_with_ids(false)
+    }
+
+    /// Renders the choice, keeping id case when `preserve_ids` is set
+    pub fn to_mermaid_with_ids(&self, preserve_ids: bool) -> String {
+        let id = state_ref(&self.id, preserve_ids);
+        let mut output = format!("state {} <<choice>>\n", id);
         for cond in &self.conditions {
             output.push_str(&format!(
                 "    {} --> {}: {}\n",
-                self.id, cond.target, cond.condition
+                id,
+                state_ref(&cond.target, preserve_ids),
+                cond.condition
             ));
         }
         output
@@ -104,6 +121,7 @@ impl Choice {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Fork {
     pub id: String,
     pub targets: Vec<String>,
@@ -128,15 +146,26 @@ impl Fork {
     }
 
     pub fn to_mermaid(&self) -> String {
-        let mut output = format!("state {} <<fork>>\n", self.id);
+        self.to_mermaid_with_ids(false)
+    }
+
+    /// Renders the fork, keeping id case when `preserve_ids` is set
+    pub fn to_mermaid_with_ids(&self, preserve_ids: bool) -> String {
+        let id = state_ref(&self.id, preserve_ids);
+        let mut output = format!("state {} <<fork>>\n", id);
         for target in &self.targets {
-            output.push_str(&format!("    {} --> {}\n", self.id, target));
+            output.push_str(&format!(
+                "    {} --> {}\n",
+                id,
+                state_ref(target, preserve_ids)
+            ));
         }
         output
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Join {
     pub id: String,
     pub sources: Vec<String>,
@@ -163,11 +192,25 @@ impl Join {
     }
 
     pub fn to_mermaid(&self) -> String {
-        let mut output = format!("state {} <<join>>\n", self.id);
+        self.to_mermaid_with_ids(false)
+    }
+
+    /// Renders the join, keeping id case when `preserve_ids` is set
+    pub fn to_mermaid_with_ids(&self, preserve_ids: bool) -> String {
+        let id = state_ref(&self.id, preserve_ids);
+        let mut output = format!("state {} <<join>>\n", id);
         for source in &self.sources {
-            output.push_str(&format!("    {} --> {}\n", source, self.id));
+            output.push_str(&format!(
+                "    {} --> {}\n",
+                state_ref(source, preserve_ids),
+                id
+            ));
         }
-        output.push_str(&format!("    {} --> {}\n", self.id, self.target));
+        output.push_str(&format!(
+            "    {} --> {}\n",
+            id,
+            state_ref(&self.target, preserve_ids)
+        ));
         output
     }
 }
@@ -216,8 +259,8 @@ mod tests {
             .with_condition("no", "StateB");
         let mermaid = c.to_mermaid();
         assert!(mermaid.contains("<<choice>>"));
-        assert!(mermaid.contains("decide --> StateA: yes"));
-        assert!(mermaid.contains("decide --> StateB: no"));
+        assert!(mermaid.contains("decide --> statea: yes"));
+        assert!(mermaid.contains("decide --> stateb: no"));
     }
 
     #[test]
@@ -227,8 +270,8 @@ mod tests {
             .with_target("Parallel2");
         let mermaid = f.to_mermaid();
         assert!(mermaid.contains("<<fork>>"));
-        assert!(mermaid.contains("fork_state --> Parallel1"));
-        assert!(mermaid.contains("fork_state --> Parallel2"));
+        assert!(mermaid.contains("fork_state --> parallel1"));
+        assert!(mermaid.contains("fork_state --> parallel2"));
     }
 
     #[test]
@@ -238,7 +281,7 @@ mod tests {
             .with_source("Parallel2");
         let mermaid = j.to_mermaid();
         assert!(mermaid.contains("<<join>>"));
-        assert!(mermaid.contains("Parallel1 --> join_state"));
-        assert!(mermaid.contains("join_state --> Next"));
+        assert!(mermaid.contains("parallel1 --> join_state"));
+        assert!(mermaid.contains("join_state --> next"));
     }
 }
@@ -2,10 +2,20 @@ pub mod cli;
 pub mod core;
 pub mod diagrams;
 pub mod render;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 // Re-export commonly used types
-pub use core::{normalize_id, Config, Diagram, Direction, FromConfig, MermaidError, Style, Theme};
+pub use core::{
+    escape_text, normalize_id, Config, Diagram, DiagramKind, Direction, FormatOptions, FromConfig,
+    IndentStyle, MermaidError, ScriptStyle, Style, Theme,
+};
 pub use diagrams::{
+    detect_diagram_type,
+    extract_mermaid_blocks,
+    load_diagram,
+    parse,
+    AnyDiagram,
     // ER Diagram
     Attribute,
     AttributeKey,
@@ -22,6 +32,7 @@ pub use diagrams::{
     Entity,
     // Flowchart
     FlowChart,
+    FlowchartKeyword,
     Fork,
     HrefType,
     Join,
@@ -45,6 +56,7 @@ pub use diagrams::{
     NotePosition,
     Participant,
     ParticipantBox,
+    ParticipantLink,
     ParticipantType,
     // Pie
     PieChart,
@@ -55,6 +67,7 @@ pub use diagrams::{
     Risk,
     Section,
     SequenceDiagram,
+    SequenceEvent,
     State,
     StateDiagram,
     StateType,
@@ -63,4 +76,7 @@ pub use diagrams::{
     Transition,
     VerifyMethod,
 };
-pub use render::{MermaidClient, RenderOptions};
+pub use render::{
+    decode_script, encode_script, encode_script_pako, inject_svg_style, make_svg_responsive,
+    render_error_svg, MermaidClient, RenderOptions, ScriptEncoding,
+};
@@ -4,12 +4,21 @@ use mermaid_rs::cli::{Cli, Commands};
 use mermaid_rs::core::MermaidError;
 
 #[tokio::main]
-async fn main() -> Result<(), MermaidError> {
+async fn main() {
     // Initialize logging
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
 
     let cli = Cli::parse();
 
+    // Report errors with their Display message rather than the Debug form,
+    // exiting with a code per error category
+    if let Err(e) = run(cli).await {
+        eprintln!("Error: {}", e);
+        std::process::exit(e.exit_code());
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), MermaidError> {
     match cli.command {
         Commands::Completions(args) => {
             let mut cmd = Cli::command();
@@ -21,12 +30,15 @@ async fn main() -> Result<(), MermaidError> {
             mermaid_rs::cli::commands::flowchart::run(args, &cli.global).await
         }
         Commands::Journey(args) => mermaid_rs::cli::commands::journey::run(args, &cli.global).await,
+        Commands::List(args) => mermaid_rs::cli::commands::list::run(args),
         Commands::Mindmap(args) => mermaid_rs::cli::commands::mindmap::run(args, &cli.global).await,
         Commands::Pie(args) => mermaid_rs::cli::commands::pie::run(args, &cli.global).await,
         Commands::Render(args) => mermaid_rs::cli::commands::render::run(args, &cli.global).await,
         Commands::Requirement(args) => {
             mermaid_rs::cli::commands::requirement::run(args, &cli.global).await
         }
+        #[cfg(feature = "schema")]
+        Commands::Schema(args) => mermaid_rs::cli::commands::schema::run(args),
         Commands::Sequence(args) => {
             mermaid_rs::cli::commands::sequence::run(args, &cli.global).await
         }
@@ -1,11 +1,13 @@
 use reqwest::Client;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use crate::core::{Diagram, MermaidError};
-use crate::render::encoder::encode_diagram;
+use crate::render::encoder::ScriptEncoding;
 
 pub struct MermaidClient {
     client: Client,
     server: String,
+    encoding: ScriptEncoding,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -14,6 +16,8 @@ pub struct RenderOptions {
     pub height: Option<u32>,
     pub scale: Option<f32>,
     pub background_color: Option<String>,
+    /// Render PNGs with a transparent background; `background_color` is ignored
+    pub transparent: bool,
 }
 
 impl RenderOptions {
@@ -40,6 +44,38 @@ impl RenderOptions {
         self.background_color = Some(color.into());
         self
     }
+
+    pub fn transparent(mut self, transparent: bool) -> Self {
+        self.transparent = transparent;
+        self
+    }
+
+    /// Scale factors mermaid.ink accepts
+    pub const SCALE_RANGE: std::ops::RangeInclusive<f32> = 1.0..=3.0;
+
+    /// Pre-flight check for options mermaid.ink would reject with a bare 400.
+    ///
+    /// `scale` must be within [`Self::SCALE_RANGE`] and only applies when a
+    /// width or height is set.
+    pub fn validate(&self) -> Result<(), MermaidError> {
+        let Some(scale) = self.scale else {
+            return Ok(());
+        };
+        if !Self::SCALE_RANGE.contains(&scale) {
+            return Err(MermaidError::InvalidInput(format!(
+                "Scale {} is out of range; mermaid.ink accepts {} to {}",
+                scale,
+                Self::SCALE_RANGE.start(),
+                Self::SCALE_RANGE.end()
+            )));
+        }
+        if self.width.is_none() && self.height.is_none() {
+            return Err(MermaidError::InvalidInput(
+                "Scale requires a width or height (e.g. --width 800 --scale 2)".to_string(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 impl MermaidClient {
@@ -51,10 +87,18 @@ impl MermaidClient {
 
         Self {
             client: Client::new(),
-            server,
+            // Keep path prefixes (`https://host/mermaid`) but drop trailing slashes
+            server: server.trim_end_matches('/').to_string(),
+            encoding: ScriptEncoding::default(),
         }
     }
 
+    /// Choose how scripts are encoded into render URLs
+    pub fn encoding(mut self, encoding: ScriptEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
     pub fn server(&self) -> &str {
         &self.server
     }
@@ -73,19 +117,22 @@ impl MermaidClient {
         script: &str,
         options: &RenderOptions,
     ) -> Result<String, MermaidError> {
-        let encoded = encode_diagram(script);
+        options.validate()?;
+        let encoded = self.encoding.encode(script);
         let url = self.build_url("svg", &encoded, options);
+        self.fetch_svg(&url).await
+    }
 
-        let response = self.client.get(&url).send().await?;
-
-        if !response.status().is_success() {
-            return Err(MermaidError::RenderFailed(format!(
-                "Server returned status: {}",
-                response.status()
-            )));
-        }
-
-        Ok(response.text().await?)
+    /// Render to SVG, also returning the URL that was fetched
+    pub async fn render_svg_with_url(
+        &self,
+        diagram: &dyn Diagram,
+        options: &RenderOptions,
+    ) -> Result<(String, String), MermaidError> {
+        options.validate()?;
+        let url = self.build_render_url(diagram, "svg", options);
+        let svg = self.fetch_svg(&url).await?;
+        Ok((svg, url))
     }
 
     pub async fn render_png(
@@ -102,19 +149,92 @@ impl MermaidClient {
         script: &str,
         options: &RenderOptions,
     ) -> Result<Vec<u8>, MermaidError> {
-        let encoded = encode_diagram(script);
+        options.validate()?;
+        let encoded = self.encoding.encode(script);
         let url = self.build_url("img", &encoded, options);
+        self.fetch_png(&url).await
+    }
+
+    /// Render to PNG, also returning the URL that was fetched
+    pub async fn render_png_with_url(
+        &self,
+        diagram: &dyn Diagram,
+        options: &RenderOptions,
+    ) -> Result<(Vec<u8>, String), MermaidError> {
+        options.validate()?;
+        let url = self.build_render_url(diagram, "img", options);
+        let png = self.fetch_png(&url).await?;
+        Ok((png, url))
+    }
+
+    /// Stream a rendered SVG into `writer` chunk by chunk, returning the bytes written.
+    ///
+    /// Unlike [`Self::render_svg_from_script`] the body is never buffered whole,
+    /// so it is not checked for an `<svg` root.
+    pub async fn stream_svg_from_script<W: AsyncWrite + Unpin>(
+        &self,
+        script: &str,
+        options: &RenderOptions,
+        writer: &mut W,
+    ) -> Result<u64, MermaidError> {
+        options.validate()?;
+        let url = self.build_url("svg", &self.encoding.encode(script), options);
+        self.stream(&url, writer).await
+    }
+
+    /// Stream a rendered PNG into `writer` chunk by chunk, returning the bytes written
+    pub async fn stream_png_from_script<W: AsyncWrite + Unpin>(
+        &self,
+        script: &str,
+        options: &RenderOptions,
+        writer: &mut W,
+    ) -> Result<u64, MermaidError> {
+        options.validate()?;
+        let url = self.build_url("img", &self.encoding.encode(script), options);
+        self.stream(&url, writer).await
+    }
+
+    async fn stream<W: AsyncWrite + Unpin>(
+        &self,
+        url: &str,
+        writer: &mut W,
+    ) -> Result<u64, MermaidError> {
+        let mut response = self.get(url).await?;
+        let mut written = 0u64;
+        while let Some(chunk) = response.chunk().await.map_err(request_error)? {
+            writer.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        writer.flush().await?;
+        Ok(written)
+    }
+
+    async fn fetch_svg(&self, url: &str) -> Result<String, MermaidError> {
+        let response = self.get(url).await?;
+        let svg = response.text().await.map_err(request_error)?;
+        check_svg(&svg)?;
+        Ok(svg)
+    }
+
+    async fn fetch_png(&self, url: &str) -> Result<Vec<u8>, MermaidError> {
+        let response = self.get(url).await?;
+        let png = response.bytes().await.map_err(request_error)?.to_vec();
+        check_png(&png)?;
+        Ok(png)
+    }
 
-        let response = self.client.get(&url).send().await?;
+    /// GET a render URL, failing on a non-success status
+    async fn get(&self, url: &str) -> Result<reqwest::Response, MermaidError> {
+        let response = self.client.get(url).send().await.map_err(request_error)?;
 
         if !response.status().is_success() {
             return Err(MermaidError::RenderFailed(format!(
-                "Server returned status: {}",
+                "Server rejected the diagram with status: {}",
                 response.status()
             )));
         }
 
-        Ok(response.bytes().await?.to_vec())
+        Ok(response)
     }
 
     /// Build the URL for a render request
@@ -125,7 +245,7 @@ impl MermaidClient {
         options: &RenderOptions,
     ) -> String {
         let script = diagram.build_script();
-        let encoded = encode_diagram(&script);
+        let encoded = self.encoding.encode(&script);
         self.build_url(format, &encoded, options)
     }
 
@@ -142,11 +262,20 @@ impl MermaidClient {
         if let Some(s) = options.scale {
             params.push(format!("scale={}", s));
         }
-        if let Some(bg) = &options.background_color {
+        // No bgColor keeps the background transparent
+        if let Some(bg) = options
+            .background_color
+            .as_ref()
+            .filter(|_| !options.transparent)
+        {
             // mermaid.ink expects hex without # (e.g., "1e1e1e" not "#1e1e1e")
             let bg_value = bg.strip_prefix('#').unwrap_or(bg);
             params.push(format!("bgColor={}", bg_value));
         }
+        if endpoint == "img" {
+            // /img returns JPEG unless asked for PNG, which also keeps the alpha channel
+            params.push("type=png".to_string());
+        }
 
         if !params.is_empty() {
             url.push('?');
@@ -157,6 +286,49 @@ impl MermaidClient {
     }
 }
 
+/// Split connection/timeout failures from other request errors
+fn request_error(e: reqwest::Error) -> MermaidError {
+    if e.is_connect() || e.is_timeout() {
+        MermaidError::NetworkUnreachable(e.to_string())
+    } else {
+        MermaidError::HttpError(e)
+    }
+}
+
+const PNG_MAGIC: &[u8] = b"\x89PNG";
+
+/// Reject a response body that doesn't look like SVG (e.g. an HTML error page)
+fn check_svg(body: &str) -> Result<(), MermaidError> {
+    let start = body.trim_start();
+    if start.starts_with("<svg") || start.starts_with("<?xml") {
+        Ok(())
+    } else {
+        Err(MermaidError::RenderFailed(format!(
+            "Server did not return SVG data (response starts with {:?})",
+            preview(body.as_bytes())
+        )))
+    }
+}
+
+/// Reject a response body without the PNG magic number
+fn check_png(body: &[u8]) -> Result<(), MermaidError> {
+    if body.starts_with(PNG_MAGIC) {
+        Ok(())
+    } else {
+        Err(MermaidError::RenderFailed(format!(
+            "Server did not return PNG data (response starts with {:?})",
+            preview(body)
+        )))
+    }
+}
+
+/// First few characters of a response body, for error messages
+fn preview(body: &[u8]) -> String {
+    String::from_utf8_lossy(&body[..body.len().min(40)])
+        .trim()
+        .to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -194,6 +366,18 @@ mod tests {
         assert_eq!(client.server(), "https://custom.example.com");
     }
 
+    #[test]
+    fn client_server_trailing_slash() {
+        let plain = MermaidClient::new(Some("https://host/mermaid".to_string()));
+        let slashed = MermaidClient::new(Some("https://host/mermaid/".to_string()));
+        assert_eq!(slashed.server(), "https://host/mermaid");
+
+        let options = RenderOptions::default();
+        let url = plain.build_render_url(&TestDiagram, "svg", &options);
+        assert_eq!(url, slashed.build_render_url(&TestDiagram, "svg", &options));
+        assert!(url.starts_with("https://host/mermaid/svg/"));
+    }
+
     #[test]
     fn build_url_without_options() {
         let client = MermaidClient::new(Some("https://mermaid.ink".to_string()));
@@ -203,6 +387,14 @@ mod tests {
         assert!(!url.contains('?'));
     }
 
+    #[test]
+    fn build_url_with_pako_encoding() {
+        let client = MermaidClient::new(Some("https://mermaid.ink".to_string()))
+            .encoding(ScriptEncoding::Pako);
+        let url = client.build_render_url(&TestDiagram, "svg", &RenderOptions::default());
+        assert!(url.starts_with("https://mermaid.ink/svg/pako:"));
+    }
+
     #[test]
     fn build_url_with_options() {
         let client = MermaidClient::new(Some("https://mermaid.ink".to_string()));
@@ -212,4 +404,62 @@ mod tests {
         assert!(url.contains("width=800"));
         assert!(url.contains("height=600"));
     }
+
+    #[test]
+    fn build_url_transparent_omits_bg_color() {
+        let client = MermaidClient::new(Some("https://mermaid.ink".to_string()));
+        let diagram = TestDiagram;
+        let options = RenderOptions::new()
+            .background_color("#1e1e1e")
+            .transparent(true);
+
+        let url = client.build_render_url(&diagram, "img", &options);
+        assert!(!url.contains("bgColor"));
+        assert!(url.ends_with("?type=png"));
+
+        let url = client.build_render_url(&diagram, "img", &options.transparent(false));
+        assert!(url.contains("bgColor=1e1e1e"));
+    }
+
+    #[test]
+    fn build_url_img_requests_png() {
+        let client = MermaidClient::new(Some("https://mermaid.ink".to_string()));
+        let url = client.build_render_url(&TestDiagram, "img", &RenderOptions::default());
+        assert!(url.starts_with("https://mermaid.ink/img/"));
+        assert!(url.ends_with("?type=png"));
+
+        let url = client.build_render_url(&TestDiagram, "svg", &RenderOptions::default());
+        assert!(!url.contains("type="));
+    }
+
+    #[test]
+    fn render_options_scale_validation() {
+        assert!(RenderOptions::new()
+            .scale(0.5)
+            .width(800)
+            .validate()
+            .is_err());
+        assert!(RenderOptions::new().scale(2.0).validate().is_err());
+        assert!(RenderOptions::new().validate().is_ok());
+
+        let options = RenderOptions::new().scale(2.0).width(800);
+        assert!(options.validate().is_ok());
+        let client = MermaidClient::new(Some("https://mermaid.ink".to_string()));
+        let url = client.build_render_url(&TestDiagram, "img", &options);
+        assert!(url.contains("width=800&scale=2"));
+    }
+
+    #[test]
+    fn check_svg_magic() {
+        assert!(check_svg("<svg xmlns=\"http://www.w3.org/2000/svg\"></svg>").is_ok());
+        assert!(check_svg("<?xml version=\"1.0\"?><svg></svg>").is_ok());
+        assert!(check_svg("<!DOCTYPE html><html></html>").is_err());
+    }
+
+    #[test]
+    fn check_png_magic() {
+        assert!(check_png(&[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A]).is_ok());
+        assert!(check_png(b"<html>").is_err());
+        assert!(check_png(&[]).is_err());
+    }
 }
@@ -1,15 +1,109 @@
+//! Encoding of mermaid scripts into mermaid.ink URL path segments.
+//!
+//! The plain form is URL-safe base64 (RFC 4648 §5, `-` and `_`, no `=`
+//! padding) of the script's raw UTF-8 bytes. The `pako:` form, shared with the
+//! mermaid live editor, wraps the script in a JSON state object, zlib-deflates
+//! it and base64-encodes the result the same way; it is much shorter for
+//! large diagrams.
+
+use std::io::{Read, Write};
+
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+use serde::{Deserialize, Serialize};
 
-/// Encode a mermaid diagram script for use in mermaid.ink URLs
-pub fn encode_diagram(script: &str) -> String {
+use crate::core::MermaidError;
+
+/// Encode a mermaid script into a mermaid.ink URL segment
+///
+/// The result can be appended to `https://mermaid.ink/svg/` or `/img/`.
+pub fn encode_script(script: &str) -> String {
     URL_SAFE_NO_PAD.encode(script.as_bytes())
 }
 
-/// Decode a base64-encoded mermaid diagram script
-#[allow(dead_code)]
-pub fn decode_diagram(encoded: &str) -> Result<String, base64::DecodeError> {
-    let bytes = URL_SAFE_NO_PAD.decode(encoded)?;
-    Ok(String::from_utf8_lossy(&bytes).to_string())
+/// Prefix marking a deflate-compressed URL segment
+pub const PAKO_PREFIX: &str = "pako:";
+
+/// Live-editor state object carried inside a `pako:` segment
+#[derive(Serialize, Deserialize)]
+struct PakoState {
+    code: String,
+    #[serde(default)]
+    mermaid: String,
+}
+
+/// Encode a mermaid script into a `pako:` (zlib-deflated) mermaid.ink URL segment
+pub fn encode_script_pako(script: &str) -> String {
+    let state = PakoState {
+        code: script.to_string(),
+        mermaid: "{}".to_string(),
+    };
+    let json = serde_json::to_vec(&state).expect("PakoState serializes");
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+    let compressed = encoder
+        .write_all(&json)
+        .and_then(|_| encoder.finish())
+        .expect("deflating into a Vec cannot fail");
+    format!("{}{}", PAKO_PREFIX, URL_SAFE_NO_PAD.encode(compressed))
+}
+
+/// Decode a URL segment produced by [`encode_script`] or [`encode_script_pako`]
+pub fn decode_script(encoded: &str) -> Result<String, MermaidError> {
+    let (pako, data) = match encoded.strip_prefix(PAKO_PREFIX) {
+        Some(rest) => (true, rest),
+        None => (false, encoded),
+    };
+    let bytes = URL_SAFE_NO_PAD
+        .decode(data)
+        .map_err(|e| MermaidError::InvalidInput(format!("Invalid encoded script: {}", e)))?;
+    if pako {
+        let mut json = Vec::new();
+        ZlibDecoder::new(bytes.as_slice())
+            .read_to_end(&mut json)
+            .map_err(|e| MermaidError::InvalidInput(format!("Invalid pako data: {}", e)))?;
+        let state: PakoState = serde_json::from_slice(&json)?;
+        return Ok(state.code);
+    }
+    String::from_utf8(bytes)
+        .map_err(|e| MermaidError::InvalidInput(format!("Encoded script is not UTF-8: {}", e)))
+}
+
+/// How [`crate::render::MermaidClient`] encodes scripts into URLs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScriptEncoding {
+    /// Plain URL-safe base64
+    Base64,
+    /// zlib-deflated `pako:` form
+    Pako,
+    /// Plain base64, switching to pako once it exceeds [`Self::AUTO_PAKO_THRESHOLD`]
+    #[default]
+    Auto,
+}
+
+impl ScriptEncoding {
+    /// Plain-encoded length above which `Auto` switches to pako
+    pub const AUTO_PAKO_THRESHOLD: usize = 2048;
+
+    pub fn encode(&self, script: &str) -> String {
+        match self {
+            Self::Base64 => encode_script(script),
+            Self::Pako => encode_script_pako(script),
+            Self::Auto => {
+                let plain = encode_script(script);
+                if plain.len() > Self::AUTO_PAKO_THRESHOLD {
+                    encode_script_pako(script)
+                } else {
+                    plain
+                }
+            }
+        }
+    }
+}
+
+/// Encode a mermaid diagram script for use in mermaid.ink URLs
+#[deprecated(note = "use `encode_script`")]
+pub fn encode_diagram(script: &str) -> String {
+    encode_script(script)
 }
 
 #[cfg(test)]
@@ -19,17 +113,62 @@ mod tests {
     #[test]
     fn encode_decode_roundtrip() {
         let script = "graph TD\n  A --> B";
-        let encoded = encode_diagram(script);
-        let decoded = decode_diagram(&encoded).unwrap();
+        let encoded = encode_script(script);
+        let decoded = decode_script(&encoded).unwrap();
         assert_eq!(decoded, script);
     }
 
+    #[test]
+    fn encode_is_stable() {
+        assert_eq!(
+            encode_script("graph TD\n  A --> B"),
+            "Z3JhcGggVEQKICBBIC0tPiBC"
+        );
+        assert_eq!(
+            decode_script("Z3JhcGggVEQKICBBIC0tPiBC").unwrap(),
+            "graph TD\n  A --> B"
+        );
+    }
+
     #[test]
     fn encode_produces_url_safe_string() {
         let script = "graph TD\n  A[Start] --> B{Decision}\n  B -->|Yes| C[End]";
-        let encoded = encode_diagram(script);
+        let encoded = encode_script(script);
         // URL-safe base64 should not contain +, /, or =
         assert!(!encoded.contains('+'));
         assert!(!encoded.contains('/'));
+        assert!(!encoded.contains('='));
+    }
+
+    #[test]
+    fn decode_rejects_invalid_input() {
+        assert!(matches!(
+            decode_script("not base64!"),
+            Err(MermaidError::InvalidInput(_))
+        ));
+        // "_w" is 0xFF, which is not valid UTF-8
+        assert!(decode_script("_w").is_err());
+    }
+
+    #[test]
+    fn pako_has_prefix_and_roundtrips() {
+        let script = "graph TD\n  A --> B";
+        let encoded = encode_script_pako(script);
+        assert!(encoded.starts_with("pako:"));
+        assert_ne!(encoded, encode_script(script));
+        assert!(!encoded[PAKO_PREFIX.len()..].contains(['+', '/', '=']));
+        assert_eq!(decode_script(&encoded).unwrap(), script);
+    }
+
+    #[test]
+    fn auto_switches_to_pako_for_large_scripts() {
+        let small = "graph TD\n  A --> B";
+        assert_eq!(ScriptEncoding::Auto.encode(small), encode_script(small));
+
+        let large = format!("graph TD\n{}", "  A --> B\n".repeat(500));
+        let encoded = ScriptEncoding::Auto.encode(&large);
+        assert!(encoded.starts_with(PAKO_PREFIX));
+        assert!(encoded.len() < encode_script(&large).len());
+        assert_eq!(decode_script(&encoded).unwrap(), large);
     }
 }
@@ -1,5 +1,10 @@
 mod client;
 mod encoder;
+mod style;
 
 pub use client::{MermaidClient, RenderOptions};
+#[allow(deprecated)]
 pub use encoder::encode_diagram;
+pub use encoder::{decode_script, encode_script, encode_script_pako, ScriptEncoding, PAKO_PREFIX};
+pub(crate) use style::escape_xml;
+pub use style::{inject_svg_style, make_svg_responsive, render_error_svg};
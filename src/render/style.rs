@@ -0,0 +1,193 @@
+/// Inject a CSS block into a rendered SVG document.
+///
+/// The CSS is appended to an existing `<style>` element when there is one,
+/// otherwise a new `<style>` element is inserted right after the opening
+/// `<svg>` tag. Input without an `<svg>` tag is returned unchanged.
+pub fn inject_svg_style(svg: &str, css: &str) -> String {
+    if let Some(close) = svg.find("</style>") {
+        let mut output = String::with_capacity(svg.len() + css.len());
+        output.push_str(&svg[..close]);
+        output.push_str(css);
+        output.push_str(&svg[close..]);
+        return output;
+    }
+
+    let Some(start) = svg.find("<svg") else {
+        return svg.to_string();
+    };
+    let Some(end) = svg[start..].find('>').map(|i| start + i + 1) else {
+        return svg.to_string();
+    };
+
+    let mut output = String::with_capacity(svg.len() + css.len() + 15);
+    output.push_str(&svg[..end]);
+    output.push_str("<style>");
+    output.push_str(css);
+    output.push_str("</style>");
+    output.push_str(&svg[end..]);
+    output
+}
+
+/// A small standalone SVG reporting a failed render.
+///
+/// Used in place of the real diagram when a docs build should keep going;
+/// the diagram type and message are XML-escaped into the drawing.
+pub fn render_error_svg(message: &str, diagram_type: &str) -> String {
+    format!(
+        concat!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"480\" height=\"80\" viewBox=\"0 0 480 80\">",
+            "<rect x=\"1\" y=\"1\" width=\"478\" height=\"78\" fill=\"#fff5f5\" stroke=\"#d33\" stroke-dasharray=\"4\"/>",
+            "<text x=\"12\" y=\"30\" font-family=\"sans-serif\" font-size=\"14\" fill=\"#d33\">Failed to render {} diagram</text>",
+            "<text x=\"12\" y=\"56\" font-family=\"monospace\" font-size=\"11\" fill=\"#333\">{}</text>",
+            "</svg>\n"
+        ),
+        escape_xml(diagram_type),
+        escape_xml(message)
+    )
+}
+
+/// Escape text for XML/HTML element content and double-quoted attributes
+pub(crate) fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Make a rendered SVG scale with its container.
+///
+/// The root `<svg>` gets `width="100%"` and `height="auto"`. An existing
+/// `viewBox` is kept; without one, a viewBox is synthesized from the original
+/// numeric width and height so the drawing still scales. Input without an
+/// `<svg>` tag is returned unchanged.
+pub fn make_svg_responsive(svg: &str) -> String {
+    let Some(start) = svg.find("<svg") else {
+        return svg.to_string();
+    };
+    let Some(end) = svg[start..].find('>').map(|i| start + i) else {
+        return svg.to_string();
+    };
+
+    let mut tag = svg[start..end].to_string();
+    let width = remove_attr(&mut tag, "width");
+    let height = remove_attr(&mut tag, "height");
+
+    if attr_range(&tag, "viewBox").is_none() {
+        let dimension =
+            |v: Option<String>| -> Option<f64> { v?.trim().trim_end_matches("px").parse().ok() };
+        if let (Some(w), Some(h)) = (dimension(width), dimension(height)) {
+            tag.push_str(&format!(" viewBox=\"0 0 {} {}\"", w, h));
+        }
+    }
+
+    let self_closing = tag.ends_with('/');
+    if self_closing {
+        tag.pop();
+    }
+    let tag = tag.trim_end();
+
+    let mut output = String::with_capacity(svg.len() + 32);
+    output.push_str(&svg[..start]);
+    output.push_str(tag);
+    output.push_str(" width=\"100%\" height=\"auto\"");
+    if self_closing {
+        output.push('/');
+    }
+    output.push_str(&svg[end..]);
+    output
+}
+
+/// Byte range of ` name="value"` in a tag, and the range of the value
+fn attr_range(tag: &str, name: &str) -> Option<(usize, usize, usize)> {
+    let needle = format!("{}=\"", name);
+    let mut from = 0;
+    while let Some(i) = tag[from..].find(&needle).map(|i| from + i) {
+        // Must be a whole attribute name, not e.g. `stroke-width`
+        if tag[..i].ends_with(char::is_whitespace) {
+            let value_start = i + needle.len();
+            let value_end = value_start + tag[value_start..].find('"')?;
+            return Some((i - 1, value_start, value_end));
+        }
+        from = i + needle.len();
+    }
+    None
+}
+
+/// Remove an attribute from a tag, returning its value
+fn remove_attr(tag: &mut String, name: &str) -> Option<String> {
+    let (attr_start, value_start, value_end) = attr_range(tag, name)?;
+    let value = tag[value_start..value_end].to_string();
+    tag.replace_range(attr_start..value_end + 1, "");
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_svg_contains_message() {
+        let svg = render_error_svg("Server returned status: 400 <Bad Request>", "flowchart");
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("Failed to render flowchart diagram"));
+        assert!(svg.contains("Server returned status: 400 &lt;Bad Request&gt;"));
+    }
+
+    #[test]
+    fn responsive_keeps_view_box() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="400" height="200" viewBox="0 0 400 200"><rect stroke-width="2"/></svg>"#;
+        assert_eq!(
+            make_svg_responsive(svg),
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 400 200" width="100%" height="auto"><rect stroke-width="2"/></svg>"#
+        );
+    }
+
+    #[test]
+    fn responsive_synthesizes_view_box() {
+        let svg = r#"<svg width="320px" height="120px"><g></g></svg>"#;
+        assert_eq!(
+            make_svg_responsive(svg),
+            r#"<svg viewBox="0 0 320 120" width="100%" height="auto"><g></g></svg>"#
+        );
+    }
+
+    #[test]
+    fn responsive_without_svg_is_unchanged() {
+        assert_eq!(make_svg_responsive("not svg"), "not svg");
+    }
+
+    #[test]
+    fn inject_inserts_after_svg_tag() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg"><g></g></svg>"#;
+        let result = inject_svg_style(svg, ".node{fill:red}");
+        assert_eq!(
+            result,
+            r#"<svg xmlns="http://www.w3.org/2000/svg"><style>.node{fill:red}</style><g></g></svg>"#
+        );
+        assert_eq!(result.matches("<style>").count(), 1);
+    }
+
+    #[test]
+    fn inject_appends_to_existing_style() {
+        let svg = "<svg><style>.a{}</style><g></g></svg>";
+        let result = inject_svg_style(svg, ".b{}");
+        assert_eq!(result, "<svg><style>.a{}.b{}</style><g></g></svg>");
+        assert_eq!(result.matches("<style>").count(), 1);
+        assert_eq!(result.matches("</style>").count(), 1);
+    }
+
+    #[test]
+    fn inject_keeps_tags_balanced() {
+        let svg = "<?xml version=\"1.0\"?>\n<svg width=\"10\"><rect/></svg>";
+        let result = inject_svg_style(svg, "rect{}");
+        assert!(result.starts_with("<?xml version=\"1.0\"?>\n<svg width=\"10\"><style>"));
+        assert!(result.ends_with("</svg>"));
+        assert_eq!(result.matches("<svg").count(), 1);
+        assert_eq!(result.matches("</svg>").count(), 1);
+    }
+
+    #[test]
+    fn inject_without_svg_is_unchanged() {
+        assert_eq!(inject_svg_style("not svg", "a{}"), "not svg");
+    }
+}
@@ -0,0 +1,96 @@
+//! Helpers for asserting on generated mermaid in tests.
+//!
+//! Enabled with the `testing` feature.
+
+/// Normalize a mermaid script for comparison.
+///
+/// Leading tabs become four spaces, trailing whitespace is trimmed and blank
+/// lines are dropped, so formatting-only differences compare equal.
+pub fn normalize_mermaid(script: &str) -> String {
+    script
+        .lines()
+        .map(|line| {
+            let indent = line.len() - line.trim_start_matches('\t').len();
+            format!("{}{}", "    ".repeat(indent), &line[indent..])
+        })
+        .map(|line| line.trim_end().to_string())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Assert two mermaid scripts are equal after [`normalize_mermaid`].
+///
+/// On mismatch, panics with a line diff: `-` lines are only in `expected`,
+/// `+` lines only in `actual`.
+#[track_caller]
+pub fn assert_mermaid_eq(actual: &str, expected: &str) {
+    let actual = normalize_mermaid(actual);
+    let expected = normalize_mermaid(expected);
+    if actual != expected {
+        panic!(
+            "mermaid scripts differ (-expected +actual):\n{}",
+            line_diff(&expected, &actual)
+        );
+    }
+}
+
+/// Line diff based on the longest common subsequence of lines
+fn line_diff(expected: &str, actual: &str) -> String {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+
+    // lcs[i][j] = length of the LCS of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut output = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            output.push_str(&format!("  {}\n", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            output.push_str(&format!("- {}\n", old[i]));
+            i += 1;
+        } else {
+            output.push_str(&format!("+ {}\n", new[j]));
+            j += 1;
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_collapses_formatting() {
+        let script = "pie title Pets\n\n\t\"Dogs\" : 10   \n    \"Cats\" : 5\n\n";
+        assert_eq!(
+            normalize_mermaid(script),
+            "pie title Pets\n    \"Dogs\" : 10\n    \"Cats\" : 5"
+        );
+    }
+
+    #[test]
+    fn assert_eq_ignores_formatting() {
+        assert_mermaid_eq("flowchart TD\n\ta --> b\n\n", "flowchart TD\n    a --> b");
+    }
+
+    #[test]
+    #[should_panic(expected = "-     a --> c\n+     a --> b")]
+    fn assert_eq_reports_diff() {
+        assert_mermaid_eq("flowchart TD\n    a --> b", "flowchart TD\n    a --> c");
+    }
+}
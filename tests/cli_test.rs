@@ -9,6 +9,30 @@ fn run_cli(args: &[&str]) -> std::process::Output {
         .expect("Failed to execute command")
 }
 
+/// Run the CLI binary with `input` piped to stdin
+fn run_cli_with_stdin(args: &[&str], input: &str) -> std::process::Output {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut child = Command::new("cargo")
+        .args(["run", "--quiet", "--"])
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child
+        .wait_with_output()
+        .expect("Failed to wait for command")
+}
+
 #[test]
 fn cli_help() {
     let output = run_cli(&["--help"]);
@@ -240,6 +264,41 @@ fn cli_sequence_mermaid_output() {
     assert!(stdout.contains("participant Server"));
 }
 
+#[test]
+fn cli_sequence_spanning_note() {
+    let output = run_cli(&[
+        "sequence",
+        "--participant",
+        "Alice",
+        "--participant",
+        "Bob",
+        "--note",
+        "over:Alice, Bob:Handshake\\: step 1",
+        "--note",
+        "right of:Bob:Thinking",
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("Note over Alice,Bob: Handshake: step 1"));
+    assert!(stdout.contains("Note right of Bob: Thinking"));
+
+    let output = run_cli(&[
+        "sequence",
+        "--note",
+        "left:Alice,Bob:Nope",
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("only 'over' notes"));
+}
+
 #[test]
 fn cli_sequence_with_autonumber() {
     let output = run_cli(&[
@@ -323,3 +382,885 @@ fn cli_state_with_direction() {
     assert!(output.status.success());
     assert!(stdout.contains("direction LR"));
 }
+
+#[test]
+fn cli_render_input_detects_type() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("chart.yaml");
+    std::fs::write(
+        &path,
+        "type: pie\ntitle: Pets\ndata:\n  - label: Dogs\n    value: 10\n",
+    )
+    .unwrap();
+
+    let output = run_cli(&[
+        "render",
+        "--input",
+        path.to_str().unwrap(),
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("pie title Pets"));
+    assert!(stdout.contains("\"Dogs\" : 10"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cli_pie_multiple_formats_derive_filenames() {
+    use wiremock::matchers::{method, path_regex};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/svg/.*"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("<svg></svg>"))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/img/.*"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0x89, 0x50, 0x4E, 0x47]))
+        .mount(&mock_server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let output_path = dir.path().join("chart.svg");
+    let server = mock_server.uri();
+    let output_arg = output_path.to_str().unwrap().to_string();
+
+    let output = tokio::task::spawn_blocking(move || {
+        run_cli(&[
+            "pie",
+            "--data",
+            "Dogs:10",
+            "--server",
+            &server,
+            "--format",
+            "svg,png",
+            "--output",
+            &output_arg,
+        ])
+    })
+    .await
+    .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let svg = std::fs::read_to_string(dir.path().join("chart.svg")).unwrap();
+    assert!(svg.contains("<svg"));
+    let png = std::fs::read(dir.path().join("chart.png")).unwrap();
+    assert_eq!(&png[0..4], &[0x89, 0x50, 0x4E, 0x47]);
+}
+
+/// Render `args` in dark mode against a server that only answers the dark background
+async fn render_dark(args: &'static [&'static str]) -> std::process::Output {
+    use wiremock::matchers::{method, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(query_param("bgColor", "1e1e1e"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("<svg></svg>"))
+        .mount(&mock_server)
+        .await;
+    let server = mock_server.uri();
+
+    tokio::task::spawn_blocking(move || {
+        let mut cli_args = args.to_vec();
+        cli_args.extend(["--mode", "dark", "--format", "svg", "--stdout", "--server"]);
+        cli_args.push(&server);
+        run_cli(&cli_args)
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cli_flowchart_dark_mode_background() {
+    let output = render_dark(&["flowchart", "--node", "A:Start"]).await;
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cli_sequence_dark_mode_background() {
+    let output = render_dark(&[
+        "sequence",
+        "--participant",
+        "User",
+        "--message",
+        "User->User::hi",
+    ])
+    .await;
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn cli_on_error_placeholder_rejects_png() {
+    let output = run_cli(&[
+        "pie",
+        "--data",
+        "Dogs:10",
+        "--format",
+        "png",
+        "--stdout",
+        "--on-error",
+        "placeholder",
+    ]);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("--on-error placeholder only supports svg output"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cli_on_error_placeholder_writes_error_svg() {
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(400))
+        .mount(&mock_server)
+        .await;
+    let server = mock_server.uri();
+
+    let args = move |on_error: &'static str| {
+        let server = server.clone();
+        move || {
+            run_cli(&[
+                "pie",
+                "--data",
+                "Dogs:10",
+                "--server",
+                &server,
+                "--format",
+                "svg",
+                "--stdout",
+                "--on-error",
+                on_error,
+            ])
+        }
+    };
+
+    let failed = tokio::task::spawn_blocking(args("fail")).await.unwrap();
+    assert_eq!(failed.status.code(), Some(3));
+
+    let output = tokio::task::spawn_blocking(args("placeholder"))
+        .await
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("<svg"));
+    assert!(stdout.contains("Failed to render pie diagram"));
+    assert!(stdout.contains("400"));
+}
+
+#[test]
+fn cli_mermaid_with_image_format_to_stdout_fails() {
+    let output = run_cli(&["pie", "--data", "Dogs:10", "--format", "mermaid,svg"]);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("Cannot combine mermaid"));
+}
+
+#[test]
+fn cli_strict_rejects_unknown_shape() {
+    let output = run_cli(&[
+        "flowchart",
+        "--node",
+        "A:Start:stadum",
+        "--strict",
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("Unknown node shape 'stadum'"));
+}
+
+#[test]
+fn cli_unknown_shape_defaults_without_strict() {
+    let output = run_cli(&[
+        "flowchart",
+        "--node",
+        "A:Start:stadum",
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("a[\"Start\"]"));
+}
+
+#[test]
+fn cli_strict_rejects_unknown_risk() {
+    let output = run_cli(&[
+        "requirement",
+        "--requirement",
+        "r1:Login:Must log in:extreme:test",
+        "--strict",
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("Unknown risk 'extreme'"));
+}
+
+#[test]
+fn cli_config_file_applies_theme_variables() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("mermaid.yaml");
+    std::fs::write(
+        &path,
+        "theme: base\nthemeVariables:\n  primaryColor: \"#ff0000\"\n",
+    )
+    .unwrap();
+
+    let output = run_cli(&[
+        "pie",
+        "--data",
+        "Dogs:10",
+        "--config-file",
+        path.to_str().unwrap(),
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("'theme': 'base'"));
+    assert!(stdout.contains("'primaryColor': '#ff0000'"));
+}
+
+#[test]
+fn cli_config_file_without_theme_keeps_mode_theme() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("mermaid.yaml");
+    std::fs::write(&path, "themeVariables:\n  primaryColor: \"#ff0000\"\n").unwrap();
+
+    let output = run_cli(&[
+        "pie",
+        "--data",
+        "Dogs:10",
+        "--mode",
+        "dark",
+        "--config-file",
+        path.to_str().unwrap(),
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("'theme': 'dark'"));
+    assert!(stdout.contains("'primaryColor': '#ff0000'"));
+}
+
+#[test]
+fn cli_theme_var_reaches_init_directive() {
+    let output = run_cli(&[
+        "pie",
+        "--data",
+        "Dogs:10",
+        "--theme-var",
+        "primaryColor=#ff0000",
+        "--theme-var",
+        "fontFamily=monospace",
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(
+        stdout.contains("'themeVariables': {'primaryColor': '#ff0000', 'fontFamily': 'monospace'}")
+    );
+}
+
+#[test]
+fn cli_commands_share_config_handling() {
+    let shared = [
+        "--mode",
+        "dark",
+        "--theme-var",
+        "primaryColor=#ff0000",
+        "--format",
+        "mermaid",
+        "--stdout",
+    ];
+    let init_line = |args: &[&str]| {
+        let output = run_cli(&[args, &shared[..]].concat());
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find(|line| line.starts_with("%%{init"))
+            .map(String::from)
+    };
+
+    let pie = init_line(&["pie", "--data", "Dogs:10"]);
+    let flowchart = init_line(&["flowchart", "--node", "A:Start"]);
+
+    assert!(pie.is_some());
+    assert_eq!(pie, flowchart);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cli_commands_share_render_options() {
+    use wiremock::matchers::{method, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(query_param("width", "640"))
+        .and(query_param("scale", "2"))
+        .and(query_param("bgColor", "abcdef"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("<svg></svg>"))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+    let server = mock_server.uri();
+
+    for args in [
+        &["pie", "--data", "Dogs:10"][..],
+        &["er", "--entity", "USER"][..],
+    ] {
+        let server = server.clone();
+        let output = tokio::task::spawn_blocking(move || {
+            let mut cli_args = args.to_vec();
+            cli_args.extend([
+                "--width",
+                "640",
+                "--scale",
+                "2",
+                "--background-color",
+                "#abcdef",
+                "--format",
+                "svg",
+                "--stdout",
+                "--server",
+                &server,
+            ]);
+            run_cli(&cli_args)
+        })
+        .await
+        .unwrap();
+
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
+
+#[test]
+fn cli_theme_var_rejects_missing_value() {
+    let output = run_cli(&["pie", "--data", "Dogs:10", "--theme-var", "primaryColor"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("Invalid theme variable 'primaryColor'"));
+}
+
+#[test]
+fn cli_stdin_toml_with_input_format() {
+    let toml = "title = \"Pets\"\n\n[[data]]\nlabel = \"Dogs\"\nvalue = 10\n";
+    let output = run_cli_with_stdin(
+        &[
+            "pie",
+            "--stdin",
+            "--input-format",
+            "toml",
+            "--format",
+            "mermaid",
+            "--stdout",
+        ],
+        toml,
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("pie title Pets"));
+    assert!(stdout.contains("\"Dogs\" : 10"));
+}
+
+#[test]
+fn cli_stdin_toml_fallback() {
+    let toml = "title = \"API\"\n\n[[participants]]\nid = \"User\"\n\n[[participants]]\nid = \"API\"\n\n[[messages]]\nfrom = \"User\"\nto = \"API\"\ntext = \"GET /users\"\n";
+    let output = run_cli_with_stdin(
+        &["sequence", "--stdin", "--format", "mermaid", "--stdout"],
+        toml,
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("title: API"));
+    assert!(stdout.contains("User->>API: GET /users"));
+}
+
+#[test]
+fn cli_stdin_unparseable_lists_formats() {
+    let output = run_cli_with_stdin(&["pie", "--stdin", "--format", "mermaid"], "= nope =\n");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("could not parse stdin as yaml or toml"));
+    assert!(stderr.contains("yaml: "));
+    assert!(stderr.contains("toml: "));
+}
+
+#[test]
+fn cli_stdin_mermaid_input_format() {
+    let output = run_cli_with_stdin(
+        &[
+            "flowchart",
+            "--stdin",
+            "--input-format",
+            "mermaid",
+            "--format",
+            "mermaid",
+            "--stdout",
+        ],
+        "flowchart LR\n    a --> b\n",
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("flowchart LR\n    a --> b"));
+}
+
+#[test]
+fn cli_flowchart_node_class() {
+    let output = run_cli(&[
+        "flowchart",
+        "--node",
+        "A:Start:rect:important",
+        "--node",
+        "B:End:::done",
+        "--node",
+        "C:Plain:stadium",
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("a[\"Start\"]:::important"));
+    assert!(stdout.contains("b[\"End\"]:::done"));
+    assert!(stdout.contains("c([\"Plain\"])\n"));
+}
+
+#[test]
+fn cli_pie_invalid_data_warns() {
+    let output = run_cli(&[
+        "pie", "--data", "Debt:-2", "--format", "mermaid", "--stdout",
+    ]);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(output.status.success());
+    assert!(stderr.contains("Warning:"));
+    assert!(stderr.contains("pie slice 'Debt' has negative value -2"));
+}
+
+#[test]
+fn cli_pie_invalid_data_strict_fails() {
+    let output = run_cli(&[
+        "pie", "--data", "Debt:-2", "--strict", "--format", "mermaid", "--stdout",
+    ]);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("pie slice 'Debt' has negative value -2"));
+}
+
+#[test]
+fn cli_flowchart_empty_warns() {
+    let output = run_cli(&["flowchart", "--format", "mermaid", "--stdout"]);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(output.status.success());
+    assert!(stderr.contains("Warning: flowchart diagram is empty"));
+}
+
+#[test]
+fn cli_flowchart_empty_strict_fails() {
+    let output = run_cli(&["flowchart", "--strict", "--format", "mermaid", "--stdout"]);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("flowchart diagram is empty"));
+}
+
+#[test]
+fn cli_flowchart_duplicate_ids_strict_fails() {
+    let output = run_cli(&[
+        "flowchart",
+        "--node",
+        "A:First",
+        "--node",
+        "A:Second",
+        "--strict",
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("Duplicate node id 'A'"));
+}
+
+#[test]
+fn cli_render_from_markdown() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("README.md");
+    std::fs::write(
+        &path,
+        "# Docs\n\n```mermaid\nflowchart LR\n    a --> b\n```\n\ntext\n\n```mermaid\npie\n    \"A\" : 1\n```\n",
+    )
+    .unwrap();
+
+    let output = run_cli(&[
+        "render",
+        "--from-markdown",
+        path.to_str().unwrap(),
+        "--format",
+        "mermaid",
+    ]);
+
+    assert!(output.status.success());
+    let first = std::fs::read_to_string(dir.path().join("README-1.mmd")).unwrap();
+    let second = std::fs::read_to_string(dir.path().join("README-2.mmd")).unwrap();
+    assert!(first.contains("flowchart LR\n    a --> b"));
+    assert!(second.contains("\"A\" : 1"));
+    assert!(!first.contains("```"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cli_render_from_markdown_gallery() {
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("<svg></svg>"))
+        .mount(&mock_server)
+        .await;
+    let server = mock_server.uri();
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("README.md");
+    std::fs::write(
+        &path,
+        "```mermaid\nflowchart LR\n    a --> b\n```\n\n```mermaid\npie\n    \"A\" : 1\n```\n",
+    )
+    .unwrap();
+    let path_arg = path.to_str().unwrap().to_string();
+
+    let output = tokio::task::spawn_blocking(move || {
+        run_cli(&[
+            "render",
+            "--from-markdown",
+            &path_arg,
+            "--gallery",
+            "--format",
+            "svg",
+            "--server",
+            &server,
+        ])
+    })
+    .await
+    .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let html = std::fs::read_to_string(dir.path().join("index.html")).unwrap();
+    assert!(html.contains("<object data=\"README-1.svg\""));
+    assert!(html.contains("<object data=\"README-2.svg\""));
+}
+
+#[test]
+fn cli_render_gallery_needs_image_format() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("README.md");
+    std::fs::write(&path, "```mermaid\npie\n    \"A\" : 1\n```\n").unwrap();
+
+    let output = run_cli(&[
+        "render",
+        "--from-markdown",
+        path.to_str().unwrap(),
+        "--gallery",
+        "--format",
+        "mermaid",
+    ]);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("--gallery needs svg or png"));
+    assert!(!dir.path().join("README-1.mmd").exists());
+}
+
+#[test]
+fn cli_layout_elk() {
+    let output = run_cli(&[
+        "flowchart",
+        "--node",
+        "A:Start",
+        "--layout",
+        "elk",
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("'defaultRenderer': 'elk'"));
+}
+
+#[test]
+fn cli_transparent_conflicts_with_background_color() {
+    let output = run_cli(&[
+        "pie",
+        "--data",
+        "Dogs:10",
+        "--transparent",
+        "--background-color",
+        "#ffffff",
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+
+    assert!(!output.status.success());
+}
+
+#[test]
+fn cli_er_attribute_length_and_custom_type() {
+    let output = run_cli(&[
+        "er",
+        "--entity",
+        "User:name:string(255),payload:jsonb",
+        "--allow-custom-types",
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("string(255) name"));
+    assert!(stdout.contains("jsonb payload"));
+}
+
+#[test]
+fn cli_sequence_participant_spec_keeps_declaration_order() {
+    let output = run_cli(&[
+        "sequence",
+        "--participant",
+        "api:API",
+        "--participant",
+        "user:User:actor",
+        "--participant",
+        "db",
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    let api = stdout.find("participant api as API").unwrap();
+    let user = stdout.find("actor user as User").unwrap();
+    let db = stdout.find("participant db").unwrap();
+    assert!(api < user && user < db);
+}
+
+#[test]
+fn cli_sequence_participant_spec_escaped_colon() {
+    let output = run_cli(&[
+        "sequence",
+        "--participant",
+        "svc:Role\\:actor",
+        "--participant",
+        "api:API: v2:actor",
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("participant svc as Role#58;actor"));
+    assert!(stdout.contains("actor api as API#58; v2"));
+}
+
+#[test]
+fn cli_flowchart_indent_tabs() {
+    let output = run_cli(&[
+        "flowchart",
+        "--node",
+        "A:Start",
+        "--node",
+        "B:End",
+        "--link",
+        "A->B",
+        "--indent",
+        "tabs",
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("\ta[\"Start\"]"));
+    assert!(!stdout.contains("    a"));
+}
+
+#[test]
+fn cli_config_style_frontmatter() {
+    let output = run_cli(&[
+        "pie",
+        "--data",
+        "A:50",
+        "--config-style",
+        "frontmatter",
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.starts_with("---\nconfig:\n  theme: "));
+    assert!(!stdout.contains("%%{init"));
+}
+
+#[test]
+fn cli_list_shapes() {
+    let output = run_cli(&["list", "shapes"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("stadium"));
+    assert!(stdout.contains("diamond"));
+}
+
+#[test]
+fn cli_exit_codes_by_error_category() {
+    let output = run_cli(&[
+        "pie", "--data", "invalid", "--format", "mermaid", "--stdout",
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let dir = tempfile::tempdir().unwrap();
+    let missing = dir.path().join("missing.yaml");
+    let output = run_cli(&[
+        "render",
+        "--input",
+        missing.to_str().unwrap(),
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+    assert_eq!(output.status.code(), Some(4));
+
+    let malformed = dir.path().join("bad.yaml");
+    std::fs::write(&malformed, "type: pie\ndata: [unclosed\n").unwrap();
+    let output = run_cli(&[
+        "render",
+        "--input",
+        malformed.to_str().unwrap(),
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+    assert_eq!(output.status.code(), Some(5));
+
+    // Usage errors from clap share code 2 with invalid specs
+    let output = run_cli(&["pie", "--no-such-flag"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn cli_flowchart_escaped_colon_in_label() {
+    let output = run_cli(&[
+        "flowchart",
+        "--node",
+        r"A:Time\: 10\:30:stadium",
+        "--node",
+        r"ns\:B:End",
+        "--link",
+        r"A->ns\:B",
+        "--format",
+        "mermaid",
+        "--stdout",
+    ]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("a([\"Time: 10:30\"])"));
+    assert!(stdout.contains("ns_b[\"End\"]"));
+    assert!(stdout.contains("a --> ns_b"));
+}
@@ -219,3 +219,26 @@ fn er_diagram_many_to_many() {
     assert!(mermaid.contains("Student"));
     assert!(mermaid.contains("Course"));
 }
+
+#[test]
+fn er_sort_stable_ignores_insertion_order() {
+    use mermaid_rs::FormatOptions;
+
+    let forward = ERDiagram::builder()
+        .entity_simple("CUSTOMER")
+        .entity_simple("ORDER")
+        .one_to_many("CUSTOMER", "ORDER", Some("places"))
+        .one_to_many("ORDER", "LINE_ITEM", Some("contains"))
+        .build();
+    let shuffled = ERDiagram::builder()
+        .entity_simple("ORDER")
+        .entity_simple("CUSTOMER")
+        .one_to_many("ORDER", "LINE_ITEM", Some("contains"))
+        .one_to_many("CUSTOMER", "ORDER", Some("places"))
+        .build();
+
+    let options = FormatOptions::new().sort_stable(true);
+    let mermaid = forward.to_mermaid_with(&options);
+    assert_eq!(mermaid, shuffled.to_mermaid_with(&options));
+    assert!(mermaid.find("CUSTOMER").unwrap() < mermaid.find("\tORDER").unwrap());
+}
@@ -1,7 +1,7 @@
 mod common;
 
 use mermaid_rs::core::Diagram;
-use mermaid_rs::diagrams::flowchart::{FlowChart, LinkStyle, Node, NodeShape, Subgraph};
+use mermaid_rs::diagrams::flowchart::{FlowChart, Link, LinkStyle, Node, NodeShape, Subgraph};
 use mermaid_rs::Direction;
 
 #[test]
@@ -208,3 +208,107 @@ fn flowchart_build_script_with_theme() {
     assert!(script.contains("%%{init:"));
     assert!(script.contains("'theme': 'dark'"));
 }
+
+#[test]
+fn flowchart_to_mermaid_with_tabs() {
+    use mermaid_rs::{FormatOptions, IndentStyle};
+
+    let chart = FlowChart::builder()
+        .node_simple("A", "Start")
+        .node_simple("B", "End")
+        .link_simple("A", "B")
+        .build();
+
+    let mermaid = chart.to_mermaid_with(&FormatOptions::new().indent(IndentStyle::Tabs));
+    let body: Vec<&str> = mermaid.lines().skip(1).filter(|l| !l.is_empty()).collect();
+    assert!(!body.is_empty());
+    assert!(body
+        .iter()
+        .all(|l| l.starts_with('\t') && !l.starts_with("\t ")));
+    assert!(!mermaid.contains("    "));
+}
+
+#[test]
+fn flowchart_estimated_complexity_grows_with_size() {
+    let small = FlowChart::builder()
+        .node_simple("A", "A")
+        .node_simple("B", "B")
+        .node_simple("C", "C")
+        .build();
+
+    let mut builder = FlowChart::builder();
+    for i in 0..100 {
+        builder = builder.node_simple(format!("N{}", i), "node");
+        if i > 0 {
+            builder = builder.link_simple(format!("N{}", i - 1), format!("N{}", i));
+        }
+    }
+    let large = builder.build();
+
+    assert_eq!(small.estimated_complexity(), 3);
+    assert!(large.estimated_complexity() > small.estimated_complexity());
+
+    let raw = FlowChart::from_raw_mermaid("flowchart TD\n    A --> B\n    B --> C".to_string());
+    assert_eq!(raw.estimated_complexity(), 2);
+}
+
+#[test]
+fn flowchart_link_style_default_and_many() {
+    use mermaid_rs::Style;
+
+    let chart = FlowChart::builder()
+        .node_simple("A", "A")
+        .node_simple("B", "B")
+        .node_simple("C", "C")
+        .link_simple("A", "B")
+        .link_simple("B", "C")
+        .link_style_default(Style::new().stroke("#999"))
+        .link_style_many(vec![0, 1], Style::new().stroke("#f00"))
+        .build();
+
+    let mermaid = chart.to_mermaid();
+    assert!(mermaid.contains("    linkStyle default stroke:#999\n"));
+    assert!(mermaid.contains("    linkStyle 0,1 stroke:#f00\n"));
+}
+
+#[test]
+fn flowchart_sort_stable_ignores_insertion_order() {
+    use mermaid_rs::{FormatOptions, Style};
+
+    let forward = FlowChart::builder()
+        .node_simple("A", "Start")
+        .node_simple("B", "Middle")
+        .node_simple("C", "End")
+        .link_simple("A", "B")
+        .link_simple("B", "C")
+        .link_style(1, Style::new().stroke("#f00"))
+        .build();
+    let shuffled = FlowChart::builder()
+        .node_simple("C", "End")
+        .node_simple("A", "Start")
+        .node_simple("B", "Middle")
+        .link_simple("B", "C")
+        .link_simple("A", "B")
+        .link_style(0, Style::new().stroke("#f00"))
+        .build();
+
+    let options = FormatOptions::new().sort_stable(true);
+    let mermaid = forward.to_mermaid_with(&options);
+    assert_eq!(mermaid, shuffled.to_mermaid_with(&options));
+    assert!(mermaid.contains("    b --> c\n    linkStyle 1 stroke:#f00"));
+
+    // Default keeps insertion order
+    assert_ne!(forward.to_mermaid(), shuffled.to_mermaid());
+}
+
+#[test]
+fn flowchart_animated_edge() {
+    let chart = FlowChart::builder()
+        .node_simple("A", "Start")
+        .node_simple("B", "End")
+        .link(Link::new("A", "B").with_id("e1").with_class("animate"))
+        .build();
+
+    let mermaid = chart.to_mermaid();
+    assert!(mermaid.contains("    a e1@--> b\n    class e1 animate\n"));
+}
@@ -1,6 +1,7 @@
 mod common;
 
 use mermaid_rs::render::{MermaidClient, RenderOptions};
+use mermaid_rs::{Diagram, MermaidError};
 use wiremock::matchers::{method, path_regex, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
@@ -28,6 +29,7 @@ async fn render_png_with_mock_server() {
 
     Mock::given(method("GET"))
         .and(path_regex(r"^/img/.*"))
+        .and(query_param("type", "png"))
         .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0x89, 0x50, 0x4E, 0x47]))
         .mount(&mock_server)
         .await;
@@ -79,6 +81,61 @@ async fn render_server_error() {
     assert!(result.is_err());
 }
 
+#[tokio::test]
+async fn render_unreachable_server() {
+    // Reserve a local port and release it so connections are refused
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+
+    let client = MermaidClient::new(Some(format!("http://127.0.0.1:{}", port)));
+    let chart = common::simple_pie_chart();
+
+    let result = client.render_svg(&chart, &RenderOptions::default()).await;
+    assert!(matches!(result, Err(MermaidError::NetworkUnreachable(_))));
+}
+
+#[tokio::test]
+async fn render_png_rejects_html_body() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/img/.*"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string("<html><body>Rate limited</body></html>"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = MermaidClient::new(Some(mock_server.uri()));
+    let chart = common::simple_pie_chart();
+
+    let result = client.render_png(&chart, &RenderOptions::default()).await;
+    match result {
+        Err(MermaidError::RenderFailed(msg)) => assert!(msg.contains("PNG")),
+        other => panic!("expected RenderFailed, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn render_svg_rejects_html_body() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/svg/.*"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("<!DOCTYPE html><html></html>"))
+        .mount(&mock_server)
+        .await;
+
+    let client = MermaidClient::new(Some(mock_server.uri()));
+    let chart = common::simple_pie_chart();
+
+    let result = client.render_svg(&chart, &RenderOptions::default()).await;
+    assert!(matches!(result, Err(MermaidError::RenderFailed(_))));
+}
+
 #[tokio::test]
 async fn render_from_raw_script() {
     let mock_server = MockServer::start().await;
@@ -141,3 +198,72 @@ async fn render_svg_real_server() {
     assert!(svg.contains("<svg"));
     assert!(svg.contains("</svg>"));
 }
+
+#[tokio::test]
+async fn render_with_url_returns_requested_url() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/svg/.*"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("<svg></svg>"))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/img/.*"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0x89, 0x50, 0x4E, 0x47]))
+        .mount(&mock_server)
+        .await;
+
+    let client = MermaidClient::new(Some(mock_server.uri()));
+    let chart = common::simple_pie_chart();
+    let options = RenderOptions::new().width(640);
+
+    let (svg, url) = client.render_svg_with_url(&chart, &options).await.unwrap();
+    assert!(svg.contains("<svg"));
+    assert_eq!(url, client.build_render_url(&chart, "svg", &options));
+
+    let (png, url) = client.render_png_with_url(&chart, &options).await.unwrap();
+    assert_eq!(&png[0..4], &[0x89, 0x50, 0x4E, 0x47]);
+    assert_eq!(url, client.build_render_url(&chart, "img", &options));
+}
+
+#[tokio::test]
+async fn stream_png_into_writer() {
+    let mock_server = MockServer::start().await;
+    let body: Vec<u8> = [0x89, 0x50, 0x4E, 0x47]
+        .into_iter()
+        .chain((0..64 * 1024).map(|i| (i % 251) as u8))
+        .collect();
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/img/.*"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(body.clone()))
+        .mount(&mock_server)
+        .await;
+
+    let client = MermaidClient::new(Some(mock_server.uri()));
+    let chart = common::simple_pie_chart();
+    let mut writer: Vec<u8> = Vec::new();
+
+    let written = client
+        .stream_png_from_script(
+            &chart.build_script(),
+            &RenderOptions::default(),
+            &mut writer,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(written, body.len() as u64);
+    assert_eq!(writer, body);
+}
+
+#[tokio::test]
+async fn render_rejects_scale_without_size() {
+    let client = MermaidClient::new(Some("http://127.0.0.1:9".to_string()));
+    let chart = common::simple_pie_chart();
+    let options = RenderOptions::new().scale(2.0);
+
+    let result = client.render_svg(&chart, &options).await;
+    assert!(matches!(result, Err(MermaidError::InvalidInput(_))));
+}
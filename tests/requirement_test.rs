@@ -144,6 +144,26 @@ fn requirement_diagram_from_json() {
     assert!(mermaid.contains("element Login Form"));
 }
 
+#[test]
+fn requirement_diagram_reversed_relationship() {
+    let diagram = RequirementDiagram::builder()
+        .requirement_simple("REQ-001", "Parent", None)
+        .requirement_simple("REQ-002", "Child", None)
+        .relationship(ReqRelationship::derives("Child", "Parent").with_reversed())
+        .build();
+    assert!(diagram
+        .to_mermaid()
+        .contains("    Child <- derives - Parent\n"));
+
+    let json = r#"{
+        "relationships": [
+            {"from": "Child", "to": "Parent", "type": "traces", "reversed": true}
+        ]
+    }"#;
+    let diagram = RequirementDiagram::from_json(json).unwrap();
+    assert!(diagram.to_mermaid().contains("Child <- traces - Parent"));
+}
+
 #[test]
 fn requirement_diagram_from_yaml() {
     let yaml = r#"
@@ -112,6 +112,32 @@ fn sequence_with_logic_alt() {
     assert!(mermaid.contains("end"));
 }
 
+#[test]
+fn sequence_with_logic_critical_options() {
+    let diagram = SequenceDiagram::builder()
+        .participant_simple("Service")
+        .participant_simple("DB")
+        .logic(
+            Logic::critical("Establish a connection to the DB")
+                .with_message(Message::new("Service", "DB").with_text("connect"))
+                .with_option(
+                    "Network timeout",
+                    vec![Message::new("Service", "Service").with_text("Log error")],
+                )
+                .with_else_condition(
+                    "Credentials rejected",
+                    vec![Message::new("Service", "Service").with_text("Log different error")],
+                ),
+        )
+        .build();
+    let mermaid = diagram.to_mermaid();
+
+    assert!(mermaid.contains("critical Establish a connection to the DB"));
+    assert!(mermaid.contains("option Network timeout"));
+    assert!(mermaid.contains("option Credentials rejected"));
+    assert!(!mermaid.contains("else"));
+}
+
 #[test]
 fn sequence_with_logic_loop() {
     let diagram = SequenceDiagram::builder()
@@ -250,3 +276,21 @@ fn sequence_build_script_with_theme() {
     assert!(script.contains("%%{init:"));
     assert!(script.contains("'theme': 'dark'"));
 }
+
+#[test]
+fn sequence_sort_stable_orders_participants_only() {
+    use mermaid_rs::FormatOptions;
+
+    let diagram = SequenceDiagram::builder()
+        .participant_simple("Server")
+        .participant_simple("Client")
+        .message_simple("Server", "Client", "ready")
+        .message_simple("Client", "Server", "request")
+        .build();
+
+    let mermaid = diagram.to_mermaid_with(&FormatOptions::new().sort_stable(true));
+    assert!(
+        mermaid.starts_with("sequenceDiagram\n    participant Client\n    participant Server\n")
+    );
+    assert!(mermaid.find("ready").unwrap() < mermaid.find("request").unwrap());
+}
@@ -97,7 +97,7 @@ fn state_diagram_with_choice() {
     let mermaid = diagram.to_mermaid();
 
     assert!(mermaid.contains("<<choice>>"));
-    assert!(mermaid.contains("decision --> Valid: is valid"));
+    assert!(mermaid.contains("decision --> valid: is valid"));
 }
 
 #[test]